@@ -4,8 +4,9 @@ extern crate syn;
 #[macro_use]
 extern crate quote;
 use proc_macro::{TokenStream as StdStream, TokenTree as StdTree};
-use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
 #[proc_macro_attribute]
+#[allow(clippy::single_match)]
 pub fn inherit(attr: StdStream, input: StdStream) -> StdStream {
     let mut out: TokenStream = input.into();
     for item in attr.into_iter() {
@@ -52,7 +53,8 @@ fn func(input: TokenStream) -> TokenStream {
         input,
         "pub id: Option<Identifier>,
     pub params: Vec<Pattern>,
-    pub generator: bool,",
+    pub generator: bool,
+    pub _async: bool,",
     )
 }
 
@@ -80,6 +82,7 @@ fn mod_spec(input: TokenStream, import: bool) -> TokenStream {
     impl_node(updated, &name, &kind)
 }
 
+#[allow(clippy::single_match)]
 fn inherit_node(input: TokenStream) -> (String, TokenStream) {
     let mut at_name = false;
     let mut name = String::new();
@@ -96,6 +99,7 @@ fn inherit_node(input: TokenStream) -> (String, TokenStream) {
     (name, updated)
 }
 
+#[allow(clippy::single_match, clippy::collapsible_match)]
 fn add_props(input: TokenStream, props: &str) -> TokenStream {
     input
         .into_iter()
@@ -104,6 +108,15 @@ fn add_props(input: TokenStream, props: &str) -> TokenStream {
                 proc_macro2::TokenTree::Group(ref grp) => {
                     if grp.delimiter() == Delimiter::Brace {
                         let mut extended = grp.stream();
+                        let needs_comma = match extended.clone().into_iter().last() {
+                            Some(TokenTree::Punct(ref p)) => p.as_char() != ',',
+                            Some(_) => true,
+                            None => false,
+                        };
+                        if needs_comma {
+                            extended
+                                .extend(Some(TokenTree::Punct(Punct::new(',', Spacing::Alone))));
+                        }
                         let extension: StdStream = props.parse().unwrap();
                         let extension: TokenStream = extension.into();
                         extended.extend(extension);
@@ -117,18 +130,205 @@ fn add_props(input: TokenStream, props: &str) -> TokenStream {
 }
 
 fn impl_node(input: TokenStream, name: &str, kind: &str) -> TokenStream {
+    let fields = child_fields(input.clone());
     let name = Ident::new(name, Span::call_site());
     let kind = Ident::new(kind, Span::call_site());
+    let children = if fields.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+                #(Children::each_node(&self.#fields, f);)*
+            }
+        }
+    };
     quote! {
         #input
         impl Node for #name {
             fn loc(&self) -> SourceLocation {
-                self.loc.clone()
+                self.loc
             }
 
             fn kind(&self) -> NodeKind {
                 NodeKind::#kind
             }
+
+            #children
+        }
+
+        impl Children for #name {
+            fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+                f(self);
+            }
         }
     }
 }
+
+fn child_fields(input: TokenStream) -> Vec<Ident> {
+    let input: syn::DeriveInput = syn::parse2(input).expect("inherit targets must be structs");
+    match input.data {
+        syn::Data::Struct(ref data) => data
+            .fields
+            .iter()
+            .filter_map(|f| f.ident.clone())
+            .filter(|ident| ident != "loc")
+            .collect(),
+        _ => panic!("inherit targets must be structs"),
+    }
+}
+
+/// Compare and hash every field except through `SourceLocation`,
+/// whose `Structural` impl treats all locations as equal.
+///
+/// A struct field marked `#[structural(name)]` holds a name that isn't
+/// a binding or reference, like a label, and is always compared by
+/// name. `#[structural(name_unless = "computed")]` does the same
+/// unless the `computed` field is true.
+#[proc_macro_derive(Structural, attributes(structural))]
+pub fn structural(input: StdStream) -> StdStream {
+    let input: syn::DeriveInput = syn::parse(input).expect("Structural targets must parse");
+    let name = &input.ident;
+    let (eq, hash) = match input.data {
+        syn::Data::Struct(ref data) => {
+            let (pat_a, fields_a) = bind_fields(quote!(#name), &data.fields, "a");
+            let (pat_b, fields_b) = bind_fields(quote!(#name), &data.fields, "b");
+            let options = field_options(&data.fields, &fields_a);
+            let eq_a = fields_a.iter();
+            let eq_options = options.iter();
+            let eq = quote! {
+                match (self, other) {
+                    (&#pat_a, &#pat_b) => true #(&& Structural::structural_eq(#eq_a, #fields_b, #eq_options))*,
+                }
+            };
+            let hash = quote! {
+                match *self {
+                    #pat_a => {
+                        #(Structural::structural_hash(#fields_a, hasher, #options);)*
+                    }
+                }
+            };
+            (eq, hash)
+        }
+        syn::Data::Enum(ref data) => {
+            let mut eq_arms = Vec::new();
+            let mut hash_arms = Vec::new();
+            for (idx, variant) in data.variants.iter().enumerate() {
+                let path = {
+                    let ident = &variant.ident;
+                    quote!(#name::#ident)
+                };
+                let (pat_a, fields_a) = bind_fields(path.clone(), &variant.fields, "a");
+                let (pat_b, fields_b) = bind_fields(path, &variant.fields, "b");
+                let idx = idx as u32;
+                let eq_a = fields_a.iter();
+                eq_arms.push(quote! {
+                    (&#pat_a, &#pat_b) => true #(&& Structural::structural_eq(#eq_a, #fields_b, options))*,
+                });
+                hash_arms.push(quote! {
+                    #pat_a => {
+                        ::std::hash::Hasher::write_u32(hasher, #idx);
+                        #(Structural::structural_hash(#fields_a, hasher, options);)*
+                    }
+                });
+            }
+            let fallback = if data.variants.len() > 1 {
+                quote!(_ => false,)
+            } else {
+                quote!()
+            };
+            let eq = quote! {
+                match (self, other) {
+                    #(#eq_arms)*
+                    #fallback
+                }
+            };
+            let hash = quote! {
+                match *self {
+                    #(#hash_arms)*
+                }
+            };
+            (eq, hash)
+        }
+        syn::Data::Union(_) => panic!("Structural can't be derived for unions"),
+    };
+    let out = quote! {
+        impl Structural for #name {
+            fn structural_eq(&self, other: &Self, options: StructuralOptions) -> bool {
+                let _ = options;
+                #eq
+            }
+
+            fn structural_hash(&self, hasher: &mut StableHasher, options: StructuralOptions) {
+                let _ = (&hasher, options);
+                #hash
+            }
+        }
+    };
+    out.into()
+}
+
+/// The options to compare each field with, given the names its
+/// struct is bound to
+fn field_options(fields: &syn::Fields, names: &[Ident]) -> Vec<TokenStream> {
+    let exact = quote!(options.keep_names());
+    fields
+        .iter()
+        .map(|field| {
+            let mut ret = quote!(options);
+            for attr in field.attrs.iter() {
+                let list = match attr.parse_meta() {
+                    Ok(syn::Meta::List(ref list)) if list.ident == "structural" => list.clone(),
+                    _ => continue,
+                };
+                for item in list.nested.iter() {
+                    ret = match *item {
+                        syn::NestedMeta::Meta(syn::Meta::Word(ref word)) if word == "name" => {
+                            exact.clone()
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(ref nv))
+                            if nv.ident == "name_unless" =>
+                        {
+                            let flag = match nv.lit {
+                                syn::Lit::Str(ref s) => s.value(),
+                                _ => panic!("name_unless takes the name of a field"),
+                            };
+                            let idx = fields
+                                .iter()
+                                .position(|f| f.ident.as_ref().is_some_and(|i| i == &flag))
+                                .expect("name_unless takes the name of a field");
+                            let flag = &names[idx];
+                            quote!(if *#flag { options } else { #exact })
+                        }
+                        _ => panic!("unknown structural attribute"),
+                    };
+                }
+            }
+            ret
+        })
+        .collect()
+}
+
+/// A pattern binding every field of a struct or variant by ref, and
+/// the names it binds them to
+fn bind_fields(path: TokenStream, fields: &syn::Fields, prefix: &str) -> (TokenStream, Vec<Ident>) {
+    let names: Vec<Ident> = (0..fields.iter().count())
+        .map(|idx| Ident::new(&format!("{}{}", prefix, idx), Span::call_site()))
+        .collect();
+    let pat = match *fields {
+        syn::Fields::Named(ref named) => {
+            let idents: Vec<&Ident> = named
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref())
+                .collect();
+            let binds = names.iter();
+            quote!(#path { #(#idents: ref #binds),* })
+        }
+        syn::Fields::Unnamed(_) => {
+            let binds = names.iter();
+            quote!(#path(#(ref #binds),*))
+        }
+        syn::Fields::Unit => quote!(#path),
+    };
+    (pat, names)
+}
@@ -3,12 +3,60 @@ extern crate inherit;
 pub trait Node {
     fn loc(&self) -> SourceLocation;
     fn kind(&self) -> NodeKind;
+    fn for_each_child<'a>(&'a self, _f: &mut dyn FnMut(&'a dyn Node)) {}
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = Vec::new();
+        self.for_each_child(&mut |child| children.push(child));
+        children
+    }
 }
+pub trait Children {
+    fn each_node<'a>(&'a self, _f: &mut dyn FnMut(&'a dyn Node)) {}
+}
+impl Children for bool {}
 pub enum NodeKind {
     Thing,
+    Parent,
+    Derived,
 }
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SourceLocation;
 
 #[inherit(Node)]
 pub struct Thing {}
+
+#[inherit(Node)]
+pub struct Parent {
+    pub thing: Thing,
+    pub flag: bool,
+}
+
+#[test]
+fn children_skip_loc_and_leaves() {
+    let parent = Parent {
+        thing: Thing {
+            loc: SourceLocation,
+        },
+        flag: true,
+        loc: SourceLocation,
+    };
+    assert_eq!(parent.children().len(), 1);
+    assert!(parent.thing.children().is_empty());
+}
+
+#[inherit(Node)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Derived {
+    pub flag: bool
+}
+
+#[test]
+fn derives_see_generated_fields() {
+    let derived = Derived {
+        flag: false,
+        loc: SourceLocation,
+    };
+    let copy = derived.clone();
+    assert_eq!(copy, derived);
+    assert_eq!(copy.loc, SourceLocation);
+}
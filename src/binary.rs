@@ -0,0 +1,630 @@
+//! A compact binary encoding of a `Program`, for caching trees
+//! on disk.
+//!
+//! An encoding starts with the bytes `RSTB` and the format `VERSION`,
+//! followed by a table of every distinct string in the tree and
+//! then the tree itself. Nodes are written field by field with enum
+//! variants as fixed tags, strings as indexes into the table and
+//! each `SourceLocation` as the difference from the one written
+//! before it. Every integer is an unsigned LEB128 varint, differences
+//! are zigzag encoded first.
+//!
+//! `decode` reads straight from a byte slice, so a memory-mapped
+//! cache file can be decoded without copying it first. It recurses
+//! once per level of the tree, and gives up with `Error::TooDeep`
+//! past `MAX_DEPTH` levels instead of overflowing the stack.
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::num::NonZeroU64;
+
+use crate::expression::{self, *};
+use crate::module::*;
+use crate::node::{FileId, Position, Range, SourceLocation};
+use crate::statement::*;
+use crate::{
+    Array, Assignment, AssignmentProperty, Class, ClassBody, Directive, Function, MethodDefinition,
+    MethodKind, Object, Pattern, Program, ProgramPart, RestElement, SourceType,
+};
+
+const MAGIC: &[u8; 4] = b"RSTB";
+/// The version of the format `encode` writes, bumped whenever a
+/// change to the tree changes the encoding
+pub const VERSION: u64 = 1;
+/// How deeply nested expressions, statements and other enum nodes
+/// `decode` accepts. Each level takes up to 16 KB of stack in debug
+/// builds and about 2 KB with optimizations.
+pub const MAX_DEPTH: usize = 512;
+
+pub fn encode(program: &Program) -> Vec<u8> {
+    let mut enc = Encoder::default();
+    program.encode(&mut enc);
+    let mut out = Vec::with_capacity(enc.out.len() + enc.strings.len() * 8 + 16);
+    out.extend_from_slice(MAGIC);
+    write_varint(&mut out, VERSION);
+    write_varint(&mut out, enc.strings.len() as u64);
+    for s in &enc.strings {
+        write_varint(&mut out, s.len() as u64);
+        out.extend_from_slice(s.as_bytes());
+    }
+    out.extend_from_slice(&enc.out);
+    out
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Program, Error> {
+    let mut dec = Decoder {
+        bytes,
+        pos: 0,
+        strings: Vec::new(),
+        last: [0; 6],
+        depth: 0,
+    };
+    if !bytes.starts_with(MAGIC) {
+        return Err(Error::NotBinary);
+    }
+    dec.pos = MAGIC.len();
+    let version = dec.varint()?;
+    if version != VERSION {
+        return Err(Error::Version(version));
+    }
+    let count = dec.varint()?;
+    for _ in 0..count {
+        let len = dec.varint()? as usize;
+        let raw = dec.take(len)?;
+        let s = ::std::str::from_utf8(raw).map_err(|_| Error::Utf8)?;
+        dec.strings.push(s);
+    }
+    let program = Program::decode(&mut dec)?;
+    if dec.pos != bytes.len() {
+        return Err(Error::TrailingBytes);
+    }
+    Ok(program)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The bytes don't start with the `RSTB` header
+    NotBinary,
+    /// The bytes were encoded with a different format version
+    Version(u64),
+    /// The bytes ended part way through the tree
+    UnexpectedEnd,
+    /// A tag that isn't one of the variants of `ty`
+    InvalidTag { ty: &'static str, tag: u64 },
+    /// A string index past the end of the string table
+    InvalidString(u64),
+    /// A string in the table isn't valid UTF-8
+    Utf8,
+    /// A location with a line of 0 or an out of range offset
+    InvalidLocation,
+    /// There are bytes left over after the tree
+    TrailingBytes,
+    /// The tree is nested more than `MAX_DEPTH` levels deep
+    TooDeep,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotBinary => write!(f, "not an encoded tree"),
+            Error::Version(version) => write!(
+                f,
+                "encoded with format version {}, expected {}",
+                version, VERSION
+            ),
+            Error::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Error::InvalidTag { ty, tag } => write!(f, "invalid tag {} for {}", tag, ty),
+            Error::InvalidString(idx) => write!(f, "invalid string index {}", idx),
+            Error::Utf8 => write!(f, "string table isn't valid UTF-8"),
+            Error::InvalidLocation => write!(f, "invalid source location"),
+            Error::TrailingBytes => write!(f, "trailing bytes after the tree"),
+            Error::TooDeep => write!(f, "tree nested more than {} levels deep", MAX_DEPTH),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+#[derive(Default)]
+struct Encoder<'a> {
+    out: Vec<u8>,
+    strings: Vec<&'a str>,
+    indexes: HashMap<&'a str, u64>,
+    /// The parts of the last location written
+    last: [u64; 6],
+}
+
+impl<'a> Encoder<'a> {
+    fn varint(&mut self, value: u64) {
+        write_varint(&mut self.out, value);
+    }
+
+    fn string(&mut self, s: &'a str) {
+        let next = self.strings.len() as u64;
+        let idx = *self.indexes.entry(s).or_insert(next);
+        if idx == next {
+            self.strings.push(s);
+        }
+        self.varint(idx);
+    }
+
+    fn loc(&mut self, loc: &SourceLocation) {
+        self.varint(loc.source.map_or(0, |id| id.index() as u64 + 1));
+        let parts = loc_parts(loc);
+        for (part, last) in parts.iter().zip(self.last.iter()) {
+            let delta = part.wrapping_sub(*last) as i64;
+            write_varint(&mut self.out, ((delta << 1) ^ (delta >> 63)) as u64);
+        }
+        self.last = parts;
+    }
+}
+
+fn loc_parts(loc: &SourceLocation) -> [u64; 6] {
+    [
+        loc.start.line.get(),
+        loc.start.column,
+        loc.end.line.get(),
+        loc.end.column,
+        loc.range.start as u64,
+        loc.range.end as u64,
+    ]
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    strings: Vec<&'a str>,
+    last: [u64; 6],
+    depth: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn enter(&mut self) -> Result<(), Error> {
+        if self.depth >= MAX_DEPTH {
+            return Err(Error::TooDeep);
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(Error::UnexpectedEnd)?;
+        let ret = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(ret)
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = *self.bytes.get(self.pos).ok_or(Error::UnexpectedEnd)?;
+            self.pos += 1;
+            if shift < 64 {
+                value |= u64::from(byte & 0x7f) << shift;
+            }
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn string(&mut self) -> Result<&'a str, Error> {
+        let idx = self.varint()?;
+        self.strings
+            .get(idx as usize)
+            .cloned()
+            .ok_or(Error::InvalidString(idx))
+    }
+
+    fn loc(&mut self) -> Result<SourceLocation, Error> {
+        let source = match self.varint()? {
+            0 => None,
+            id if id - 1 > u64::from(u32::MAX) => return Err(Error::InvalidLocation),
+            id => Some(FileId::from_index((id - 1) as u32)),
+        };
+        let mut parts = [0u64; 6];
+        for (idx, part) in parts.iter_mut().enumerate() {
+            let zigzag = self.varint()?;
+            let delta = ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+            *part = self.last[idx].wrapping_add(delta as u64);
+        }
+        self.last = parts;
+        let line = |line| NonZeroU64::new(line).ok_or(Error::InvalidLocation);
+        let offset = |offset: u64| {
+            if offset > usize::MAX as u64 {
+                return Err(Error::InvalidLocation);
+            }
+            Ok(offset as usize)
+        };
+        Ok(SourceLocation {
+            source,
+            start: Position {
+                line: line(parts[0])?,
+                column: parts[1],
+            },
+            end: Position {
+                line: line(parts[2])?,
+                column: parts[3],
+            },
+            range: Range::new(offset(parts[4])?, offset(parts[5])?),
+        })
+    }
+}
+
+trait Codec: Sized {
+    fn encode<'a>(&'a self, enc: &mut Encoder<'a>);
+    fn decode(dec: &mut Decoder) -> Result<Self, Error>;
+}
+
+impl Codec for bool {
+    fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+        enc.out.push(*self as u8);
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+        match dec.varint()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(Error::InvalidTag { ty: "bool", tag }),
+        }
+    }
+}
+
+impl Codec for String {
+    fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+        enc.string(self);
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+        dec.string().map(String::from)
+    }
+}
+
+impl Codec for SourceLocation {
+    fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+        enc.loc(self);
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+        dec.loc()
+    }
+}
+
+impl<T: Codec> Codec for Option<T> {
+    fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+        match *self {
+            None => enc.out.push(0),
+            Some(ref inner) => {
+                enc.out.push(1);
+                inner.encode(enc);
+            }
+        }
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+        match dec.varint()? {
+            0 => Ok(None),
+            1 => T::decode(dec).map(Some),
+            tag => Err(Error::InvalidTag { ty: "Option", tag }),
+        }
+    }
+}
+
+impl<T: Codec> Codec for Vec<T> {
+    fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+        enc.varint(self.len() as u64);
+        for item in self {
+            item.encode(enc);
+        }
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+        let len = dec.varint()?;
+        // every item takes at least a byte, don't trust a length
+        // longer than what's left
+        let mut items = Vec::with_capacity((len as usize).min(dec.bytes.len() - dec.pos));
+        for _ in 0..len {
+            items.push(T::decode(dec)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T: Codec> Codec for Box<T> {
+    fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+        (**self).encode(enc);
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+        T::decode(dec).map(Box::new)
+    }
+}
+
+/// Encode a struct as each of its fields in turn
+macro_rules! record {
+    ($($ty:path { $($field:ident),* $(,)* })*) => {$(
+        impl Codec for $ty {
+            fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+                let Self { $(ref $field),* } = *self;
+                $($field.encode(enc);)*
+            }
+
+            fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+                Ok(Self {
+                    $($field: Codec::decode(dec)?,)*
+                })
+            }
+        }
+    )*};
+}
+
+/// Encode an enum as the tag of its variant followed by the value
+/// it holds, if any
+macro_rules! tagged {
+    ($($ty:ident { $($tag:expr => $variant:ident $(($inner:ident))*),* $(,)* })*) => {$(
+        impl Codec for $ty {
+            fn encode<'a>(&'a self, enc: &mut Encoder<'a>) {
+                match *self {
+                    $($ty::$variant $((ref $inner))* => {
+                        enc.varint($tag);
+                        $($inner.encode(enc);)*
+                    })*
+                }
+            }
+
+            fn decode(dec: &mut Decoder) -> Result<Self, Error> {
+                // every cycle in the tree goes through an enum, so
+                // counting these bounds the recursion
+                dec.enter()?;
+                let ret = match dec.varint()? {
+                    $($tag => {
+                        $(let $inner = Codec::decode(dec)?;)*
+                        Ok($ty::$variant $(($inner))*)
+                    })*
+                    tag => Err(Error::InvalidTag {
+                        ty: stringify!($ty),
+                        tag,
+                    }),
+                };
+                dec.depth -= 1;
+                ret
+            }
+        }
+    )*};
+}
+
+record! {
+    Program { source_type, body, loc }
+    Function { id, params, body, generator, _async, loc }
+    Directive { expression, directive, loc }
+    AssignmentProperty { key, value, kind, method, shorthand, computed }
+    Object { properties, loc }
+    Array { elements, loc }
+    RestElement { argument, loc }
+    Assignment { left, right, loc }
+    Class { id, super_class, body, loc }
+    ClassBody { body, loc }
+    MethodDefinition { key, value, kind, computed, _static, loc }
+}
+
+tagged! {
+    SourceType { 0 => Script, 1 => Module }
+    ProgramPart { 0 => Directive(inner), 1 => Statement(inner), 2 => ModuleDecl(inner) }
+    Pattern {
+        0 => Ident(inner),
+        1 => Object(inner),
+        2 => Array(inner),
+        3 => RestElement(inner),
+        4 => Assignment(inner),
+    }
+    MethodKind { 0 => Constructor, 1 => Method, 2 => Get, 3 => Set }
+}
+
+record! {
+    Identifier { name, loc }
+    Literal { value, loc }
+    RegExpLiteral { regex, loc }
+    RegEx { pattern, flags, loc }
+    This { loc }
+    Super { loc }
+    expression::Array { elements, loc }
+    expression::Object { properties, loc }
+    Property { key, value, kind, method, shorthand, computed, loc }
+    Unary { operator, prefix, argument, loc }
+    Update { operator, argument, prefix, loc }
+    Binary { operator, left, right, loc }
+    expression::Assignment { operator, left, right, loc }
+    Logical { operator, left, right, loc }
+    Member { object, property, computed, optional, loc }
+    Conditional { test, alternate, consequent, loc }
+    Call { callee, arguments, optional, loc }
+    New { callee, arguments, loc }
+    Sequence { expressions, loc }
+    SpreadElement { argument, loc }
+    ArrowFunction { body, id, params, generator, _async, loc }
+    Yield { argument, delegate, loc }
+    Await { argument, loc }
+    Chain { expression, loc }
+    TemplateLiteral { quasis, expressions, loc }
+    TaggedTemplate { tag, quasi, loc }
+    TemplateElement { tail, value, loc }
+    TemplateValue { cooked, raw }
+    MetaProperty { meta, property, loc }
+}
+
+tagged! {
+    Expression {
+        0 => This(inner),
+        1 => Array(inner),
+        2 => Object(inner),
+        3 => Function(inner),
+        4 => Unary(inner),
+        5 => Update(inner),
+        6 => Binary(inner),
+        7 => Assignment(inner),
+        8 => Logical(inner),
+        9 => Member(inner),
+        10 => Conditional(inner),
+        11 => Call(inner),
+        12 => New(inner),
+        13 => Sequence(inner),
+        14 => Spread(inner),
+        15 => ArrowFunction(inner),
+        16 => Yield(inner),
+        17 => Await(inner),
+        18 => Identifier(inner),
+        19 => Literal(inner),
+        20 => TaggedTemplate(inner),
+        21 => Class(inner),
+        22 => Meta(inner),
+        23 => Chain(inner),
+    }
+    LiteralValue {
+        0 => String(inner),
+        1 => Boolean(inner),
+        2 => Null,
+        3 => Number(inner),
+        4 => RegExp(inner),
+        5 => TemplateLiteral(inner),
+    }
+    Spreadable { 0 => Expr(inner), 1 => Spread(inner) }
+    PropertyKind { 0 => Init, 1 => Get, 2 => Set }
+    UnaryOperator {
+        0 => Minus,
+        1 => Plus,
+        2 => Not,
+        3 => Tilde,
+        4 => TypeOf,
+        5 => Void,
+        6 => Delete,
+    }
+    UpdateOperator { 0 => Decrement, 1 => Increment }
+    BinaryOperator {
+        0 => And,
+        1 => GreaterThan,
+        2 => GreaterThanEqual,
+        3 => Div,
+        4 => Equal,
+        5 => In,
+        6 => InstanceOf,
+        7 => LeftShift,
+        8 => LessThan,
+        9 => LessThanEqual,
+        10 => Minus,
+        11 => Mod,
+        12 => NotEqual,
+        13 => Or,
+        14 => Plus,
+        15 => RightShift,
+        16 => StrictEqual,
+        17 => StrictNotEqual,
+        18 => Times,
+        19 => UnsignedRightShift,
+        20 => XOr,
+    }
+    PatOrExpr { 0 => Pat(inner), 1 => Expr(inner) }
+    AssignmentOperator {
+        0 => AddAssign,
+        1 => AndAssign,
+        2 => Assign,
+        3 => DivAssign,
+        4 => LHSAssign,
+        5 => MulAssign,
+        6 => ModAssign,
+        7 => OrAssign,
+        8 => RHSAssign,
+        9 => SubAssign,
+        10 => URHSAssign,
+        11 => XOrAssign,
+    }
+    LogicalOperator { 0 => Or, 1 => And }
+    Parent { 0 => Expr(inner), 1 => Super(inner) }
+    ArrowFunctionBody { 0 => Expr(inner), 1 => Block(inner) }
+}
+
+record! {
+    Block { body, loc }
+    FunctionBody { body, loc }
+    Empty { loc }
+    Debugger { loc }
+    With { object, body, loc }
+    Return { argument, loc }
+    Labeled { label, body, loc }
+    Break { label, loc }
+    Continue { label, loc }
+    If { test, consequent, alternate, loc }
+    Switch { discriminant, cases, loc }
+    SwitchCase { test, consequent, loc }
+    Throw { argument, loc }
+    Try { block, handler, finalizer, loc }
+    CatchClause { param, body, loc }
+    While { test, body, loc }
+    DoWhile { body, test, loc }
+    For { init, test, update, body, loc }
+    ForIn { left, right, body, loc }
+    ForOf { left, right, body, _await, loc }
+    VariableDeclaration { declarations, kind, loc }
+    VariableDeclarator { id, init, loc }
+}
+
+tagged! {
+    Statement {
+        0 => Expr(inner),
+        1 => Block(inner),
+        2 => FuncBody(inner),
+        3 => Empty(inner),
+        4 => Debugger(inner),
+        5 => With(inner),
+        6 => Return(inner),
+        7 => Labeled(inner),
+        8 => Break(inner),
+        9 => Continue(inner),
+        10 => If(inner),
+        11 => Switch(inner),
+        12 => Throw(inner),
+        13 => Try(inner),
+        14 => While(inner),
+        15 => DoWhile(inner),
+        16 => For(inner),
+        17 => ForIn(inner),
+        18 => ForOf(inner),
+        19 => Decl(inner),
+    }
+    FunctionBodyPart { 0 => Directive(inner), 1 => Statement(inner) }
+    ForInit { 0 => Var(inner), 1 => Expr(inner), 2 => Null }
+    ForLeft { 0 => Var(inner), 1 => Pat(inner) }
+    Declaration { 0 => Function(inner), 1 => Variable(inner), 2 => Class(inner) }
+    VariableKind { 0 => Var, 1 => Let, 2 => Const, 3 => Using, 4 => AwaitUsing }
+}
+
+record! {
+    ImportDeclaration { specifiers, source, loc }
+    Import { import, local, loc }
+    ImportDefault { local, loc }
+    ImportNamespace { local, loc }
+    ExportNamed { declaration, specifiers, source, loc }
+    ExportSpecifier { local, exported, loc }
+    ExportDefault { declaration, loc }
+    ExportAll { source, loc }
+}
+
+tagged! {
+    ModuleDeclaration { 0 => Import(inner), 1 => Export(inner) }
+    ImportSpecifier { 0 => Named(inner), 1 => Default(inner), 2 => NameSpace(inner) }
+    ExportDeclaration { 0 => Named(inner), 1 => Default(inner), 2 => All(inner) }
+    ExportDecl { 0 => Decl(inner), 1 => Expr(inner) }
+}
@@ -0,0 +1,1869 @@
+//! Turning a tree back into JavaScript source.
+//!
+//! `print` writes a whole `Program` and `print_node` any single
+//! node. Parentheses are only added where the tree needs them: for
+//! operator precedence, around expressions that would otherwise be
+//! read as a block or declaration at the start of a statement, and
+//! around `in` inside the head of a `for` loop.
+//!
+//! `minify` prints the same tree as compactly as it can be read
+//! back: without whitespace, with the shortest spelling of each
+//! number and string, and without semicolons before a `}` or the end
+//! of the program.
+//!
+//! `print_with` takes `PrintOptions` for the layout. Call arguments
+//! and array and object literals that don't fit in `print_width`
+//! are broken into one item per line. That choice only depends on
+//! the tree, never on where its nodes came from, so printing the
+//! parsed output again gives back the same text.
+//!
+//! `print_with_map` also makes a `SourceMap` from the start of each
+//! printed node back to its `SourceLocation`. Identifiers are mapped
+//! with their name in the original text, where the `SourceRegistry`
+//! holds it, so renamed identifiers can be shown as they were.
+//! Only files added to the registry with their text are mapped, since
+//! source map columns count UTF-16 code units rather than bytes.
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU64;
+
+use crate::expression::{self, *};
+use crate::line_index::LineIndex;
+use crate::module::*;
+use crate::node::{FileId, Node, Position, SourceLocation, SourceRegistry};
+use crate::node_ref::NodeRef;
+use crate::source_map::{Mapping, Original, SourceMap};
+use crate::statement::*;
+use crate::{
+    Array, Assignment, AssignmentProperty, Class, ClassBody, Directive, Function, MethodDefinition,
+    MethodKind, Object, Pattern, Program, ProgramPart, RestElement,
+};
+
+/// Print `program` as JavaScript source
+pub fn print(program: &Program) -> String {
+    let mut printer = Printer::new();
+    printer.program(program);
+    printer.finish()
+}
+
+/// Print any single node as JavaScript source
+pub fn print_node(node: NodeRef) -> String {
+    let mut printer = Printer::new();
+    printer.node(node);
+    printer.finish()
+}
+
+/// Print `program` as compactly as possible
+pub fn minify(program: &Program) -> String {
+    print_with(
+        program,
+        PrintOptions {
+            minify: true,
+            ..PrintOptions::default()
+        },
+    )
+}
+
+/// Print `program` in the style `options` describe
+pub fn print_with(program: &Program, options: PrintOptions) -> String {
+    let mut printer = Printer::with_options(options);
+    printer.program(program);
+    printer.finish()
+}
+
+/// Print `program` along with a source map back to the files in
+/// `registry`. Nodes without a `source`, or whose file was added
+/// without its text, are left unmapped.
+pub fn print_with_map(
+    program: &Program,
+    options: PrintOptions,
+    registry: &SourceRegistry,
+) -> (String, SourceMap) {
+    let mut printer = Printer::with_source_map(options);
+    printer.program(program);
+    printer.finish_with_map(registry)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrintOptions {
+    /// Leave out all whitespace and optional semicolons, and pick the
+    /// shortest form of numbers and strings. The other options have
+    /// no effect when this is set.
+    pub minify: bool,
+    pub indent: Indent,
+    /// The quote strings use, unless the other one needs fewer escapes
+    pub quote: Quote,
+    /// End lists broken over several lines with a comma
+    pub trailing_commas: bool,
+    /// End statements with `;`. Without them, one is put in front
+    /// of any statement that would otherwise continue the last.
+    pub semicolons: bool,
+    /// The width lines are kept to where they can be broken
+    pub print_width: usize,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            minify: false,
+            indent: Indent::Spaces(2),
+            quote: Quote::Double,
+            trailing_commas: false,
+            semicolons: true,
+            print_width: 80,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indent {
+    Spaces(usize),
+    Tabs,
+}
+
+/// How wide a tab is counted when measuring lines
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quote {
+    Double,
+    Single,
+}
+
+impl Quote {
+    fn char(self) -> char {
+        match self {
+            Quote::Double => '"',
+            Quote::Single => '\'',
+        }
+    }
+
+    fn other(self) -> Quote {
+        match self {
+            Quote::Double => Quote::Single,
+            Quote::Single => Quote::Double,
+        }
+    }
+}
+
+/// How tightly an expression binds, loosest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Prec {
+    Lowest,
+    Comma,
+    Assign,
+    Conditional,
+    LogicalOr,
+    LogicalAnd,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Equality,
+    Relational,
+    Shift,
+    Additive,
+    Multiplicative,
+    Prefix,
+    Postfix,
+    Call,
+    Member,
+    Primary,
+}
+
+impl Prec {
+    /// The next tighter level, for the right side of a left
+    /// associative operator
+    fn next(self) -> Prec {
+        match self {
+            Prec::Lowest => Prec::Comma,
+            Prec::Comma => Prec::Assign,
+            Prec::Assign => Prec::Conditional,
+            Prec::Conditional => Prec::LogicalOr,
+            Prec::LogicalOr => Prec::LogicalAnd,
+            Prec::LogicalAnd => Prec::BitOr,
+            Prec::BitOr => Prec::BitXor,
+            Prec::BitXor => Prec::BitAnd,
+            Prec::BitAnd => Prec::Equality,
+            Prec::Equality => Prec::Relational,
+            Prec::Relational => Prec::Shift,
+            Prec::Shift => Prec::Additive,
+            Prec::Additive => Prec::Multiplicative,
+            Prec::Multiplicative => Prec::Prefix,
+            Prec::Prefix => Prec::Postfix,
+            Prec::Postfix => Prec::Call,
+            Prec::Call => Prec::Member,
+            Prec::Member | Prec::Primary => Prec::Primary,
+        }
+    }
+}
+
+fn binary_prec(op: &BinaryOperator) -> Prec {
+    match op {
+        BinaryOperator::Or => Prec::BitOr,
+        BinaryOperator::XOr => Prec::BitXor,
+        BinaryOperator::And => Prec::BitAnd,
+        BinaryOperator::Equal
+        | BinaryOperator::NotEqual
+        | BinaryOperator::StrictEqual
+        | BinaryOperator::StrictNotEqual => Prec::Equality,
+        BinaryOperator::GreaterThan
+        | BinaryOperator::GreaterThanEqual
+        | BinaryOperator::LessThan
+        | BinaryOperator::LessThanEqual
+        | BinaryOperator::In
+        | BinaryOperator::InstanceOf => Prec::Relational,
+        BinaryOperator::LeftShift
+        | BinaryOperator::RightShift
+        | BinaryOperator::UnsignedRightShift => Prec::Shift,
+        BinaryOperator::Plus | BinaryOperator::Minus => Prec::Additive,
+        BinaryOperator::Times | BinaryOperator::Div | BinaryOperator::Mod => Prec::Multiplicative,
+    }
+}
+
+fn prec(expr: &Expression) -> Prec {
+    match expr {
+        Expression::Sequence(_) => Prec::Comma,
+        Expression::Assignment(_)
+        | Expression::ArrowFunction(_)
+        | Expression::Yield(_)
+        | Expression::Spread(_) => Prec::Assign,
+        Expression::Conditional(_) => Prec::Conditional,
+        Expression::Logical(ref logical) => match logical.operator {
+            LogicalOperator::Or => Prec::LogicalOr,
+            LogicalOperator::And => Prec::LogicalAnd,
+        },
+        Expression::Binary(ref binary) => binary_prec(&binary.operator),
+        Expression::Unary(_) | Expression::Await(_) => Prec::Prefix,
+        Expression::Update(ref update) if update.prefix => Prec::Prefix,
+        Expression::Update(_) => Prec::Postfix,
+        Expression::Call(_) | Expression::Chain(_) => Prec::Call,
+        Expression::Member(_) | Expression::New(_) | Expression::TaggedTemplate(_) => Prec::Member,
+        _ => Prec::Primary,
+    }
+}
+
+/// The kinds of first token that change how a statement, arrow
+/// body or for loop head would be parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Start {
+    Brace,
+    Function,
+    Class,
+    Let,
+    Other,
+}
+
+/// The first token `expr` prints, following the left edge of the
+/// expression. Parentheses added for precedence along the way are
+/// ignored, so this can only err towards adding more of them.
+fn first_token(expr: &Expression) -> Start {
+    match expr {
+        Expression::Object(_) => Start::Brace,
+        Expression::Function(_) => Start::Function,
+        Expression::Class(_) => Start::Class,
+        Expression::Identifier(ref ident) if ident.name == "let" => Start::Let,
+        Expression::Binary(ref binary) => first_token(&binary.left),
+        Expression::Logical(ref logical) => first_token(&logical.left),
+        Expression::Conditional(ref conditional) => first_token(&conditional.test),
+        Expression::Assignment(ref assignment) => match assignment.left {
+            PatOrExpr::Expr(ref left) => first_token(left),
+            PatOrExpr::Pat(Pattern::Object(_)) => Start::Brace,
+            PatOrExpr::Pat(Pattern::Ident(ref ident)) if ident.name == "let" => Start::Let,
+            PatOrExpr::Pat(_) => Start::Other,
+        },
+        Expression::Member(ref member) => match member.object {
+            Parent::Expr(ref object) => first_token(object),
+            Parent::Super(_) => Start::Other,
+        },
+        Expression::Call(ref call) => match call.callee {
+            Parent::Expr(ref callee) => first_token(callee),
+            Parent::Super(_) => Start::Other,
+        },
+        Expression::Sequence(ref sequence) => sequence
+            .expressions
+            .first()
+            .map_or(Start::Other, first_token),
+        Expression::Update(ref update) if !update.prefix => first_token(&update.argument),
+        Expression::TaggedTemplate(ref tagged) => first_token(&tagged.tag),
+        Expression::Chain(ref chain) => first_token(&chain.expression),
+        _ => Start::Other,
+    }
+}
+
+/// If `expr` calls something before any member access is wrapped
+/// up, which `new` would otherwise take as its own arguments
+fn has_call(expr: &Expression) -> bool {
+    match expr {
+        Expression::Call(_) => true,
+        Expression::Member(ref member) => match member.object {
+            Parent::Expr(ref object) => has_call(object),
+            Parent::Super(_) => false,
+        },
+        Expression::TaggedTemplate(ref tagged) => has_call(&tagged.tag),
+        _ => false,
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || !c.is_ascii()
+}
+
+/// A JS string literal for `value` quoted with `quote`
+fn quote(value: &str, quote: char) -> String {
+    let mut ret = String::with_capacity(value.len() + 2);
+    ret.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\u{2028}' => ret.push_str("\\u2028"),
+            '\u{2029}' => ret.push_str("\\u2029"),
+            c if c == quote => {
+                ret.push('\\');
+                ret.push(c);
+            }
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\x{:02x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push(quote);
+    ret
+}
+
+/// If the raw text of a string holds `quote` without a backslash
+/// escaping it
+fn has_unescaped(raw: &str, quote: char) -> bool {
+    let mut escaped = false;
+    for c in raw.chars() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return true;
+        }
+    }
+    false
+}
+
+/// Writes JavaScript source for nodes, one at a time, into a buffer
+#[derive(Debug, Default)]
+pub struct Printer {
+    options: PrintOptions,
+    out: String,
+    indent: usize,
+    /// Inside the head of a `for` loop, where a bare `in` would end
+    /// the initializer
+    no_in: bool,
+    /// A statement ended without its `;`, which is still needed
+    /// unless a `}`, the end of the output or a new line that can't
+    /// continue the statement comes next
+    semicolon: bool,
+    /// A line was started since `semicolon` was set
+    broke: bool,
+    /// Lists are being printed on one line to measure them, so
+    /// the lists inside them don't need to be measured too
+    flat: bool,
+    /// Where the last regular expression ended, since flags would
+    /// run into a word right after it
+    regex_end: Option<usize>,
+    /// Where nodes were written, when making a source map
+    marks: Option<Vec<Mark>>,
+    /// The node whose first token is written next
+    mark: Option<Mark>,
+    /// How many lists were started, which numbers them in the order
+    /// they're written
+    groups: usize,
+    /// The lists that are always broken, since text after them
+    /// didn't fit on their line
+    broken: HashSet<usize>,
+    /// The number and start of each list printed on one line in the
+    /// statement being measured
+    flat_groups: Vec<(usize, usize)>,
+}
+/// The start of a node in the output
+#[derive(Clone, Debug)]
+struct Mark {
+    offset: usize,
+    loc: SourceLocation,
+    name: Option<String>,
+}
+
+impl Printer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: PrintOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+    /// The source printed so far
+    pub fn finish(self) -> String {
+        self.out
+    }
+    /// A printer that also records where each node is written
+    pub fn with_source_map(options: PrintOptions) -> Self {
+        Self {
+            marks: Some(Vec::new()),
+            ..Self::with_options(options)
+        }
+    }
+    /// The source printed so far and its source map, which is empty
+    /// unless made `with_source_map`. Only nodes from files the
+    /// registry holds the text of are mapped.
+    pub fn finish_with_map(self, registry: &SourceRegistry) -> (String, SourceMap) {
+        let mut map = SourceMap {
+            sources: registry.iter().map(|(_, f)| f.name.clone()).collect(),
+            sources_content: registry.iter().map(|(_, f)| f.text.clone()).collect(),
+            ..SourceMap::default()
+        };
+        let mut line_indexes: HashMap<FileId, LineIndex> = HashMap::new();
+        let mut names: HashMap<String, u32> = HashMap::new();
+        let (mut line, mut column, mut scanned) = (1, 0, 0);
+        for mark in self.marks.unwrap_or_default() {
+            for c in self.out[scanned..mark.offset].chars() {
+                if c == '\n' {
+                    line += 1;
+                    column = 0;
+                } else {
+                    column += c.len_utf16() as u64;
+                }
+            }
+            scanned = mark.offset;
+            // source map columns count UTF-16 units, which can only be
+            // worked out from the text
+            let (file, text) = match mark.loc.source {
+                Some(file) => match registry.text(file) {
+                    Some(text) => (file, text),
+                    None => continue,
+                },
+                None => continue,
+            };
+            let index = line_indexes
+                .entry(file)
+                .or_insert_with(|| LineIndex::new(text));
+            let start = mark.loc.start;
+            let position = match index.utf16_column(start) {
+                Some(column) => Position { column, ..start },
+                None => continue,
+            };
+            let range = mark.loc.range;
+            let name = mark.name.map(|name| {
+                let original = text
+                    .get(range.start..range.end)
+                    .filter(|original| !original.is_empty() && original.chars().all(is_ident_char))
+                    .map_or(name, str::to_string);
+                let next = names.len() as u32;
+                *names.entry(original.clone()).or_insert_with(|| {
+                    map.names.push(original);
+                    next
+                })
+            });
+            map.mappings.push(Mapping {
+                generated: Position {
+                    line: NonZeroU64::new(line).unwrap(),
+                    column,
+                },
+                original: Some(Original {
+                    source: file.index() as u32,
+                    position,
+                    name,
+                }),
+            });
+        }
+        (self.out, map)
+    }
+    /// Map the next token written back to `loc`
+    fn mark(&mut self, loc: SourceLocation, name: Option<&str>) {
+        if self.marks.is_some() && loc.source.is_some() {
+            self.mark = Some(Mark {
+                offset: 0,
+                loc,
+                name: name.map(str::to_string),
+            });
+        }
+    }
+    /// Write text to the output, as the start of any marked node
+    fn write(&mut self, text: &str) {
+        if let Some(mut mark) = self.mark.take() {
+            mark.offset = self.out.len();
+            if let Some(ref mut marks) = self.marks {
+                marks.push(mark);
+            }
+        }
+        self.out.push_str(text);
+    }
+    /// Forget everything written from `start` on
+    fn truncate(&mut self, start: usize) {
+        self.out.truncate(start);
+        if let Some(ref mut marks) = self.marks {
+            while marks.last().is_some_and(|mark| mark.offset >= start) {
+                marks.pop();
+            }
+        }
+    }
+
+    fn ident(&mut self, ident: &Identifier) {
+        self.mark(ident.loc, Some(&ident.name));
+        self.token(&ident.name);
+    }
+    /// Write the `;` left off the last statement if `next` could
+    /// otherwise continue it
+    fn end_statement(&mut self, next: &str) {
+        if !self.semicolon {
+            return;
+        }
+        self.semicolon = false;
+        let continues = next.starts_with(|c| "([`+-/".contains(c));
+        if next != "}" && (!self.broke || continues) {
+            if self.broke {
+                self.out.push(';');
+            } else {
+                // right after the statement, so `b ;else` reads `b; else`
+                let end = self.out.trim_end_matches(' ').len();
+                self.out.insert(end, ';');
+            }
+        }
+    }
+    /// Write text that is never merged with what comes before it
+    fn raw(&mut self, text: &str) {
+        self.end_statement(text);
+        self.write(text);
+    }
+    /// Write a token, with a space before it if it would otherwise
+    /// run into the one before it
+    fn token(&mut self, token: &str) {
+        self.end_statement(token);
+        if let (Some(last), Some(first)) = (self.out.chars().last(), token.chars().next()) {
+            let merges = (is_ident_char(last) && is_ident_char(first))
+                || (self.regex_end == Some(self.out.len()) && is_ident_char(first))
+                || (last == '+' && first == '+')
+                || (last == '-' && first == '-')
+                || (last == '/' && (first == '/' || first == '*'))
+                || (self.out.ends_with("--") && first == '>')
+                || (last == '<' && token.starts_with("!--"));
+            if merges {
+                self.out.push(' ');
+            }
+        }
+        self.write(token);
+    }
+
+    fn space(&mut self) {
+        if !self.options.minify {
+            self.out.push(' ');
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.options.minify {
+            return;
+        }
+        self.broke = true;
+        self.out.push('\n');
+        for _ in 0..self.indent {
+            match self.options.indent {
+                Indent::Spaces(width) => self.out.extend((0..width).map(|_| ' ')),
+                Indent::Tabs => self.out.push('\t'),
+            }
+        }
+    }
+    /// The end of a statement
+    fn semicolon(&mut self) {
+        if self.options.minify || !self.options.semicolons {
+            self.semicolon = true;
+            self.broke = false;
+        } else {
+            self.out.push(';');
+        }
+    }
+
+    fn comma(&mut self) {
+        self.token(",");
+        self.space();
+    }
+
+    fn list<T, F>(&mut self, items: &[T], mut each: F)
+    where
+        F: FnMut(&mut Self, &T),
+    {
+        for (idx, item) in items.iter().enumerate() {
+            if idx > 0 {
+                self.comma();
+            }
+            each(self, item);
+        }
+    }
+
+    /// Items between `open` and `close`, on one line if they fit in
+    /// the print width and otherwise each on its own line. `last_comma`
+    /// is for an array ending in a hole, which always needs one.
+    fn group<T, F>(&mut self, open: &str, close: &str, items: &[T], last_comma: bool, each: F)
+    where
+        F: Fn(&mut Self, &T),
+    {
+        self.groups += 1;
+        let id = self.groups;
+        self.token(open);
+        if !items.is_empty() && !self.flat && !self.options.minify {
+            let start = self.out.len();
+            let regex_end = self.regex_end;
+            let broken = self.broken.contains(&id);
+            if !broken {
+                self.flat = true;
+                self.list(items, &each);
+                self.flat = false;
+            }
+            if broken || !self.fits(start) {
+                self.truncate(start);
+                self.regex_end = regex_end;
+                // the lists inside are numbered the same either way
+                self.groups = id;
+                self.indent += 1;
+                for (idx, item) in items.iter().enumerate() {
+                    self.newline();
+                    each(self, item);
+                    if idx + 1 < items.len() || self.options.trailing_commas || last_comma {
+                        self.token(",");
+                    }
+                }
+                self.indent -= 1;
+                self.newline();
+            } else {
+                self.flat_groups.push((id, start));
+                if last_comma {
+                    self.token(",");
+                }
+            }
+        } else {
+            self.list(items, &each);
+            if last_comma {
+                self.token(",");
+            }
+        }
+        self.token(close);
+    }
+    /// If the line written up to `start` and then on to the next line
+    /// break stays within the print width, with room for one more
+    /// character to close it
+    fn fits(&self, start: usize) -> bool {
+        self.line_width(start) < self.options.print_width
+    }
+    /// The width of the line `start` is on, as written so far
+    fn line_width(&self, start: usize) -> usize {
+        let line_start = self.out[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = self.out[start..]
+            .find('\n')
+            .map_or(self.out.len(), |idx| start + idx);
+        self.out[line_start..line_end]
+            .chars()
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum()
+    }
+    /// Run `print` for a statement, then again with a list broken
+    /// for as long as text after a list on one line, which it
+    /// couldn't see when it was measured, runs past the print width
+    fn measured<F: Fn(&mut Self)>(&mut self, print: F) {
+        if self.options.minify {
+            return print(self);
+        }
+        let start = self.out.len();
+        let groups = self.groups;
+        let (indent, no_in, semicolon, broke) =
+            (self.indent, self.no_in, self.semicolon, self.broke);
+        let (regex_end, mark) = (self.regex_end, self.mark.clone());
+        let first = self.flat_groups.len();
+        loop {
+            print(self);
+            let width = self.options.print_width;
+            let long = self.flat_groups[first..]
+                .iter()
+                .find(|&&(_, start)| self.line_width(start) > width)
+                .map(|&(id, _)| id);
+            self.flat_groups.truncate(first);
+            // each pass breaks a list that was flat, so this ends
+            let id = match long {
+                Some(id) => id,
+                None => return,
+            };
+            self.broken.insert(id);
+            self.truncate(start);
+            self.groups = groups;
+            self.indent = indent;
+            self.no_in = no_in;
+            self.semicolon = semicolon;
+            self.broke = broke;
+            self.regex_end = regex_end;
+            self.mark = mark.clone();
+        }
+    }
+    /// Run `inner` for a body of statements, whose lists are measured
+    /// on their own lines
+    fn lines<F: FnOnce(&mut Self)>(&mut self, inner: F) {
+        let flat = self.flat;
+        self.flat = false;
+        inner(self);
+        self.flat = flat;
+    }
+
+    fn parens<F: FnOnce(&mut Self)>(&mut self, wrap: bool, inner: F) {
+        if !wrap {
+            return inner(self);
+        }
+        let no_in = self.no_in;
+        self.no_in = false;
+        self.token("(");
+        inner(self);
+        self.token(")");
+        self.no_in = no_in;
+    }
+    /// Print any node
+    pub fn node(&mut self, node: NodeRef) {
+        match node {
+            NodeRef::Program(n) => self.program(n),
+            NodeRef::Directive(n) => self.directive(n),
+            NodeRef::Pattern(n) => self.pattern(n),
+            NodeRef::ObjectPattern(n) => self.object_pattern(n),
+            NodeRef::ArrayPattern(n) => self.array_pattern(n),
+            NodeRef::RestElement(n) => self.rest_element(n),
+            NodeRef::AssignmentPattern(n) => self.assignment_pattern(n),
+            NodeRef::ClassBody(n) => self.class_body(n),
+            NodeRef::MethodDefinition(n) => self.method_definition(n),
+            NodeRef::Expression(n) => self.expression(n),
+            NodeRef::Identifier(n) => self.ident(n),
+            NodeRef::Literal(n) => self.literal(n),
+            NodeRef::RegExpLiteral(n) => self.regex(&n.regex),
+            NodeRef::RegEx(n) => self.regex(n),
+            NodeRef::This(_) => self.token("this"),
+            NodeRef::Super(_) => self.token("super"),
+            NodeRef::ArrayExpression(n) => self.array(n),
+            NodeRef::ObjectExpression(n) => self.object(n),
+            NodeRef::Property(n) => self.property(n),
+            NodeRef::Unary(n) => self.unary(n),
+            NodeRef::Update(n) => self.update(n),
+            NodeRef::Binary(n) => self.binary(n),
+            NodeRef::AssignmentExpression(n) => self.assignment(n),
+            NodeRef::Logical(n) => self.logical(n),
+            NodeRef::Member(n) => self.member(n),
+            NodeRef::Conditional(n) => self.conditional(n),
+            NodeRef::Call(n) => self.call(n),
+            NodeRef::New(n) => self.new_expr(n),
+            NodeRef::Sequence(n) => self.sequence(n),
+            NodeRef::SpreadElement(n) => self.spread(n),
+            NodeRef::ArrowFunction(n) => self.arrow_function(n),
+            NodeRef::Yield(n) => self.yield_expr(n),
+            NodeRef::Await(n) => self.await_expr(n),
+            NodeRef::Chain(n) => self.expr(&n.expression, Prec::Lowest),
+            NodeRef::TemplateLiteral(n) => self.template_literal(n),
+            NodeRef::TaggedTemplate(n) => self.tagged_template(n),
+            NodeRef::TemplateElement(n) => self.raw(&n.value.raw),
+            NodeRef::MetaProperty(n) => self.meta_property(n),
+            NodeRef::Statement(n) => self.statement(n),
+            NodeRef::Block(n) => self.block(n),
+            NodeRef::FunctionBody(n) => self.function_body(n),
+            NodeRef::Empty(_) => self.token(";"),
+            NodeRef::Debugger(_) => self.simple("debugger"),
+            NodeRef::With(n) => self.with(n),
+            NodeRef::Return(n) => self.jump("return", n.argument.as_ref()),
+            NodeRef::Labeled(n) => self.labeled(n),
+            NodeRef::Break(n) => self.label_jump("break", n.label.as_ref()),
+            NodeRef::Continue(n) => self.label_jump("continue", n.label.as_ref()),
+            NodeRef::If(n) => self.if_statement(n),
+            NodeRef::Switch(n) => self.switch(n),
+            NodeRef::SwitchCase(n) => self.switch_case(n),
+            NodeRef::Throw(n) => self.jump("throw", Some(&n.argument)),
+            NodeRef::Try(n) => self.try_statement(n),
+            NodeRef::CatchClause(n) => self.catch_clause(n),
+            NodeRef::While(n) => self.while_statement(n),
+            NodeRef::DoWhile(n) => self.do_while(n),
+            NodeRef::For(n) => self.for_statement(n),
+            NodeRef::ForIn(n) => self.for_in(n),
+            NodeRef::ForOf(n) => self.for_of(n),
+            NodeRef::Declaration(n) => self.declaration(n),
+            NodeRef::VariableDeclaration(n) => {
+                self.variable_declaration(n);
+                self.semicolon();
+            }
+            NodeRef::VariableDeclarator(n) => self.variable_declarator(n),
+            NodeRef::ModuleDeclaration(n) => self.module_declaration(n),
+            NodeRef::ImportDeclaration(n) => self.import_declaration(n),
+            NodeRef::Import(n) => self.import_specifier(n),
+            NodeRef::ImportDefault(n) => self.ident(&n.local),
+            NodeRef::ImportNamespace(n) => self.import_namespace(n),
+            NodeRef::ExportDeclaration(n) => self.export_declaration(n),
+            NodeRef::ExportNamed(n) => self.export_named(n),
+            NodeRef::ExportSpecifier(n) => self.export_specifier(n),
+            NodeRef::ExportDefault(n) => self.export_default(n),
+            NodeRef::ExportAll(n) => self.export_all(n),
+        }
+    }
+
+    pub fn program(&mut self, program: &Program) {
+        for (idx, part) in program.body.iter().enumerate() {
+            if idx > 0 {
+                self.newline();
+            }
+            match part {
+                ProgramPart::Directive(ref directive) => self.directive(directive),
+                ProgramPart::Statement(ref stmt) => self.statement(stmt),
+                ProgramPart::ModuleDecl(ref decl) => self.module_declaration(decl),
+            }
+        }
+        if !program.body.is_empty() && !self.options.minify {
+            self.out.push('\n');
+        }
+    }
+
+    fn directive(&mut self, directive: &Directive) {
+        self.mark(directive.loc, None);
+        // the directive is the raw text between the quotes and is
+        // written unchanged, so it can only be wrapped in quotes it
+        // doesn't hold unescaped
+        let mut quote = self.options.quote;
+        if has_unescaped(&directive.directive, quote.char()) {
+            quote = quote.other();
+        }
+        let quote = quote.char();
+        let mut text = String::with_capacity(directive.directive.len() + 2);
+        text.push(quote);
+        text.push_str(&directive.directive);
+        text.push(quote);
+        self.token(&text);
+        self.semicolon();
+    }
+
+    pub fn statement(&mut self, stmt: &Statement) {
+        self.measured(|p| p.unmeasured_statement(stmt));
+    }
+
+    fn unmeasured_statement(&mut self, stmt: &Statement) {
+        self.mark(stmt.loc(), None);
+        match stmt {
+            Statement::Expr(ref expr) => self.expression_statement(expr),
+            Statement::Block(ref block) => self.block(block),
+            Statement::FuncBody(ref body) => self.function_body(body),
+            Statement::Empty(_) => self.token(";"),
+            Statement::Debugger(_) => self.simple("debugger"),
+            Statement::With(ref with) => self.with(with),
+            Statement::Return(ref ret) => self.jump("return", ret.argument.as_ref()),
+            Statement::Labeled(ref labeled) => self.labeled(labeled),
+            Statement::Break(ref brk) => self.label_jump("break", brk.label.as_ref()),
+            Statement::Continue(ref cont) => self.label_jump("continue", cont.label.as_ref()),
+            Statement::If(ref stmt) => self.if_statement(stmt),
+            Statement::Switch(ref switch) => self.switch(switch),
+            Statement::Throw(ref throw) => self.jump("throw", Some(&throw.argument)),
+            Statement::Try(ref stmt) => self.try_statement(stmt),
+            Statement::While(ref stmt) => self.while_statement(stmt),
+            Statement::DoWhile(ref stmt) => self.do_while(stmt),
+            Statement::For(ref stmt) => self.for_statement(stmt),
+            Statement::ForIn(ref stmt) => self.for_in(stmt),
+            Statement::ForOf(ref stmt) => self.for_of(stmt),
+            Statement::Decl(ref decl) => self.declaration(decl),
+        }
+    }
+
+    fn expression_statement(&mut self, expr: &Expression) {
+        // a lone string would become a directive
+        let string = match expr {
+            Expression::Literal(ref lit) => matches!(lit.value, LiteralValue::String(_)),
+            _ => false,
+        };
+        let wrap = string || first_token(expr) != Start::Other;
+        self.parens(wrap, |p| p.expr(expr, Prec::Lowest));
+        self.semicolon();
+    }
+
+    fn simple(&mut self, keyword: &str) {
+        self.token(keyword);
+        self.semicolon();
+    }
+    /// `return` or `throw`, whose argument has to start on the
+    /// same line
+    fn jump(&mut self, keyword: &str, argument: Option<&Expression>) {
+        self.token(keyword);
+        if let Some(arg) = argument {
+            self.space();
+            self.expr(arg, Prec::Lowest);
+        }
+        self.semicolon();
+    }
+
+    fn label_jump(&mut self, keyword: &str, label: Option<&Identifier>) {
+        self.token(keyword);
+        if let Some(label) = label {
+            self.space();
+            self.ident(label);
+        }
+        self.semicolon();
+    }
+
+    fn statements(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            self.newline();
+            self.statement(stmt);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.token("{");
+        if !block.body.is_empty() {
+            self.indent += 1;
+            self.lines(|p| p.statements(&block.body));
+            self.indent -= 1;
+            self.newline();
+        }
+        self.token("}");
+    }
+
+    fn function_body(&mut self, body: &FunctionBody) {
+        let no_in = self.no_in;
+        self.no_in = false;
+        self.token("{");
+        if !body.body.is_empty() {
+            self.indent += 1;
+            self.lines(|p| {
+                for part in &body.body {
+                    p.newline();
+                    match part {
+                        FunctionBodyPart::Directive(ref directive) => p.directive(directive),
+                        FunctionBodyPart::Statement(ref stmt) => p.statement(stmt),
+                    }
+                }
+            });
+            self.indent -= 1;
+            self.newline();
+        }
+        self.token("}");
+        self.no_in = no_in;
+    }
+    /// The body of a loop, `if`, `with` or label, after its head
+    fn body(&mut self, body: &Statement) {
+        if !matches!(body, Statement::Empty(_)) {
+            self.space();
+        }
+        self.statement(body);
+    }
+
+    fn with(&mut self, with: &With) {
+        self.token("with");
+        self.space();
+        self.head(&with.object);
+        self.body(&with.body);
+    }
+    /// A parenthesized expression after a keyword
+    fn head(&mut self, expr: &Expression) {
+        self.token("(");
+        self.expr(expr, Prec::Lowest);
+        self.token(")");
+    }
+
+    fn labeled(&mut self, labeled: &Labeled) {
+        self.ident(&labeled.label);
+        self.token(":");
+        self.body(&labeled.body);
+    }
+
+    fn if_statement(&mut self, stmt: &If) {
+        self.token("if");
+        self.space();
+        self.head(&stmt.test);
+        match stmt.alternate {
+            None => self.body(&stmt.consequent),
+            Some(ref alternate) => {
+                // an `else` would attach to a nested `if` without one
+                if dangles(&stmt.consequent) {
+                    self.space();
+                    self.token("{");
+                    self.indent += 1;
+                    self.newline();
+                    self.statement(&stmt.consequent);
+                    self.indent -= 1;
+                    self.newline();
+                    self.token("}");
+                } else {
+                    self.body(&stmt.consequent);
+                }
+                self.space();
+                self.token("else");
+                self.body(alternate);
+            }
+        }
+    }
+
+    fn switch(&mut self, switch: &Switch) {
+        self.token("switch");
+        self.space();
+        self.head(&switch.discriminant);
+        self.space();
+        self.token("{");
+        self.indent += 1;
+        self.lines(|p| {
+            for case in &switch.cases {
+                p.newline();
+                p.switch_case(case);
+            }
+        });
+        self.indent -= 1;
+        if !switch.cases.is_empty() {
+            self.newline();
+        }
+        self.token("}");
+    }
+
+    fn switch_case(&mut self, case: &SwitchCase) {
+        match case.test {
+            Some(ref test) => {
+                self.token("case");
+                self.space();
+                self.expr(test, Prec::Lowest);
+            }
+            None => self.token("default"),
+        }
+        self.token(":");
+        self.indent += 1;
+        self.statements(&case.consequent);
+        self.indent -= 1;
+    }
+
+    fn try_statement(&mut self, stmt: &Try) {
+        self.token("try");
+        self.space();
+        self.block(&stmt.block);
+        if let Some(ref handler) = stmt.handler {
+            self.space();
+            self.catch_clause(handler);
+        }
+        if let Some(ref finalizer) = stmt.finalizer {
+            self.space();
+            self.token("finally");
+            self.space();
+            self.block(finalizer);
+        }
+    }
+
+    fn catch_clause(&mut self, clause: &CatchClause) {
+        self.token("catch");
+        self.space();
+        self.token("(");
+        self.pattern(&clause.param);
+        self.token(")");
+        self.space();
+        self.block(&clause.body);
+    }
+
+    fn while_statement(&mut self, stmt: &While) {
+        self.token("while");
+        self.space();
+        self.head(&stmt.test);
+        self.body(&stmt.body);
+    }
+
+    fn do_while(&mut self, stmt: &DoWhile) {
+        self.token("do");
+        self.body(&stmt.body);
+        self.space();
+        self.token("while");
+        self.space();
+        self.head(&stmt.test);
+        self.semicolon();
+    }
+
+    fn for_statement(&mut self, stmt: &For) {
+        self.token("for");
+        self.space();
+        self.token("(");
+        self.no_in = true;
+        match stmt.init {
+            ForInit::Var(ref decl) => self.variable_declaration(decl),
+            ForInit::Expr(ref expr) => {
+                let wrap = first_token(expr) == Start::Let;
+                self.parens(wrap, |p| p.expr(expr, Prec::Lowest));
+            }
+            ForInit::Null => (),
+        }
+        self.no_in = false;
+        self.token(";");
+        if let Some(ref test) = stmt.test {
+            self.space();
+            self.expr(test, Prec::Lowest);
+        }
+        self.token(";");
+        if let Some(ref update) = stmt.update {
+            self.space();
+            self.expr(update, Prec::Lowest);
+        }
+        self.token(")");
+        self.body(&stmt.body);
+    }
+
+    fn for_left(&mut self, left: &ForLeft) {
+        match left {
+            ForLeft::Var(ref decl) => self.variable_declaration(decl),
+            // `for (let of x)` and `for (async of x)` start other
+            // kinds of loop
+            ForLeft::Pat(Pattern::Ident(ref ident))
+                if ident.name == "let" || ident.name == "async" =>
+            {
+                self.parens(true, |p| p.ident(ident))
+            }
+            ForLeft::Pat(ref pat) => self.pattern(pat),
+        }
+    }
+
+    fn for_in(&mut self, stmt: &ForIn) {
+        self.token("for");
+        self.space();
+        self.token("(");
+        self.for_left(&stmt.left);
+        self.space();
+        self.token("in");
+        self.space();
+        self.expr(&stmt.right, Prec::Lowest);
+        self.token(")");
+        self.body(&stmt.body);
+    }
+
+    fn for_of(&mut self, stmt: &ForOf) {
+        self.token("for");
+        self.space();
+        if stmt._await {
+            self.token("await");
+            self.space();
+        }
+        self.token("(");
+        self.for_left(&stmt.left);
+        self.space();
+        self.token("of");
+        self.space();
+        self.expr(&stmt.right, Prec::Assign);
+        self.token(")");
+        self.body(&stmt.body);
+    }
+
+    fn declaration(&mut self, decl: &Declaration) {
+        match decl {
+            Declaration::Function(ref f) => self.function(f),
+            Declaration::Variable(ref var) => {
+                self.variable_declaration(var);
+                self.semicolon();
+            }
+            Declaration::Class(ref class) => self.class(class),
+        }
+    }
+
+    fn variable_declaration(&mut self, decl: &VariableDeclaration) {
+        self.token(decl.kind.as_str());
+        self.space();
+        self.list(&decl.declarations, Self::variable_declarator);
+    }
+
+    fn variable_declarator(&mut self, decl: &VariableDeclarator) {
+        self.pattern(&decl.id);
+        if let Some(ref init) = decl.init {
+            self.space();
+            self.token("=");
+            self.space();
+            self.expr(init, Prec::Assign);
+        }
+    }
+
+    fn function(&mut self, f: &Function) {
+        if f._async {
+            self.token("async");
+            self.space();
+        }
+        self.token("function");
+        if f.generator {
+            self.token("*");
+        }
+        if let Some(ref id) = f.id {
+            self.space();
+            self.ident(id);
+        }
+        self.params(&f.params);
+        self.space();
+        self.function_body(&f.body);
+    }
+
+    fn params(&mut self, params: &[Pattern]) {
+        self.token("(");
+        self.list(params, Self::pattern);
+        self.token(")");
+    }
+
+    fn class(&mut self, class: &Class) {
+        self.token("class");
+        if let Some(ref id) = class.id {
+            self.space();
+            self.ident(id);
+        }
+        if let Some(ref super_class) = class.super_class {
+            self.space();
+            self.token("extends");
+            self.space();
+            self.expr(super_class, Prec::Call);
+        }
+        self.space();
+        self.class_body(&class.body);
+    }
+
+    fn class_body(&mut self, body: &ClassBody) {
+        self.token("{");
+        if !body.body.is_empty() {
+            self.indent += 1;
+            self.lines(|p| {
+                for method in &body.body {
+                    p.newline();
+                    p.method_definition(method);
+                }
+            });
+            self.indent -= 1;
+            self.newline();
+        }
+        self.token("}");
+    }
+
+    fn method_definition(&mut self, method: &MethodDefinition) {
+        if method._static {
+            self.token("static");
+            self.space();
+        }
+        match method.kind {
+            MethodKind::Get => self.method("get", &method.key, method.computed, &method.value),
+            MethodKind::Set => self.method("set", &method.key, method.computed, &method.value),
+            MethodKind::Constructor | MethodKind::Method => {
+                self.method("", &method.key, method.computed, &method.value)
+            }
+        }
+    }
+    /// A method of a class or object, `kind` being `get`, `set` or
+    /// empty for any other method
+    fn method(&mut self, kind: &str, key: &Expression, computed: bool, value: &Function) {
+        if !kind.is_empty() {
+            self.token(kind);
+            self.space();
+        }
+        if value._async {
+            self.token("async");
+            self.space();
+        }
+        if value.generator {
+            self.token("*");
+        }
+        self.property_key(key, computed);
+        self.params(&value.params);
+        self.space();
+        self.function_body(&value.body);
+    }
+
+    fn property_key(&mut self, key: &Expression, computed: bool) {
+        if computed {
+            self.token("[");
+            self.expr(key, Prec::Assign);
+            self.token("]");
+        } else {
+            self.expr(key, Prec::Primary);
+        }
+    }
+
+    fn module_declaration(&mut self, decl: &ModuleDeclaration) {
+        self.measured(|p| match decl {
+            ModuleDeclaration::Import(ref import) => p.import_declaration(import),
+            ModuleDeclaration::Export(ref export) => p.export_declaration(export),
+        });
+    }
+
+    fn import_declaration(&mut self, decl: &ImportDeclaration) {
+        self.token("import");
+        let mut named = Vec::new();
+        let mut first = true;
+        for spec in &decl.specifiers {
+            match spec {
+                ImportSpecifier::Named(ref import) => named.push(import),
+                ImportSpecifier::Default(ref default) => {
+                    self.space();
+                    self.ident(&default.local);
+                    first = false;
+                }
+                ImportSpecifier::NameSpace(ref namespace) => {
+                    if !first {
+                        self.token(",");
+                    }
+                    self.space();
+                    self.import_namespace(namespace);
+                    first = false;
+                }
+            }
+        }
+        if !named.is_empty() {
+            if !first {
+                self.token(",");
+            }
+            self.space();
+            self.token("{");
+            self.list(&named, |p, import| p.import_specifier(import));
+            self.token("}");
+            first = false;
+        }
+        if !first {
+            self.space();
+            self.token("from");
+        }
+        self.space();
+        self.literal(&decl.source);
+        self.semicolon();
+    }
+
+    fn import_specifier(&mut self, import: &Import) {
+        self.ident(&import.import);
+        if import.import.name != import.local.name {
+            self.space();
+            self.token("as");
+            self.space();
+            self.ident(&import.local);
+        }
+    }
+
+    fn import_namespace(&mut self, namespace: &ImportNamespace) {
+        self.token("*");
+        self.space();
+        self.token("as");
+        self.space();
+        self.ident(&namespace.local);
+    }
+
+    fn export_declaration(&mut self, decl: &ExportDeclaration) {
+        match decl {
+            ExportDeclaration::Named(ref named) => self.export_named(named),
+            ExportDeclaration::Default(ref default) => self.export_default(default),
+            ExportDeclaration::All(ref all) => self.export_all(all),
+        }
+    }
+
+    fn export_named(&mut self, named: &ExportNamed) {
+        self.token("export");
+        self.space();
+        if let Some(ref decl) = named.declaration {
+            return self.declaration(decl);
+        }
+        self.token("{");
+        self.list(&named.specifiers, Self::export_specifier);
+        self.token("}");
+        if let Some(ref source) = named.source {
+            self.space();
+            self.token("from");
+            self.space();
+            self.literal(source);
+        }
+        self.semicolon();
+    }
+
+    fn export_specifier(&mut self, spec: &ExportSpecifier) {
+        self.ident(&spec.local);
+        if spec.local.name != spec.exported.name {
+            self.space();
+            self.token("as");
+            self.space();
+            self.ident(&spec.exported);
+        }
+    }
+
+    fn export_default(&mut self, default: &ExportDefault) {
+        self.token("export");
+        self.space();
+        self.token("default");
+        self.space();
+        match default.declaration {
+            ExportDecl::Decl(ref decl) => self.declaration(decl),
+            ExportDecl::Expr(ref expr) => {
+                // a function or class here would be a declaration
+                let wrap = matches!(first_token(expr), Start::Function | Start::Class);
+                self.parens(wrap, |p| p.expr(expr, Prec::Assign));
+                self.semicolon();
+            }
+        }
+    }
+
+    fn export_all(&mut self, all: &ExportAll) {
+        self.token("export");
+        self.space();
+        self.token("*");
+        self.space();
+        self.token("from");
+        self.space();
+        self.literal(&all.source);
+        self.semicolon();
+    }
+
+    pub fn pattern(&mut self, pat: &Pattern) {
+        self.mark(pat.loc(), None);
+        match pat {
+            Pattern::Ident(ref ident) => self.ident(ident),
+            Pattern::Object(ref obj) => self.object_pattern(obj),
+            Pattern::Array(ref arr) => self.array_pattern(arr),
+            Pattern::RestElement(ref rest) => self.rest_element(rest),
+            Pattern::Assignment(ref assign) => self.assignment_pattern(assign),
+        }
+    }
+
+    fn object_pattern(&mut self, obj: &Object) {
+        self.token("{");
+        self.list(&obj.properties, Self::assignment_property);
+        self.token("}");
+    }
+
+    fn assignment_property(&mut self, prop: &AssignmentProperty) {
+        if prop.shorthand {
+            return self.pattern(&prop.value);
+        }
+        self.property_key(&prop.key, prop.computed);
+        self.token(":");
+        self.space();
+        self.pattern(&prop.value);
+    }
+
+    fn array_pattern(&mut self, arr: &Array) {
+        self.token("[");
+        self.holes(&arr.elements, Self::pattern);
+        self.token("]");
+    }
+    /// The elements of an array, with a trailing comma when the last
+    /// one is a hole so it isn't dropped
+    fn holes<T, F>(&mut self, elements: &[Option<T>], mut each: F)
+    where
+        F: FnMut(&mut Self, &T),
+    {
+        for (idx, element) in elements.iter().enumerate() {
+            if idx > 0 {
+                self.token(",");
+                if element.is_some() {
+                    self.space();
+                }
+            }
+            if let Some(ref element) = element {
+                each(self, element);
+            }
+        }
+        if let Some(None) = elements.last() {
+            self.token(",");
+        }
+    }
+
+    fn rest_element(&mut self, rest: &RestElement) {
+        self.token("...");
+        self.pattern(&rest.argument);
+    }
+
+    fn assignment_pattern(&mut self, assign: &Assignment) {
+        self.pattern(&assign.left);
+        self.space();
+        self.token("=");
+        self.space();
+        self.expr(&assign.right, Prec::Assign);
+    }
+
+    pub fn expression(&mut self, expr: &Expression) {
+        self.expr(expr, Prec::Lowest)
+    }
+    /// Print `expr` where an expression binding at least as tightly
+    /// as `min` is expected, adding parentheses if it doesn't
+    fn expr(&mut self, expr: &Expression, min: Prec) {
+        let in_operator = match expr {
+            Expression::Binary(ref binary) => binary.operator == BinaryOperator::In,
+            _ => false,
+        };
+        let wrap = prec(expr) < min || (in_operator && self.no_in);
+        self.parens(wrap, |p| {
+            p.mark(expr.loc(), None);
+            match expr {
+                Expression::This(_) => p.token("this"),
+                Expression::Array(ref arr) => p.array(arr),
+                Expression::Object(ref obj) => p.object(obj),
+                Expression::Function(ref f) => p.function(f),
+                Expression::Unary(ref unary) => p.unary(unary),
+                Expression::Update(ref update) => p.update(update),
+                Expression::Binary(ref binary) => p.binary(binary),
+                Expression::Assignment(ref assign) => p.assignment(assign),
+                Expression::Logical(ref logical) => p.logical(logical),
+                Expression::Member(ref member) => p.member(member),
+                Expression::Conditional(ref conditional) => p.conditional(conditional),
+                Expression::Call(ref call) => p.call(call),
+                Expression::New(ref new) => p.new_expr(new),
+                Expression::Sequence(ref sequence) => p.sequence(sequence),
+                Expression::Spread(ref spread) => p.spread(spread),
+                Expression::ArrowFunction(ref arrow) => p.arrow_function(arrow),
+                Expression::Yield(ref expr) => p.yield_expr(expr),
+                Expression::Await(ref expr) => p.await_expr(expr),
+                Expression::Identifier(ref ident) => p.ident(ident),
+                Expression::Literal(ref lit) => p.literal(lit),
+                Expression::TaggedTemplate(ref tagged) => p.tagged_template(tagged),
+                Expression::Class(ref class) => p.class(class),
+                Expression::Meta(ref meta) => p.meta_property(meta),
+                Expression::Chain(ref chain) => p.expr(&chain.expression, Prec::Lowest),
+            }
+        });
+    }
+
+    fn literal(&mut self, lit: &Literal) {
+        match lit.value {
+            LiteralValue::String(ref s) => self.string(s),
+            LiteralValue::Boolean(b) => self.token(if b { "true" } else { "false" }),
+            LiteralValue::Null => self.token("null"),
+            LiteralValue::Number(ref n) => {
+                let n = self.number(n);
+                self.token(&n)
+            }
+            LiteralValue::RegExp(ref re) => self.regex(&re.regex),
+            LiteralValue::TemplateLiteral(ref template) => self.template_literal(template),
+        }
+    }
+
+    fn string(&mut self, value: &str) {
+        let mut quote_char = self.options.quote;
+        let other = quote_char.other();
+        if value.matches(other.char()).count() < value.matches(quote_char.char()).count() {
+            quote_char = other;
+        }
+        self.token(&quote(value, quote_char.char()));
+    }
+    /// The text for a number literal
+    fn number(&self, raw: &str) -> String {
+        if self.options.minify {
+            shortest_number(raw)
+        } else {
+            raw.to_string()
+        }
+    }
+
+    fn regex(&mut self, regex: &RegEx) {
+        self.token("/");
+        self.raw(&regex.pattern);
+        self.raw("/");
+        self.raw(&regex.flags);
+        self.regex_end = Some(self.out.len());
+    }
+
+    fn array(&mut self, arr: &expression::Array) {
+        let hole = arr.elements.last().is_some_and(Option::is_none);
+        self.group("[", "]", &arr.elements, hole, |p, element| {
+            if let Some(ref element) = element {
+                p.spreadable(element);
+            }
+        });
+    }
+
+    fn spreadable(&mut self, item: &Spreadable) {
+        match item {
+            Spreadable::Expr(ref expr) => self.expr(expr, Prec::Assign),
+            Spreadable::Spread(ref spread) => self.spread(spread),
+        }
+    }
+
+    fn spread(&mut self, spread: &SpreadElement) {
+        self.token("...");
+        self.expr(&spread.argument, Prec::Assign);
+    }
+
+    fn object(&mut self, obj: &expression::Object) {
+        self.group("{", "}", &obj.properties, false, Self::property);
+    }
+
+    fn property(&mut self, prop: &Property) {
+        let function = match prop.value {
+            Expression::Function(ref f) => Some(f),
+            _ => None,
+        };
+        match (&prop.kind, function) {
+            (PropertyKind::Get, Some(f)) => self.method("get", &prop.key, prop.computed, f),
+            (PropertyKind::Set, Some(f)) => self.method("set", &prop.key, prop.computed, f),
+            (PropertyKind::Init, Some(f)) if prop.method => {
+                self.method("", &prop.key, prop.computed, f)
+            }
+            _ if prop.shorthand => self.expr(&prop.value, Prec::Assign),
+            _ => {
+                self.property_key(&prop.key, prop.computed);
+                self.token(":");
+                self.space();
+                self.expr(&prop.value, Prec::Assign);
+            }
+        }
+    }
+
+    fn unary(&mut self, unary: &Unary) {
+        let op = unary.operator.as_str();
+        self.token(op);
+        if op.chars().all(char::is_alphabetic) {
+            self.space();
+        }
+        self.expr(&unary.argument, Prec::Prefix);
+    }
+
+    fn update(&mut self, update: &Update) {
+        if update.prefix {
+            self.token(update.operator.as_str());
+            self.expr(&update.argument, Prec::Prefix);
+        } else {
+            self.expr(&update.argument, Prec::Call);
+            self.token(update.operator.as_str());
+        }
+    }
+
+    fn binary(&mut self, binary: &Binary) {
+        let prec = binary_prec(&binary.operator);
+        self.expr(&binary.left, prec);
+        self.space();
+        self.token(binary.operator.as_str());
+        self.space();
+        self.expr(&binary.right, prec.next());
+    }
+
+    fn logical(&mut self, logical: &Logical) {
+        let prec = match logical.operator {
+            LogicalOperator::Or => Prec::LogicalOr,
+            LogicalOperator::And => Prec::LogicalAnd,
+        };
+        self.expr(&logical.left, prec);
+        self.space();
+        self.token(logical.operator.as_str());
+        self.space();
+        self.expr(&logical.right, prec.next());
+    }
+
+    fn assignment(&mut self, assign: &expression::Assignment) {
+        match assign.left {
+            PatOrExpr::Pat(ref pat) => self.pattern(pat),
+            PatOrExpr::Expr(ref expr) => self.expr(expr, Prec::Call),
+        }
+        self.space();
+        self.token(assign.operator.as_str());
+        self.space();
+        self.expr(&assign.right, Prec::Assign);
+    }
+
+    fn conditional(&mut self, conditional: &Conditional) {
+        self.expr(&conditional.test, Prec::LogicalOr);
+        self.space();
+        self.token("?");
+        self.space();
+        // `in` is always allowed between `?` and `:`
+        let no_in = self.no_in;
+        self.no_in = false;
+        self.expr(&conditional.consequent, Prec::Assign);
+        self.no_in = no_in;
+        self.space();
+        self.token(":");
+        self.space();
+        self.expr(&conditional.alternate, Prec::Assign);
+    }
+    /// The object of a member access or callee of a call
+    fn parent(&mut self, parent: &Parent) {
+        match parent {
+            Parent::Super(ref sup) => {
+                self.mark(sup.loc, None);
+                self.token("super")
+            }
+            // a chain ends at the first parenthesis, so one inside
+            // another member or call has to be wrapped
+            Parent::Expr(Expression::Chain(ref chain)) => {
+                self.parens(true, |p| p.expr(&chain.expression, Prec::Lowest))
+            }
+            // `1.toString()` would read the `.` as a decimal point
+            Parent::Expr(Expression::Literal(ref lit)) => match lit.value {
+                LiteralValue::Number(ref n) => {
+                    let n = self.number(n);
+                    self.parens(is_integer(&n), |p| {
+                        p.mark(lit.loc, None);
+                        p.token(&n)
+                    })
+                }
+                _ => {
+                    self.mark(lit.loc, None);
+                    self.literal(lit)
+                }
+            },
+            Parent::Expr(ref expr) => self.expr(expr, Prec::Call),
+        }
+    }
+
+    fn member(&mut self, member: &Member) {
+        self.parent(&member.object);
+        if member.optional {
+            self.token("?.");
+        }
+        if member.computed {
+            self.token("[");
+            self.expr(&member.property, Prec::Lowest);
+            self.token("]");
+        } else {
+            if !member.optional {
+                self.token(".");
+            }
+            self.expr(&member.property, Prec::Primary);
+        }
+    }
+
+    fn arguments(&mut self, args: &[Spreadable]) {
+        self.group("(", ")", args, false, Self::spreadable);
+    }
+
+    fn call(&mut self, call: &Call) {
+        self.parent(&call.callee);
+        if call.optional {
+            self.token("?.");
+        }
+        self.arguments(&call.arguments);
+    }
+
+    fn new_expr(&mut self, new: &New) {
+        self.token("new");
+        self.space();
+        let wrap = has_call(&new.callee)
+            || prec(&new.callee) < Prec::Member
+            || matches!(new.callee, Expression::Chain(_));
+        self.parens(wrap, |p| p.expr(&new.callee, Prec::Lowest));
+        self.arguments(&new.arguments);
+    }
+
+    fn sequence(&mut self, sequence: &Sequence) {
+        self.list(&sequence.expressions, |p, expr| p.expr(expr, Prec::Assign));
+    }
+
+    fn arrow_function(&mut self, arrow: &ArrowFunction) {
+        if arrow._async {
+            self.token("async");
+            self.space();
+        }
+        self.params(&arrow.params);
+        self.space();
+        self.token("=>");
+        self.space();
+        match arrow.body {
+            ArrowFunctionBody::Block(ref body) => self.function_body(body),
+            ArrowFunctionBody::Expr(ref expr) => {
+                // a `{` would start a function body
+                let wrap = first_token(expr) == Start::Brace;
+                self.parens(wrap, |p| p.expr(expr, Prec::Assign));
+            }
+        }
+    }
+
+    fn yield_expr(&mut self, expr: &Yield) {
+        self.token("yield");
+        if expr.delegate {
+            self.token("*");
+        }
+        if let Some(ref arg) = expr.argument {
+            self.space();
+            self.expr(arg, Prec::Assign);
+        }
+    }
+
+    fn await_expr(&mut self, expr: &Await) {
+        self.token("await");
+        self.space();
+        self.expr(&expr.argument, Prec::Prefix);
+    }
+
+    fn template_literal(&mut self, template: &TemplateLiteral) {
+        self.token("`");
+        for (idx, quasi) in template.quasis.iter().enumerate() {
+            self.raw(&quasi.value.raw);
+            if let Some(expr) = template.expressions.get(idx) {
+                self.raw("${");
+                self.expr(expr, Prec::Lowest);
+                self.raw("}");
+            }
+        }
+        self.raw("`");
+    }
+
+    fn tagged_template(&mut self, tagged: &TaggedTemplate) {
+        let wrap = matches!(tagged.tag, Expression::Chain(_));
+        self.parens(wrap, |p| p.expr(&tagged.tag, Prec::Call));
+        self.template_literal(&tagged.quasi);
+    }
+
+    fn meta_property(&mut self, meta: &MetaProperty) {
+        self.ident(&meta.meta);
+        self.token(".");
+        self.ident(&meta.property);
+    }
+}
+/// If `stmt` is an `if` that an `else` after it would attach to
+fn dangles(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::If(ref stmt) => match stmt.alternate {
+            None => true,
+            Some(ref alternate) => dangles(alternate),
+        },
+        Statement::Labeled(ref labeled) => dangles(&labeled.body),
+        Statement::With(ref with) => dangles(&with.body),
+        Statement::While(ref stmt) => dangles(&stmt.body),
+        Statement::For(ref stmt) => dangles(&stmt.body),
+        Statement::ForIn(ref stmt) => dangles(&stmt.body),
+        Statement::ForOf(ref stmt) => dangles(&stmt.body),
+        _ => false,
+    }
+}
+/// A decimal number without a `.` or exponent
+fn is_integer(number: &str) -> bool {
+    number.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+}
+/// The value of a number literal, if it isn't a `BigInt` and fits
+/// in the integer types for other bases
+fn number_value(digits: &str) -> Option<f64> {
+    let lower = digits.to_ascii_lowercase();
+    let radix = |prefix: &str, radix| {
+        lower
+            .strip_prefix(prefix)
+            .map(|rest| u64::from_str_radix(rest, radix).ok().map(|v| v as f64))
+    };
+    if lower.ends_with('n') {
+        return None;
+    }
+    if let Some(value) = radix("0x", 16)
+        .or_else(|| radix("0o", 8))
+        .or_else(|| radix("0b", 2))
+    {
+        return value;
+    }
+    // legacy octal like `017`, as long as every digit is one
+    if lower.len() > 1 && lower.starts_with('0') && lower.bytes().all(|b| (b'0'..b'8').contains(&b))
+    {
+        return u64::from_str_radix(&lower, 8).ok().map(|v| v as f64);
+    }
+    lower.parse().ok().filter(|v: &f64| v.is_finite())
+}
+/// The shortest way to write the number `raw` stands for
+fn shortest_number(raw: &str) -> String {
+    let digits: String = raw.chars().filter(|&c| c != '_').collect();
+    let value = match number_value(&digits) {
+        Some(value) => value,
+        None => return digits,
+    };
+    let mut plain = value.to_string();
+    if plain.starts_with("0.") {
+        plain.remove(0);
+    }
+    // `{:e}` gives the shortest digits as `d.ddde±x`, which becomes
+    // the integer `dddd` with the exponent moved past the point
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific.split_at(scientific.find('e').unwrap_or(0));
+    let mantissa = mantissa.replace('.', "");
+    let exponent = exponent[1..].parse::<i64>().unwrap_or(0) - (mantissa.len() as i64 - 1);
+    let exponential = match exponent {
+        0 => mantissa,
+        _ => format!("{}e{}", mantissa, exponent),
+    };
+    let mut best = digits;
+    if plain.len() < best.len() {
+        best = plain;
+    }
+    if exponential.len() < best.len() {
+        best = exponential;
+    }
+    best
+}
@@ -0,0 +1,934 @@
+//! ESTree JSON for every node, enabled with the `serde` feature.
+//!
+//! Each node serializes to an object with a `type` named like its
+//! `NodeKind`, the ESTree fields for that type, a `loc` object of
+//! 1-based lines and 0-based columns and a `[start, end]` `range`.
+//! Wrapper enums like `Expression` and `Statement` are transparent.
+//! `from_str` and `from_value` read the same JSON back and `babel`
+//! converts to and from Babel's flavor of it.
+//!
+//! On its own a tree doesn't know its source text, so columns are
+//! the byte columns of its `Position`s and a literal's `raw` is
+//! synthesized from its value. Serializing through `with_sources`
+//! instead looks up the files of a `SourceRegistry`: a `loc` gets the
+//! `source` name of its file and, for files with text, columns in
+//! UTF-16 code units like acorn's and a `raw` sliced from the text.
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+
+use serde::ser::{self, Serialize, SerializeMap, Serializer};
+
+use crate::expression::{self, *};
+use crate::line_index::LineIndex;
+use crate::module::*;
+use crate::node::{FileId, Node, Position, SourceLocation, SourceRegistry};
+use crate::statement::*;
+use crate::{
+    Array, Assignment, AssignmentProperty, Class, ClassBody, Directive, Function, MethodDefinition,
+    Object, Pattern, Program, ProgramPart, RestElement,
+};
+
+pub mod babel;
+mod de;
+
+pub use self::de::{
+    from_str, from_str_with_source, from_value, from_value_with_source, Error, ErrorKind,
+};
+
+/// Write `program` as ESTree JSON while walking it, without building
+/// the document in memory first. Memory use grows with the depth of
+/// the tree rather than its size. `writer` gets many small writes, so
+/// unbuffered writers like a `File` should be wrapped in a `BufWriter`.
+pub fn to_writer<W: io::Write>(writer: W, program: &Program) -> io::Result<()> {
+    serde_json::to_writer(writer, program).map_err(io::Error::from)
+}
+
+/// `to_writer` with newlines and indentation
+pub fn to_writer_pretty<W: io::Write>(writer: W, program: &Program) -> io::Result<()> {
+    serde_json::to_writer_pretty(writer, program).map_err(io::Error::from)
+}
+
+/// A node to serialize with the files of a registry at hand
+pub struct WithSources<'a, T: ?Sized> {
+    node: &'a T,
+    registry: &'a SourceRegistry,
+}
+
+/// Serialize `node` using the names and text of the files in
+/// `registry`, for example with `serde_json::to_writer(w, &with_sources(&program, &registry))`
+pub fn with_sources<'a, T: Serialize + ?Sized>(
+    node: &'a T,
+    registry: &'a SourceRegistry,
+) -> WithSources<'a, T> {
+    WithSources { node, registry }
+}
+
+impl<'a, T: Serialize + ?Sized> Serialize for WithSources<'a, T> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let sources = Sources {
+            registry: self.registry,
+            lines: RefCell::new(HashMap::new()),
+        };
+        let prev = SOURCES.with(|s| s.replace(Some(Rc::new(sources))));
+        let _restore = Restore(prev);
+        self.node.serialize(ser)
+    }
+}
+
+// The nodes' `Serialize` impls can't take extra arguments, so the
+// registry of the innermost `WithSources` being serialized is kept here
+thread_local! {
+    static SOURCES: RefCell<Option<Rc<Sources>>> = const { RefCell::new(None) };
+}
+
+struct Sources {
+    /// Borrowed from the `WithSources` being serialized, whose
+    /// `Restore` guard takes it out of `SOURCES` before it ends
+    registry: *const SourceRegistry,
+    lines: RefCell<HashMap<FileId, Rc<LineIndex>>>,
+}
+
+/// Puts back the outer registry, even if serializing panics
+struct Restore(Option<Rc<Sources>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let prev = self.0.take();
+        SOURCES.with(|s| *s.borrow_mut() = prev);
+    }
+}
+
+fn sources() -> Option<Rc<Sources>> {
+    SOURCES.with(|s| s.borrow().clone())
+}
+
+impl Sources {
+    fn registry(&self) -> &SourceRegistry {
+        // the registry outlives every `Sources` made for it, see above
+        unsafe { &*self.registry }
+    }
+
+    fn text(&self, file: FileId) -> Option<&str> {
+        self.registry().text(file)
+    }
+
+    fn lines(&self, file: FileId) -> Option<Rc<LineIndex>> {
+        let text = self.text(file)?;
+        let mut lines = self.lines.borrow_mut();
+        let index = lines
+            .entry(file)
+            .or_insert_with(|| Rc::new(LineIndex::new(text)));
+        Some(index.clone())
+    }
+}
+
+/// The text a location covers, if it comes from a file with text
+fn source_text(loc: &SourceLocation) -> Option<String> {
+    let file = loc.source?;
+    let sources = sources()?;
+    let text = sources.text(file)?;
+    loc.get(text).map(String::from)
+}
+
+/// Serialize a node object with its `type`, fields, `loc` and `range`
+macro_rules! node {
+    ($ser:expr, $ty:expr, $loc:expr, { $($key:expr => $value:expr,)* }) => {{
+        let loc: SourceLocation = $loc;
+        let mut map = $ser.serialize_map(None)?;
+        map.serialize_entry("type", $ty)?;
+        $(map.serialize_entry($key, $value)?;)*
+        map.serialize_entry("loc", &loc)?;
+        map.serialize_entry("range", &[loc.range.start, loc.range.end])?;
+        map.end()
+    }};
+}
+
+macro_rules! serialize {
+    ($ty:ty, |$this:ident, $ser:ident| $body:expr) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, $ser: S) -> Result<S::Ok, S::Error> {
+                let $this = self;
+                $body
+            }
+        }
+    };
+}
+
+/// Serialize an enum by serializing whichever value it wraps
+macro_rules! transparent {
+    ($ty:ident { $($variant:ident),* $(,)* }) => {
+        serialize!($ty, |this, ser| match this {
+            $($ty::$variant(ref inner) => inner.serialize(ser),)*
+        });
+    };
+}
+
+/// Serialize a value by its `as_str` name
+macro_rules! named {
+    ($($ty:ty),*) => {
+        $(serialize!($ty, |this, ser| ser.serialize_str(this.as_str()));)*
+    };
+}
+
+named!(
+    crate::SourceType,
+    crate::MethodKind,
+    PropertyKind,
+    UnaryOperator,
+    UpdateOperator,
+    BinaryOperator,
+    AssignmentOperator,
+    LogicalOperator,
+    VariableKind
+);
+
+serialize!(SourceLocation, |this, ser| {
+    let (mut start, mut end) = (this.start, this.end);
+    let mut name = None;
+    if let (Some(file), Some(sources)) = (this.source, sources()) {
+        name = sources.registry().name(file).map(String::from);
+        if let Some(lines) = sources.lines(file) {
+            for pos in [&mut start, &mut end] {
+                pos.column = lines
+                    .utf16_column(*pos)
+                    .ok_or_else(|| ser::Error::custom("position outside of its source text"))?;
+            }
+        }
+    }
+    let mut map = ser.serialize_map(None)?;
+    map.serialize_entry("start", &start)?;
+    map.serialize_entry("end", &end)?;
+    if this.source.is_some() {
+        map.serialize_entry("source", &name)?;
+    }
+    map.end()
+});
+
+serialize!(Position, |this, ser| {
+    let mut map = ser.serialize_map(Some(2))?;
+    map.serialize_entry("line", &this.line.get())?;
+    map.serialize_entry("column", &this.column)?;
+    map.end()
+});
+
+serialize!(Program, |this, ser| node!(ser, "Program", this.loc, {
+    "sourceType" => &this.source_type,
+    "body" => &this.body,
+}));
+
+transparent!(ProgramPart {
+    Directive,
+    Statement,
+    ModuleDecl
+});
+
+serialize!(
+    Directive,
+    |this, ser| node!(ser, "ExpressionStatement", this.loc, {
+        "expression" => &this.expression,
+        "directive" => &this.directive,
+    })
+);
+
+/// A `Function` serialized as the declaration or expression holding it
+struct FunctionAs<'a>(&'a Function, &'static str);
+
+serialize!(FunctionAs<'_>, |this, ser| {
+    let FunctionAs(f, ty) = *this;
+    node!(ser, ty, f.loc, {
+        "id" => &f.id,
+        "params" => &f.params,
+        "body" => &f.body,
+        "generator" => &f.generator,
+        "async" => &f._async,
+        "expression" => &false,
+    })
+});
+
+/// A `Class` serialized as the declaration or expression holding it
+struct ClassAs<'a>(&'a Class, &'static str);
+
+serialize!(ClassAs<'_>, |this, ser| {
+    let ClassAs(c, ty) = *this;
+    node!(ser, ty, c.loc, {
+        "id" => &c.id,
+        "superClass" => &c.super_class,
+        "body" => &c.body,
+    })
+});
+
+serialize!(ClassBody, |this, ser| node!(ser, "ClassBody", this.loc, {
+    "body" => &this.body,
+}));
+
+serialize!(
+    MethodDefinition,
+    |this, ser| node!(ser, "MethodDefinition", this.loc, {
+        "key" => &this.key,
+        "value" => &FunctionAs(&this.value, "FunctionExpression"),
+        "kind" => &this.kind,
+        "computed" => &this.computed,
+        "static" => &this._static,
+    })
+);
+
+transparent!(Pattern {
+    Ident,
+    Object,
+    Array,
+    RestElement,
+    Assignment
+});
+
+serialize!(Object, |this, ser| node!(ser, "ObjectPattern", this.loc, {
+    "properties" => &this.properties,
+}));
+
+// assignment properties don't keep a location of their own
+serialize!(AssignmentProperty, |this, ser| node!(
+    ser,
+    "Property",
+    this.key.loc().merge(&this.value.loc()),
+    {
+        "key" => &this.key,
+        "value" => &this.value,
+        "kind" => &this.kind,
+        "method" => &this.method,
+        "shorthand" => &this.shorthand,
+        "computed" => &this.computed,
+    }
+));
+
+serialize!(Array, |this, ser| node!(ser, "ArrayPattern", this.loc, {
+    "elements" => &this.elements,
+}));
+
+serialize!(
+    RestElement,
+    |this, ser| node!(ser, "RestElement", this.loc, {
+        "argument" => &this.argument,
+    })
+);
+
+serialize!(
+    Assignment,
+    |this, ser| node!(ser, "AssignmentPattern", this.loc, {
+        "left" => &this.left,
+        "right" => &this.right,
+    })
+);
+
+serialize!(Expression, |this, ser| match this {
+    Expression::This(ref inner) => inner.serialize(ser),
+    Expression::Array(ref inner) => inner.serialize(ser),
+    Expression::Object(ref inner) => inner.serialize(ser),
+    Expression::Function(ref inner) => FunctionAs(inner, "FunctionExpression").serialize(ser),
+    Expression::Unary(ref inner) => inner.serialize(ser),
+    Expression::Update(ref inner) => inner.serialize(ser),
+    Expression::Binary(ref inner) => inner.serialize(ser),
+    Expression::Assignment(ref inner) => inner.serialize(ser),
+    Expression::Logical(ref inner) => inner.serialize(ser),
+    Expression::Member(ref inner) => inner.serialize(ser),
+    Expression::Conditional(ref inner) => inner.serialize(ser),
+    Expression::Call(ref inner) => inner.serialize(ser),
+    Expression::New(ref inner) => inner.serialize(ser),
+    Expression::Sequence(ref inner) => inner.serialize(ser),
+    Expression::Spread(ref inner) => inner.serialize(ser),
+    Expression::ArrowFunction(ref inner) => inner.serialize(ser),
+    Expression::Yield(ref inner) => inner.serialize(ser),
+    Expression::Await(ref inner) => inner.serialize(ser),
+    Expression::Chain(ref inner) => inner.serialize(ser),
+    Expression::Identifier(ref inner) => inner.serialize(ser),
+    Expression::Literal(ref inner) => inner.serialize(ser),
+    Expression::TaggedTemplate(ref inner) => inner.serialize(ser),
+    Expression::Class(ref inner) => ClassAs(inner, "ClassExpression").serialize(ser),
+    Expression::Meta(ref inner) => inner.serialize(ser),
+});
+
+serialize!(Identifier, |this, ser| node!(ser, "Identifier", this.loc, {
+    "name" => &this.name,
+}));
+
+serialize!(Literal, |this, ser| {
+    let raw = match this.value {
+        LiteralValue::TemplateLiteral(ref t) => return t.serialize(ser),
+        ref value => source_text(&this.loc)
+            .map(Cow::Owned)
+            .unwrap_or_else(|| synthesized_raw(value)),
+    };
+    match this.value {
+        LiteralValue::String(ref s) => node!(ser, "Literal", this.loc, {
+            "value" => s,
+            "raw" => &raw,
+        }),
+        LiteralValue::Boolean(b) => node!(ser, "Literal", this.loc, {
+            "value" => &b,
+            "raw" => &raw,
+        }),
+        LiteralValue::Null => node!(ser, "Literal", this.loc, {
+            "value" => &(),
+            "raw" => &raw,
+        }),
+        LiteralValue::Number(ref n) if n.ends_with('n') => node!(ser, "Literal", this.loc, {
+            "value" => &(),
+            "raw" => &raw,
+            "bigint" => &n[..n.len() - 1].replace('_', ""),
+        }),
+        LiteralValue::Number(ref n) => node!(ser, "Literal", this.loc, {
+            "value" => &Number(n),
+            "raw" => &raw,
+        }),
+        LiteralValue::RegExp(ref re) => node!(ser, "Literal", this.loc, {
+            "value" => &(),
+            "raw" => &raw,
+            "regex" => &re.regex,
+        }),
+        LiteralValue::TemplateLiteral(_) => unreachable!(),
+    }
+});
+
+/// A literal's `raw` when its source text isn't available. Numbers
+/// keep their text, strings are requoted with double quotes, so the
+/// original quotes and escapes are lost
+fn synthesized_raw(value: &LiteralValue) -> Cow<'_, str> {
+    match *value {
+        LiteralValue::String(ref s) => Cow::Owned(quote(s)),
+        LiteralValue::Boolean(b) => Cow::Borrowed(if b { "true" } else { "false" }),
+        LiteralValue::Null => Cow::Borrowed("null"),
+        LiteralValue::Number(ref n) => Cow::Borrowed(n),
+        LiteralValue::RegExp(ref re) => {
+            Cow::Owned(format!("/{}/{}", re.regex.pattern, re.regex.flags))
+        }
+        LiteralValue::TemplateLiteral(_) => Cow::Borrowed(""),
+    }
+}
+
+serialize!(RegEx, |this, ser| {
+    let mut map = ser.serialize_map(Some(2))?;
+    map.serialize_entry("pattern", &this.pattern)?;
+    map.serialize_entry("flags", &this.flags)?;
+    map.end()
+});
+
+/// A JS string literal for `value`, quoted with double quotes
+fn quote(value: &str) -> String {
+    let mut ret = String::with_capacity(value.len() + 2);
+    ret.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\u{2028}' => ret.push_str("\\u2028"),
+            '\u{2029}' => ret.push_str("\\u2029"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\x{:02x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+/// The numeric value of a number literal's source text, `null` if
+/// it can't be represented in JSON
+struct Number<'a>(&'a str);
+
+impl<'a> Number<'a> {
+    fn value(&self) -> Option<f64> {
+        let text = self.0.replace('_', "");
+        let (radix, digits) = match text.get(..2) {
+            Some("0x") | Some("0X") => (16, &text[2..]),
+            Some("0o") | Some("0O") => (8, &text[2..]),
+            Some("0b") | Some("0B") => (2, &text[2..]),
+            // legacy octal like `010`, while `08` and `09` are decimal
+            _ if text.len() > 1
+                && text.starts_with('0')
+                && text.bytes().all(|b| (b'0'..=b'7').contains(&b)) =>
+            {
+                (8, &text[1..])
+            }
+            _ => (10, &text[..]),
+        };
+        if radix == 10 {
+            return text.parse().ok();
+        }
+        digits.chars().try_fold(0f64, |acc, c| {
+            c.to_digit(radix)
+                .map(|d| acc * f64::from(radix) + f64::from(d))
+        })
+    }
+}
+
+serialize!(Number<'_>, |this, ser| match this.value() {
+    Some(n) if n.is_finite() && n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 => {
+        ser.serialize_i64(n as i64)
+    }
+    Some(n) if n.is_finite() => ser.serialize_f64(n),
+    _ => ser.serialize_unit(),
+});
+
+serialize!(This, |this, ser| node!(ser, "ThisExpression", this.loc, {}));
+
+serialize!(Super, |this, ser| node!(ser, "Super", this.loc, {}));
+
+serialize!(
+    expression::Array,
+    |this, ser| node!(ser, "ArrayExpression", this.loc, {
+        "elements" => &this.elements,
+    })
+);
+
+transparent!(Spreadable { Expr, Spread });
+
+serialize!(
+    SpreadElement,
+    |this, ser| node!(ser, "SpreadElement", this.loc, {
+        "argument" => &this.argument,
+    })
+);
+
+serialize!(
+    expression::Object,
+    |this, ser| node!(ser, "ObjectExpression", this.loc, {
+        "properties" => &this.properties,
+    })
+);
+
+serialize!(Property, |this, ser| node!(ser, "Property", this.loc, {
+    "key" => &this.key,
+    "value" => &this.value,
+    "kind" => &this.kind,
+    "method" => &this.method,
+    "shorthand" => &this.shorthand,
+    "computed" => &this.computed,
+}));
+
+serialize!(Unary, |this, ser| node!(ser, "UnaryExpression", this.loc, {
+    "operator" => &this.operator,
+    "prefix" => &this.prefix,
+    "argument" => &this.argument,
+}));
+
+serialize!(
+    Update,
+    |this, ser| node!(ser, "UpdateExpression", this.loc, {
+        "operator" => &this.operator,
+        "argument" => &this.argument,
+        "prefix" => &this.prefix,
+    })
+);
+
+serialize!(
+    Binary,
+    |this, ser| node!(ser, "BinaryExpression", this.loc, {
+        "operator" => &this.operator,
+        "left" => &this.left,
+        "right" => &this.right,
+    })
+);
+
+serialize!(expression::Assignment, |this, ser| node!(
+    ser,
+    "AssignmentExpression",
+    this.loc,
+    {
+        "operator" => &this.operator,
+        "left" => &this.left,
+        "right" => &this.right,
+    }
+));
+
+transparent!(PatOrExpr { Pat, Expr });
+
+serialize!(
+    Logical,
+    |this, ser| node!(ser, "LogicalExpression", this.loc, {
+        "operator" => &this.operator,
+        "left" => &this.left,
+        "right" => &this.right,
+    })
+);
+
+serialize!(
+    Member,
+    |this, ser| node!(ser, "MemberExpression", this.loc, {
+        "object" => &this.object,
+        "property" => &this.property,
+        "computed" => &this.computed,
+        "optional" => &this.optional,
+    })
+);
+
+transparent!(Parent { Expr, Super });
+
+serialize!(
+    Conditional,
+    |this, ser| node!(ser, "ConditionalExpression", this.loc, {
+        "test" => &this.test,
+        "consequent" => &this.consequent,
+        "alternate" => &this.alternate,
+    })
+);
+
+serialize!(Call, |this, ser| node!(ser, "CallExpression", this.loc, {
+    "callee" => &this.callee,
+    "arguments" => &this.arguments,
+    "optional" => &this.optional,
+}));
+
+serialize!(New, |this, ser| node!(ser, "NewExpression", this.loc, {
+    "callee" => &this.callee,
+    "arguments" => &this.arguments,
+}));
+
+serialize!(
+    Sequence,
+    |this, ser| node!(ser, "SequenceExpression", this.loc, {
+        "expressions" => &this.expressions,
+    })
+);
+
+serialize!(ArrowFunction, |this, ser| node!(
+    ser,
+    "ArrowFunctionExpression",
+    this.loc,
+    {
+        "id" => &this.id,
+        "params" => &this.params,
+        "body" => &this.body,
+        "generator" => &this.generator,
+        "async" => &this._async,
+        "expression" => &matches!(this.body, ArrowFunctionBody::Expr(_)),
+    }
+));
+
+transparent!(ArrowFunctionBody { Expr, Block });
+
+serialize!(Yield, |this, ser| node!(ser, "YieldExpression", this.loc, {
+    "argument" => &this.argument,
+    "delegate" => &this.delegate,
+}));
+
+serialize!(Await, |this, ser| node!(ser, "AwaitExpression", this.loc, {
+    "argument" => &this.argument,
+}));
+
+serialize!(Chain, |this, ser| node!(ser, "ChainExpression", this.loc, {
+    "expression" => &this.expression,
+}));
+
+serialize!(
+    TemplateLiteral,
+    |this, ser| node!(ser, "TemplateLiteral", this.loc, {
+        "quasis" => &this.quasis,
+        "expressions" => &this.expressions,
+    })
+);
+
+serialize!(TaggedTemplate, |this, ser| node!(
+    ser,
+    "TaggedTemplateExpression",
+    this.loc,
+    {
+        "tag" => &this.tag,
+        "quasi" => &this.quasi,
+    }
+));
+
+serialize!(
+    TemplateElement,
+    |this, ser| node!(ser, "TemplateElement", this.loc, {
+        "value" => &this.value,
+        "tail" => &this.tail,
+    })
+);
+
+serialize!(TemplateValue, |this, ser| {
+    let mut map = ser.serialize_map(Some(2))?;
+    map.serialize_entry("raw", &this.raw)?;
+    map.serialize_entry("cooked", &this.cooked)?;
+    map.end()
+});
+
+serialize!(
+    MetaProperty,
+    |this, ser| node!(ser, "MetaProperty", this.loc, {
+        "meta" => &this.meta,
+        "property" => &this.property,
+    })
+);
+
+serialize!(Statement, |this, ser| match this {
+    Statement::Expr(ref inner) => node!(ser, "ExpressionStatement", inner.loc(), {
+        "expression" => inner,
+    }),
+    Statement::Block(ref inner) => inner.serialize(ser),
+    Statement::FuncBody(ref inner) => inner.serialize(ser),
+    Statement::Empty(ref inner) => inner.serialize(ser),
+    Statement::Debugger(ref inner) => inner.serialize(ser),
+    Statement::With(ref inner) => inner.serialize(ser),
+    Statement::Return(ref inner) => inner.serialize(ser),
+    Statement::Labeled(ref inner) => inner.serialize(ser),
+    Statement::Break(ref inner) => inner.serialize(ser),
+    Statement::Continue(ref inner) => inner.serialize(ser),
+    Statement::If(ref inner) => inner.serialize(ser),
+    Statement::Switch(ref inner) => inner.serialize(ser),
+    Statement::Throw(ref inner) => inner.serialize(ser),
+    Statement::Try(ref inner) => inner.serialize(ser),
+    Statement::While(ref inner) => inner.serialize(ser),
+    Statement::DoWhile(ref inner) => inner.serialize(ser),
+    Statement::For(ref inner) => inner.serialize(ser),
+    Statement::ForIn(ref inner) => inner.serialize(ser),
+    Statement::ForOf(ref inner) => inner.serialize(ser),
+    Statement::Decl(ref inner) => inner.serialize(ser),
+});
+
+serialize!(Block, |this, ser| node!(ser, "BlockStatement", this.loc, {
+    "body" => &this.body,
+}));
+
+serialize!(
+    FunctionBody,
+    |this, ser| node!(ser, "BlockStatement", this.loc, {
+        "body" => &this.body,
+    })
+);
+
+transparent!(FunctionBodyPart {
+    Directive,
+    Statement
+});
+
+serialize!(Empty, |this, ser| node!(ser, "EmptyStatement", this.loc, {
+}));
+
+serialize!(Debugger, |this, ser| node!(
+    ser,
+    "DebuggerStatement",
+    this.loc,
+    {}
+));
+
+serialize!(With, |this, ser| node!(ser, "WithStatement", this.loc, {
+    "object" => &this.object,
+    "body" => &this.body,
+}));
+
+serialize!(
+    Return,
+    |this, ser| node!(ser, "ReturnStatement", this.loc, {
+        "argument" => &this.argument,
+    })
+);
+
+serialize!(
+    Labeled,
+    |this, ser| node!(ser, "LabeledStatement", this.loc, {
+        "label" => &this.label,
+        "body" => &this.body,
+    })
+);
+
+serialize!(Break, |this, ser| node!(ser, "BreakStatement", this.loc, {
+    "label" => &this.label,
+}));
+
+serialize!(
+    Continue,
+    |this, ser| node!(ser, "ContinueStatement", this.loc, {
+        "label" => &this.label,
+    })
+);
+
+serialize!(If, |this, ser| node!(ser, "IfStatement", this.loc, {
+    "test" => &this.test,
+    "consequent" => &this.consequent,
+    "alternate" => &this.alternate,
+}));
+
+serialize!(
+    Switch,
+    |this, ser| node!(ser, "SwitchStatement", this.loc, {
+        "discriminant" => &this.discriminant,
+        "cases" => &this.cases,
+    })
+);
+
+serialize!(SwitchCase, |this, ser| node!(ser, "SwitchCase", this.loc, {
+    "test" => &this.test,
+    "consequent" => &this.consequent,
+}));
+
+serialize!(Throw, |this, ser| node!(ser, "ThrowStatement", this.loc, {
+    "argument" => &this.argument,
+}));
+
+serialize!(Try, |this, ser| node!(ser, "TryStatement", this.loc, {
+    "block" => &this.block,
+    "handler" => &this.handler,
+    "finalizer" => &this.finalizer,
+}));
+
+serialize!(
+    CatchClause,
+    |this, ser| node!(ser, "CatchClause", this.loc, {
+        "param" => &this.param,
+        "body" => &this.body,
+    })
+);
+
+serialize!(While, |this, ser| node!(ser, "WhileStatement", this.loc, {
+    "test" => &this.test,
+    "body" => &this.body,
+}));
+
+serialize!(
+    DoWhile,
+    |this, ser| node!(ser, "DoWhileStatement", this.loc, {
+        "body" => &this.body,
+        "test" => &this.test,
+    })
+);
+
+serialize!(For, |this, ser| node!(ser, "ForStatement", this.loc, {
+    "init" => &this.init,
+    "test" => &this.test,
+    "update" => &this.update,
+    "body" => &this.body,
+}));
+
+serialize!(ForInit, |this, ser| match this {
+    ForInit::Var(ref inner) => inner.serialize(ser),
+    ForInit::Expr(ref inner) => inner.serialize(ser),
+    ForInit::Null => ser.serialize_unit(),
+});
+
+serialize!(ForIn, |this, ser| node!(ser, "ForInStatement", this.loc, {
+    "left" => &this.left,
+    "right" => &this.right,
+    "body" => &this.body,
+}));
+
+serialize!(ForOf, |this, ser| node!(ser, "ForOfStatement", this.loc, {
+    "left" => &this.left,
+    "right" => &this.right,
+    "body" => &this.body,
+    "await" => &this._await,
+}));
+
+transparent!(ForLeft { Var, Pat });
+
+serialize!(Declaration, |this, ser| match this {
+    Declaration::Function(ref inner) => FunctionAs(inner, "FunctionDeclaration").serialize(ser),
+    Declaration::Variable(ref inner) => inner.serialize(ser),
+    Declaration::Class(ref inner) => ClassAs(inner, "ClassDeclaration").serialize(ser),
+});
+
+serialize!(VariableDeclaration, |this, ser| node!(
+    ser,
+    "VariableDeclaration",
+    this.loc,
+    {
+        "declarations" => &this.declarations,
+        "kind" => &this.kind,
+    }
+));
+
+serialize!(VariableDeclarator, |this, ser| node!(
+    ser,
+    "VariableDeclarator",
+    this.loc,
+    {
+        "id" => &this.id,
+        "init" => &this.init,
+    }
+));
+
+transparent!(ModuleDeclaration { Import, Export });
+
+serialize!(
+    ImportDeclaration,
+    |this, ser| node!(ser, "ImportDeclaration", this.loc, {
+        "specifiers" => &this.specifiers,
+        "source" => &this.source,
+    })
+);
+
+transparent!(ImportSpecifier {
+    Named,
+    Default,
+    NameSpace
+});
+
+serialize!(
+    Import,
+    |this, ser| node!(ser, "ImportSpecifier", this.loc, {
+        "imported" => &this.import,
+        "local" => &this.local,
+    })
+);
+
+serialize!(ImportDefault, |this, ser| node!(
+    ser,
+    "ImportDefaultSpecifier",
+    this.loc,
+    {
+        "local" => &this.local,
+    }
+));
+
+serialize!(ImportNamespace, |this, ser| node!(
+    ser,
+    "ImportNamespaceSpecifier",
+    this.loc,
+    {
+        "local" => &this.local,
+    }
+));
+
+transparent!(ExportDeclaration {
+    Named,
+    Default,
+    All
+});
+
+serialize!(ExportNamed, |this, ser| node!(
+    ser,
+    "ExportNamedDeclaration",
+    this.loc,
+    {
+        "declaration" => &this.declaration,
+        "specifiers" => &this.specifiers,
+        "source" => &this.source,
+    }
+));
+
+serialize!(
+    ExportSpecifier,
+    |this, ser| node!(ser, "ExportSpecifier", this.loc, {
+        "local" => &this.local,
+        "exported" => &this.exported,
+    })
+);
+
+serialize!(ExportDefault, |this, ser| node!(
+    ser,
+    "ExportDefaultDeclaration",
+    this.loc,
+    {
+        "declaration" => &this.declaration,
+    }
+));
+
+transparent!(ExportDecl { Decl, Expr });
+
+serialize!(
+    ExportAll,
+    |this, ser| node!(ser, "ExportAllDeclaration", this.loc, {
+        "source" => &this.source,
+    })
+);
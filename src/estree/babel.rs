@@ -0,0 +1,340 @@
+//! Babel's dialect of ESTree.
+//!
+//! Babel splits `Literal` into `StringLiteral`, `NumericLiteral` and
+//! friends with the source text in `extra.raw`, has `ObjectProperty`,
+//! `ObjectMethod` and `ClassMethod` where ESTree has `Property` and
+//! `MethodDefinition`, marks optional chains by renaming their links
+//! to `OptionalMemberExpression` and `OptionalCallExpression` instead
+//! of wrapping them in a `ChainExpression`, keeps directives in their
+//! own `directives` list and has `start`/`end` offsets but no `range`.
+//!
+//! Both directions translate to and from the ESTree JSON in the rest
+//! of this module, so the path of an error from `from_value` is into
+//! the ESTree equivalent of the document.
+use serde_json::{self, Map, Value};
+
+use super::{de, Error};
+use crate::Program;
+
+/// Write `program` as Babel's AST
+pub fn to_value(program: &Program) -> Result<Value, serde_json::Error> {
+    serde_json::to_value(program).map(to_babel)
+}
+
+/// Read a `Program` from Babel's AST as JSON text
+pub fn from_str(json: &str) -> Result<Program, Error> {
+    let value: Value = serde_json::from_str(json)?;
+    from_value(value)
+}
+
+/// Read a `Program` from Babel's AST, either a `File` or the
+/// `Program` inside of it. The document is taken by value since it
+/// is rewritten into ESTree's shapes in place.
+pub fn from_value(value: Value) -> Result<Program, Error> {
+    let program = match value {
+        Value::Object(mut file) if ty(&file) == Some("File") => {
+            file.remove("program").unwrap_or(Value::Null)
+        }
+        other => other,
+    };
+    de::from_value(&from_babel(program))
+}
+
+fn ty(map: &Map<String, Value>) -> Option<&str> {
+    map.get("type").and_then(Value::as_str)
+}
+
+fn take_object(map: &mut Map<String, Value>, key: &str) -> Map<String, Value> {
+    match map.remove(key) {
+        Some(Value::Object(inner)) => inner,
+        _ => Map::new(),
+    }
+}
+/// Copy the location of one node onto another
+fn copy_loc(from: &Map<String, Value>, to: &mut Map<String, Value>) {
+    for key in &["start", "end", "loc", "range"] {
+        if let Some(value) = from.get(*key) {
+            to.insert(key.to_string(), value.clone());
+        }
+    }
+}
+
+fn to_babel(value: Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(to_babel).collect()),
+        Value::Object(map) => Value::Object(node_to_babel(map)),
+        other => other,
+    }
+}
+
+fn node_to_babel(mut map: Map<String, Value>) -> Map<String, Value> {
+    if let Some(Value::Array(range)) = map.remove("range") {
+        if let [start, end] = &range[..] {
+            map.insert("start".to_string(), start.clone());
+            map.insert("end".to_string(), end.clone());
+        }
+    }
+    match ty(&map) {
+        Some("Literal") => literal_to_babel(&mut map),
+        Some("Property") => property_to_babel(&mut map),
+        Some("MethodDefinition") => {
+            map.insert("type".to_string(), "ClassMethod".into());
+            let value = take_object(&mut map, "value");
+            function_fields(&value, &mut map);
+        }
+        Some("ChainExpression") => {
+            let mut expression = take_object(&mut map, "expression");
+            mark_optional(&mut expression);
+            return node_to_babel(expression);
+        }
+        Some("Program") | Some("BlockStatement") => split_directives(&mut map),
+        _ => (),
+    }
+    map.into_iter().map(|(k, v)| (k, to_babel(v))).collect()
+}
+
+fn literal_to_babel(map: &mut Map<String, Value>) {
+    let raw = map.remove("raw").unwrap_or(Value::Null);
+    let ty = if let Some(Value::Object(regex)) = map.remove("regex") {
+        map.remove("value");
+        for key in &["pattern", "flags"] {
+            let value = regex.get(*key).cloned().unwrap_or(Value::Null);
+            map.insert(key.to_string(), value);
+        }
+        "RegExpLiteral"
+    } else if let Some(bigint) = map.remove("bigint") {
+        map.insert("value".to_string(), bigint);
+        "BigIntLiteral"
+    } else {
+        match map.get("value") {
+            Some(Value::String(_)) => "StringLiteral",
+            Some(Value::Bool(_)) => "BooleanLiteral",
+            // numbers too big for JSON are written as `null`
+            Some(Value::Number(_)) => "NumericLiteral",
+            _ if raw != "null" => "NumericLiteral",
+            _ => {
+                map.remove("value");
+                "NullLiteral"
+            }
+        }
+    };
+    map.insert("type".to_string(), ty.into());
+    if ty != "BooleanLiteral" && ty != "NullLiteral" {
+        let raw_value = map.get("value").cloned().unwrap_or(Value::Null);
+        map.insert("extra".to_string(), extra(raw_value, raw));
+    }
+}
+
+fn extra(raw_value: Value, raw: Value) -> Value {
+    let mut extra = Map::new();
+    extra.insert("rawValue".to_string(), raw_value);
+    extra.insert("raw".to_string(), raw);
+    Value::Object(extra)
+}
+
+fn property_to_babel(map: &mut Map<String, Value>) {
+    let method = map.remove("method") == Some(Value::Bool(true));
+    let kind = map.remove("kind").unwrap_or(Value::Null);
+    if !method && kind == "init" {
+        map.insert("type".to_string(), "ObjectProperty".into());
+        return;
+    }
+    map.remove("shorthand");
+    map.insert("type".to_string(), "ObjectMethod".into());
+    map.insert(
+        "kind".to_string(),
+        if method { "method".into() } else { kind },
+    );
+    let value = take_object(map, "value");
+    function_fields(&value, map);
+}
+/// Move the fields of a `FunctionExpression` onto the method holding it
+fn function_fields(function: &Map<String, Value>, method: &mut Map<String, Value>) {
+    for key in &["id", "params", "body", "generator", "async"] {
+        let value = function.get(*key).cloned().unwrap_or(Value::Null);
+        method.insert(key.to_string(), value);
+    }
+}
+/// Rename the links of an optional chain from the top down to its
+/// last `?.`, returning if there was one at or below `node`
+fn mark_optional(node: &mut Map<String, Value>) -> bool {
+    let (ty, link) = match ty(node) {
+        Some("MemberExpression") => ("OptionalMemberExpression", "object"),
+        Some("CallExpression") => ("OptionalCallExpression", "callee"),
+        _ => return false,
+    };
+    let below = match node.get_mut(link) {
+        Some(Value::Object(inner)) => mark_optional(inner),
+        _ => false,
+    };
+    let optional = below || node.get("optional") == Some(&Value::Bool(true));
+    if optional {
+        node.insert("type".to_string(), ty.into());
+    }
+    optional
+}
+/// Move the leading directives out of a `Program` or
+/// `BlockStatement`'s body and into its `directives`
+fn split_directives(map: &mut Map<String, Value>) {
+    let mut directives = Vec::new();
+    if let Some(Value::Array(body)) = map.get_mut("body") {
+        let count = body
+            .iter()
+            .take_while(|part| part.get("directive").is_some())
+            .count();
+        for part in body.drain(..count) {
+            if let Value::Object(mut statement) = part {
+                let mut literal = take_object(&mut statement, "expression");
+                literal.insert("type".to_string(), "DirectiveLiteral".into());
+                let raw = literal.remove("raw").unwrap_or(Value::Null);
+                let value = statement.remove("directive").unwrap_or(Value::Null);
+                literal.insert("value".to_string(), value.clone());
+                literal.insert("extra".to_string(), extra(value, raw));
+                statement.insert("type".to_string(), "Directive".into());
+                statement.insert("value".to_string(), Value::Object(literal));
+                directives.push(Value::Object(statement));
+            }
+        }
+    }
+    map.insert("directives".to_string(), Value::Array(directives));
+}
+
+fn from_babel(value: Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(from_babel).collect()),
+        Value::Object(map) => Value::Object(node_from_babel(map)),
+        other => other,
+    }
+}
+
+fn node_from_babel(mut map: Map<String, Value>) -> Map<String, Value> {
+    let rename = |map: &mut Map<String, Value>, ty: &str| {
+        map.insert("type".to_string(), ty.into());
+    };
+    match ty(&map) {
+        Some("StringLiteral") | Some("NumericLiteral") | Some("BooleanLiteral") => {
+            rename(&mut map, "Literal");
+            let extra = take_object(&mut map, "extra");
+            if let Some(raw) = extra.get("raw") {
+                map.insert("raw".to_string(), raw.clone());
+            }
+        }
+        Some("NullLiteral") => {
+            rename(&mut map, "Literal");
+            map.insert("value".to_string(), Value::Null);
+        }
+        Some("BigIntLiteral") => {
+            rename(&mut map, "Literal");
+            let extra = take_object(&mut map, "extra");
+            if let Some(raw) = extra.get("raw") {
+                map.insert("raw".to_string(), raw.clone());
+            }
+            let bigint = map.remove("value").unwrap_or(Value::Null);
+            map.insert("bigint".to_string(), bigint);
+            map.insert("value".to_string(), Value::Null);
+        }
+        Some("RegExpLiteral") => {
+            rename(&mut map, "Literal");
+            let mut regex = Map::new();
+            for key in &["pattern", "flags"] {
+                regex.insert(key.to_string(), map.remove(*key).unwrap_or(Value::Null));
+            }
+            map.insert("value".to_string(), Value::Null);
+            map.insert("regex".to_string(), Value::Object(regex));
+        }
+        Some("ObjectProperty") => {
+            rename(&mut map, "Property");
+            map.insert("kind".to_string(), "init".into());
+            map.insert("method".to_string(), false.into());
+        }
+        Some("ObjectMethod") => {
+            rename(&mut map, "Property");
+            let method = map.get("kind").is_some_and(|kind| kind == "method");
+            if method {
+                map.insert("kind".to_string(), "init".into());
+            }
+            map.insert("method".to_string(), method.into());
+            map.insert("shorthand".to_string(), false.into());
+            method_value(&mut map);
+        }
+        Some("ClassMethod") => {
+            rename(&mut map, "MethodDefinition");
+            method_value(&mut map);
+        }
+        Some("OptionalMemberExpression") | Some("OptionalCallExpression") => {
+            let mut chain = Map::new();
+            rename(&mut chain, "ChainExpression");
+            copy_loc(&map, &mut chain);
+            chain.insert(
+                "expression".to_string(),
+                Value::Object(link_from_babel(map)),
+            );
+            return chain;
+        }
+        Some("Program") | Some("BlockStatement") => join_directives(&mut map),
+        _ => (),
+    }
+    map.into_iter().map(|(k, v)| (k, from_babel(v))).collect()
+}
+/// Move the function fields of an `ObjectMethod` or `ClassMethod`
+/// into the `FunctionExpression` ESTree keeps as its `value`
+fn method_value(method: &mut Map<String, Value>) {
+    let mut function = Map::new();
+    function.insert("type".to_string(), "FunctionExpression".into());
+    for key in &["id", "params", "body", "generator", "async"] {
+        function.insert(key.to_string(), method.remove(*key).unwrap_or(Value::Null));
+    }
+    copy_loc(method, &mut function);
+    method.insert("value".to_string(), Value::Object(function));
+}
+/// A link of an optional chain, the chain's `ChainExpression`
+/// having already been added above it
+fn link_from_babel(mut map: Map<String, Value>) -> Map<String, Value> {
+    let (ty, link) = match ty(&map) {
+        Some("OptionalMemberExpression") => ("MemberExpression", "object"),
+        Some("OptionalCallExpression") => ("CallExpression", "callee"),
+        _ => return node_from_babel(map),
+    };
+    map.insert("type".to_string(), ty.into());
+    let inner = match map.remove(link) {
+        Some(Value::Object(inner)) => Value::Object(link_from_babel(inner)),
+        Some(other) => from_babel(other),
+        None => Value::Null,
+    };
+    let mut map = node_from_babel(map);
+    map.insert(link.to_string(), inner);
+    map
+}
+/// Put a `Program` or `BlockStatement`'s directives back at the
+/// start of its body
+fn join_directives(map: &mut Map<String, Value>) {
+    let directives = match map.remove("directives") {
+        Some(Value::Array(directives)) => directives,
+        _ => return,
+    };
+    let mut body: Vec<Value> = directives
+        .into_iter()
+        .map(|directive| {
+            let mut statement = match directive {
+                Value::Object(statement) => statement,
+                _ => Map::new(),
+            };
+            let mut literal = take_object(&mut statement, "value");
+            let extra = take_object(&mut literal, "extra");
+            let value = literal.get("value").cloned().unwrap_or(Value::Null);
+            literal.insert("type".to_string(), "Literal".into());
+            literal.insert(
+                "raw".to_string(),
+                extra.get("raw").cloned().unwrap_or(Value::Null),
+            );
+            statement.insert("type".to_string(), "ExpressionStatement".into());
+            statement.insert("expression".to_string(), Value::Object(literal));
+            statement.insert("directive".to_string(), value);
+            Value::Object(statement)
+        })
+        .collect();
+    if let Some(Value::Array(rest)) = map.remove("body") {
+        body.extend(rest);
+    }
+    map.insert("body".to_string(), Value::Array(body));
+}
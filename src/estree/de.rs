@@ -0,0 +1,1079 @@
+//! Reading ESTree JSON, like acorn's or esprima's output, back
+//! into a `Program`.
+//!
+//! Fields without a place in the tree (`start`, `end`, `raw` on
+//! most nodes, ...) are ignored. A node's location is read from
+//! `loc` and either `range` or `start`/`end`, whichever is present.
+//!
+//! Parsers like acorn, esprima and Babel count `loc` columns in
+//! UTF-16 code units, while a `Position` counts bytes. `from_str` and
+//! `from_value` don't know the source text, so they keep the columns
+//! as they are, which is only right for ASCII lines, and fail on a
+//! node without both `loc` and offsets. `from_str_with_source` and
+//! `from_value_with_source` read the document as the output for a
+//! file of a `SourceRegistry`: every location gets that file as its
+//! `source` and, if the file has text, columns are converted to
+//! bytes and a missing `loc` or `range` is worked out from the other.
+use std::error;
+use std::fmt;
+use std::num::NonZeroU64;
+
+use serde_json::{self, Map, Value};
+
+use crate::expression::{self, *};
+use crate::line_index::LineIndex;
+use crate::module::*;
+use crate::node::{FileId, Position, Range, SourceLocation, SourceRegistry};
+use crate::statement::*;
+use crate::{
+    Array, Assignment, AssignmentProperty, Class, ClassBody, Directive, Function, MethodDefinition,
+    MethodKind, Object, Pattern, Program, ProgramPart, RestElement, SourceType,
+};
+
+/// Read a `Program` from ESTree JSON text
+pub fn from_str(json: &str) -> Result<Program, Error> {
+    let value: Value = serde_json::from_str(json)?;
+    from_value(&value)
+}
+
+/// Read a `Program` from an already parsed ESTree JSON value
+pub fn from_value(value: &Value) -> Result<Program, Error> {
+    Program::from_json(Json {
+        value,
+        path: &Path::Root,
+        source: &Source::default(),
+    })
+}
+
+/// Read a `Program` from ESTree JSON text made from `file`
+pub fn from_str_with_source(
+    json: &str,
+    registry: &SourceRegistry,
+    file: FileId,
+) -> Result<Program, Error> {
+    let value: Value = serde_json::from_str(json)?;
+    from_value_with_source(&value, registry, file)
+}
+
+/// Read a `Program` from an ESTree JSON value made from `file`
+pub fn from_value_with_source(
+    value: &Value,
+    registry: &SourceRegistry,
+    file: FileId,
+) -> Result<Program, Error> {
+    Program::from_json(Json {
+        value,
+        path: &Path::Root,
+        source: &Source {
+            file: Some(file),
+            lines: registry.text(file).map(LineIndex::new),
+        },
+    })
+}
+
+/// The file a document was made from, if known
+#[derive(Default)]
+struct Source {
+    file: Option<FileId>,
+    lines: Option<LineIndex>,
+}
+
+#[derive(Debug)]
+pub struct Error {
+    path: String,
+    kind: ErrorKind,
+}
+
+impl Error {
+    /// Where in the document reading failed, like
+    /// `$.body[0].expression.left`
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// The text wasn't valid JSON
+    Json(serde_json::Error),
+    /// A node's `type` isn't one that can appear here
+    UnknownType {
+        found: String,
+        expected: &'static str,
+    },
+    /// A node doesn't have a field it requires
+    MissingField(&'static str),
+    /// A value has the wrong shape, holding what was expected
+    Invalid(&'static str),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Json(ref e) => write!(f, "invalid JSON: {}", e),
+            ErrorKind::UnknownType {
+                ref found,
+                expected,
+            } => write!(f, "unknown node type `{}`, expected {}", found, expected),
+            ErrorKind::MissingField(field) => write!(f, "missing field `{}`", field),
+            ErrorKind::Invalid(expected) => write!(f, "expected {}", expected),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.kind, self.path)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error {
+            path: Path::Root.to_string(),
+            kind: ErrorKind::Json(e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self.kind {
+            ErrorKind::Json(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The way from the document root to a value, only rendered
+/// when reading fails
+enum Path<'a> {
+    Root,
+    Key(&'a Path<'a>, &'static str),
+    Index(&'a Path<'a>, usize),
+}
+
+impl<'a> fmt::Display for Path<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Path::Root => write!(f, "$"),
+            Path::Key(parent, key) => write!(f, "{}.{}", parent, key),
+            Path::Index(parent, idx) => write!(f, "{}[{}]", parent, idx),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Json<'a> {
+    value: &'a Value,
+    path: &'a Path<'a>,
+    source: &'a Source,
+}
+
+impl<'a> Json<'a> {
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error {
+            path: self.path.to_string(),
+            kind,
+        }
+    }
+
+    fn invalid(&self, expected: &'static str) -> Error {
+        self.error(ErrorKind::Invalid(expected))
+    }
+
+    fn unknown(&self, found: &str, expected: &'static str) -> Error {
+        self.error(ErrorKind::UnknownType {
+            found: found.to_string(),
+            expected,
+        })
+    }
+
+    fn str(&self) -> Result<&'a str, Error> {
+        self.value.as_str().ok_or_else(|| self.invalid("a string"))
+    }
+
+    fn object(&self) -> Result<Obj<'a>, Error> {
+        let map = self
+            .value
+            .as_object()
+            .ok_or_else(|| self.invalid("an object"))?;
+        Ok(Obj { json: *self, map })
+    }
+    /// The node here, which must have the `type` `ty`
+    fn expect(&self, ty: &'static str) -> Result<Obj<'a>, Error> {
+        let node = self.object()?;
+        let found = node.ty()?;
+        if found != ty {
+            return Err(self.unknown(found, ty));
+        }
+        Ok(node)
+    }
+}
+
+struct Obj<'a> {
+    json: Json<'a>,
+    map: &'a Map<String, Value>,
+}
+
+impl<'a> Obj<'a> {
+    fn ty(&self) -> Result<&'a str, Error> {
+        match self.map.get("type") {
+            Some(Value::String(ty)) => Ok(ty),
+            Some(_) => Err(Json {
+                value: self.json.value,
+                path: &Path::Key(self.json.path, "type"),
+                source: self.json.source,
+            }
+            .invalid("a string")),
+            None => Err(self.json.error(ErrorKind::MissingField("type"))),
+        }
+    }
+
+    fn has(&self, key: &str) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn field<T: FromJson>(&self, key: &'static str) -> Result<T, Error> {
+        match T::missing() {
+            Some(default) if !self.has(key) => Ok(default),
+            _ => self.field_with(key, T::from_json),
+        }
+    }
+
+    fn field_with<T, F>(&self, key: &'static str, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(Json) -> Result<T, Error>,
+    {
+        let value = self
+            .map
+            .get(key)
+            .ok_or_else(|| self.json.error(ErrorKind::MissingField(key)))?;
+        f(Json {
+            value,
+            path: &Path::Key(self.json.path, key),
+            source: self.json.source,
+        })
+    }
+
+    fn loc(&self) -> Result<SourceLocation, Error> {
+        let positions = if self.has("loc") {
+            Some(self.field_with("loc", |json| {
+                let loc = json.object()?;
+                Ok((loc.field("start")?, loc.field("end")?))
+            })?)
+        } else {
+            None
+        };
+        let range = if self.has("range") {
+            Some(self.field("range")?)
+        } else if self.has("start") && self.has("end") {
+            Some(Range::new(self.field("start")?, self.field("end")?))
+        } else {
+            None
+        };
+        let lines = self.json.source.lines.as_ref();
+        let ((start, end), range) = match (positions, range, lines) {
+            (Some(positions), Some(range), _) => (positions, range),
+            (None, Some(range), Some(lines)) => {
+                let start = lines.position(range.start);
+                let end = lines.position(range.end);
+                match (start, end) {
+                    (Some(start), Some(end)) => ((start, end), range),
+                    _ => return Err(self.json.invalid("offsets inside the source text")),
+                }
+            }
+            (Some((start, end)), None, Some(lines)) => {
+                match (lines.offset(start), lines.offset(end)) {
+                    (Some(s), Some(e)) => ((start, end), Range::new(s, e)),
+                    _ => return Err(self.json.invalid("a loc inside the source text")),
+                }
+            }
+            (None, _, _) => return Err(self.json.error(ErrorKind::MissingField("loc"))),
+            (_, None, _) => return Err(self.json.error(ErrorKind::MissingField("range"))),
+        };
+        Ok(SourceLocation {
+            source: self.json.source.file,
+            start,
+            end,
+            range,
+        })
+    }
+}
+
+trait FromJson: Sized {
+    fn from_json(json: Json) -> Result<Self, Error>;
+    /// The value of a field that isn't there, if it can be left out
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        json.value
+            .as_bool()
+            .ok_or_else(|| json.invalid("a boolean"))
+    }
+}
+
+impl FromJson for String {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        json.str().map(String::from)
+    }
+}
+
+impl FromJson for usize {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        json.value
+            .as_u64()
+            .map(|n| n as usize)
+            .ok_or_else(|| json.invalid("an offset"))
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        if json.value.is_null() {
+            return Ok(None);
+        }
+        T::from_json(json).map(Some)
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let items = json
+            .value
+            .as_array()
+            .ok_or_else(|| json.invalid("an array"))?;
+        items
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                T::from_json(Json {
+                    value,
+                    path: &Path::Index(json.path, idx),
+                    source: json.source,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<T: FromJson> FromJson for Box<T> {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        T::from_json(json).map(Box::new)
+    }
+}
+
+impl FromJson for Position {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let pos = json.object()?;
+        let line = pos.field_with("line", |json| {
+            json.value
+                .as_u64()
+                .and_then(NonZeroU64::new)
+                .ok_or_else(|| json.invalid("a line number starting at 1"))
+        })?;
+        let column = pos.field_with("column", |json| {
+            json.value.as_u64().ok_or_else(|| json.invalid("a column"))
+        })?;
+        match json.source.lines {
+            Some(ref lines) => lines
+                .position_from_utf16(line, column)
+                .ok_or_else(|| json.invalid("a UTF-16 column inside the source text")),
+            None => Ok(Position { line, column }),
+        }
+    }
+}
+
+impl FromJson for Range {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        match Vec::<usize>::from_json(json)?[..] {
+            [start, end] => Ok(Range::new(start, end)),
+            _ => Err(json.invalid("a [start, end] range")),
+        }
+    }
+}
+
+/// Read a node of type `$name` into the struct `$ty`, one
+/// field per ESTree key
+macro_rules! node {
+    ($ty:path, $name:expr, { $($field:ident: $key:expr),* $(,)* }) => {
+        impl FromJson for $ty {
+            fn from_json(json: Json) -> Result<Self, Error> {
+                let node = json.expect($name)?;
+                Ok(Self {
+                    $($field: node.field($key)?,)*
+                    loc: node.loc()?,
+                })
+            }
+        }
+    };
+}
+
+/// Read a value by matching it against each variant's `as_str` name
+macro_rules! named {
+    ($($ty:ident($expected:expr) { $($variant:ident),* $(,)* })*) => {$(
+        impl FromJson for $ty {
+            fn from_json(json: Json) -> Result<Self, Error> {
+                let name = json.str()?;
+                vec![$($ty::$variant),*]
+                    .into_iter()
+                    .find(|v| v.as_str() == name)
+                    .ok_or_else(|| json.invalid($expected))
+            }
+        }
+    )*};
+}
+
+named! {
+    SourceType("`script` or `module`") { Script, Module }
+    MethodKind("a method kind") { Constructor, Method, Get, Set }
+    PropertyKind("a property kind") { Init, Get, Set }
+    UnaryOperator("a unary operator") { Minus, Plus, Not, Tilde, TypeOf, Void, Delete }
+    UpdateOperator("an update operator") { Decrement, Increment }
+    BinaryOperator("a binary operator") {
+        And, GreaterThan, GreaterThanEqual, Div, Equal, In, InstanceOf, LeftShift, LessThan,
+        LessThanEqual, Minus, Mod, NotEqual, Or, Plus, RightShift, StrictEqual, StrictNotEqual,
+        Times, UnsignedRightShift, XOr,
+    }
+    AssignmentOperator("an assignment operator") {
+        AddAssign, AndAssign, Assign, DivAssign, LHSAssign, MulAssign, ModAssign, OrAssign,
+        RHSAssign, SubAssign, URHSAssign, XOrAssign,
+    }
+    LogicalOperator("a logical operator") { Or, And }
+    VariableKind("a variable kind") { Var, Let, Const, Using, AwaitUsing }
+}
+
+fn is_module_decl(ty: &str) -> bool {
+    matches!(
+        ty,
+        "ImportDeclaration"
+            | "ExportNamedDeclaration"
+            | "ExportDefaultDeclaration"
+            | "ExportAllDeclaration"
+    )
+}
+
+node!(Program, "Program", {
+    source_type: "sourceType",
+    body: "body",
+});
+
+impl FromJson for ProgramPart {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.object()?;
+        let ty = node.ty()?;
+        Ok(if ty == "ExpressionStatement" && node.has("directive") {
+            ProgramPart::Directive(Directive::from_json(json)?)
+        } else if is_module_decl(ty) {
+            ProgramPart::ModuleDecl(ModuleDeclaration::from_json(json)?)
+        } else {
+            ProgramPart::Statement(Statement::from_json(json)?)
+        })
+    }
+}
+
+node!(Directive, "ExpressionStatement", {
+    expression: "expression",
+    directive: "directive",
+});
+
+/// Read a `FunctionDeclaration` or `FunctionExpression`
+fn function(json: Json, ty: &'static str) -> Result<Function, Error> {
+    let node = json.expect(ty)?;
+    Ok(Function {
+        id: node.field("id")?,
+        params: node.field("params")?,
+        body: node.field("body")?,
+        generator: node.field("generator")?,
+        _async: node.field("async")?,
+        loc: node.loc()?,
+    })
+}
+
+/// Read a `ClassDeclaration` or `ClassExpression`
+fn class(json: Json, ty: &'static str) -> Result<Class, Error> {
+    let node = json.expect(ty)?;
+    Ok(Class {
+        id: node.field("id")?,
+        super_class: node.field("superClass")?,
+        body: node.field("body")?,
+        loc: node.loc()?,
+    })
+}
+
+node!(ClassBody, "ClassBody", { body: "body" });
+
+impl FromJson for MethodDefinition {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.expect("MethodDefinition")?;
+        Ok(MethodDefinition {
+            key: node.field("key")?,
+            value: node.field_with("value", |json| function(json, "FunctionExpression"))?,
+            kind: node.field("kind")?,
+            computed: node.field("computed")?,
+            _static: node.field("static")?,
+            loc: node.loc()?,
+        })
+    }
+}
+
+impl FromJson for Pattern {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "Identifier" => Pattern::Ident(FromJson::from_json(json)?),
+            "ObjectPattern" => Pattern::Object(FromJson::from_json(json)?),
+            "ArrayPattern" => Pattern::Array(FromJson::from_json(json)?),
+            "RestElement" => Pattern::RestElement(FromJson::from_json(json)?),
+            "AssignmentPattern" => Pattern::Assignment(FromJson::from_json(json)?),
+            ty => return Err(json.unknown(ty, "a pattern")),
+        })
+    }
+}
+
+node!(Object, "ObjectPattern", { properties: "properties" });
+
+// assignment properties don't keep a location of their own
+impl FromJson for AssignmentProperty {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.expect("Property")?;
+        Ok(AssignmentProperty {
+            key: node.field("key")?,
+            value: node.field("value")?,
+            kind: node.field("kind")?,
+            method: node.field("method")?,
+            shorthand: node.field("shorthand")?,
+            computed: node.field("computed")?,
+        })
+    }
+}
+
+node!(Array, "ArrayPattern", { elements: "elements" });
+
+node!(RestElement, "RestElement", { argument: "argument" });
+
+node!(Assignment, "AssignmentPattern", {
+    left: "left",
+    right: "right",
+});
+
+impl FromJson for Expression {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "ThisExpression" => Expression::This(FromJson::from_json(json)?),
+            "ArrayExpression" => Expression::Array(FromJson::from_json(json)?),
+            "ObjectExpression" => Expression::Object(FromJson::from_json(json)?),
+            "FunctionExpression" => {
+                Expression::Function(Box::new(function(json, "FunctionExpression")?))
+            }
+            "UnaryExpression" => Expression::Unary(FromJson::from_json(json)?),
+            "UpdateExpression" => Expression::Update(FromJson::from_json(json)?),
+            "BinaryExpression" => Expression::Binary(FromJson::from_json(json)?),
+            "AssignmentExpression" => Expression::Assignment(FromJson::from_json(json)?),
+            "LogicalExpression" => Expression::Logical(FromJson::from_json(json)?),
+            "MemberExpression" => Expression::Member(FromJson::from_json(json)?),
+            "ConditionalExpression" => Expression::Conditional(FromJson::from_json(json)?),
+            "CallExpression" => Expression::Call(FromJson::from_json(json)?),
+            "NewExpression" => Expression::New(FromJson::from_json(json)?),
+            "SequenceExpression" => Expression::Sequence(FromJson::from_json(json)?),
+            "SpreadElement" => Expression::Spread(FromJson::from_json(json)?),
+            "ArrowFunctionExpression" => Expression::ArrowFunction(FromJson::from_json(json)?),
+            "YieldExpression" => Expression::Yield(FromJson::from_json(json)?),
+            "AwaitExpression" => Expression::Await(FromJson::from_json(json)?),
+            "ChainExpression" => Expression::Chain(FromJson::from_json(json)?),
+            "Identifier" => Expression::Identifier(FromJson::from_json(json)?),
+            "Literal" | "TemplateLiteral" => Expression::Literal(FromJson::from_json(json)?),
+            "TaggedTemplateExpression" => Expression::TaggedTemplate(FromJson::from_json(json)?),
+            "ClassExpression" => Expression::Class(Box::new(class(json, "ClassExpression")?)),
+            "MetaProperty" => Expression::Meta(FromJson::from_json(json)?),
+            ty => return Err(json.unknown(ty, "an expression")),
+        })
+    }
+}
+
+node!(Identifier, "Identifier", { name: "name" });
+
+impl FromJson for Literal {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.object()?;
+        match node.ty()? {
+            "TemplateLiteral" => {
+                let template = TemplateLiteral::from_json(json)?;
+                return Ok(Literal {
+                    loc: template.loc,
+                    value: LiteralValue::TemplateLiteral(template),
+                });
+            }
+            "Literal" => (),
+            ty => return Err(json.unknown(ty, "Literal")),
+        }
+        let loc = node.loc()?;
+        let value = if node.has("regex") {
+            let regex = node.field_with("regex", |json| {
+                let regex = json.object()?;
+                Ok(RegEx {
+                    pattern: regex.field("pattern")?,
+                    flags: regex.field("flags")?,
+                    loc,
+                })
+            })?;
+            LiteralValue::RegExp(RegExpLiteral { regex, loc })
+        } else {
+            node.field_with("value", |json| match *json.value {
+                Value::String(ref s) => Ok(LiteralValue::String(s.clone())),
+                Value::Bool(b) => Ok(LiteralValue::Boolean(b)),
+                // BigInts and numbers too big for JSON have a null value
+                Value::Null => Ok(match (node.map.get("bigint"), node.map.get("raw")) {
+                    (Some(Value::String(_)), Some(Value::String(raw))) if raw.ends_with('n') => {
+                        LiteralValue::Number(raw.clone())
+                    }
+                    (Some(Value::String(bigint)), _) => {
+                        LiteralValue::Number(format!("{}n", bigint))
+                    }
+                    (_, Some(Value::String(raw))) if raw != "null" => {
+                        LiteralValue::Number(raw.clone())
+                    }
+                    _ => LiteralValue::Null,
+                }),
+                // keep the source text when there is one, `0xff` and
+                // `255` are different literals
+                Value::Number(ref n) => Ok(LiteralValue::Number(match node.map.get("raw") {
+                    Some(Value::String(raw)) => raw.clone(),
+                    _ => n.to_string(),
+                })),
+                _ => Err(json.invalid("a string, boolean, number or null")),
+            })?
+        };
+        Ok(Literal { value, loc })
+    }
+}
+
+impl FromJson for Spreadable {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "SpreadElement" => Spreadable::Spread(FromJson::from_json(json)?),
+            _ => Spreadable::Expr(FromJson::from_json(json)?),
+        })
+    }
+}
+
+node!(This, "ThisExpression", {});
+
+node!(Super, "Super", {});
+
+node!(expression::Array, "ArrayExpression", { elements: "elements" });
+
+node!(expression::Object, "ObjectExpression", { properties: "properties" });
+
+node!(Property, "Property", {
+    key: "key",
+    value: "value",
+    kind: "kind",
+    method: "method",
+    shorthand: "shorthand",
+    computed: "computed",
+});
+
+node!(Unary, "UnaryExpression", {
+    operator: "operator",
+    prefix: "prefix",
+    argument: "argument",
+});
+
+node!(Update, "UpdateExpression", {
+    operator: "operator",
+    argument: "argument",
+    prefix: "prefix",
+});
+
+node!(Binary, "BinaryExpression", {
+    operator: "operator",
+    left: "left",
+    right: "right",
+});
+
+node!(expression::Assignment, "AssignmentExpression", {
+    operator: "operator",
+    left: "left",
+    right: "right",
+});
+
+impl FromJson for PatOrExpr {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "ObjectPattern" | "ArrayPattern" | "RestElement" | "AssignmentPattern" => {
+                PatOrExpr::Pat(FromJson::from_json(json)?)
+            }
+            _ => PatOrExpr::Expr(FromJson::from_json(json)?),
+        })
+    }
+}
+
+node!(Logical, "LogicalExpression", {
+    operator: "operator",
+    left: "left",
+    right: "right",
+});
+
+// `optional` is newer than the rest of ESTree, not every
+// producer writes it
+impl FromJson for Member {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.expect("MemberExpression")?;
+        Ok(Member {
+            object: node.field("object")?,
+            property: node.field("property")?,
+            computed: node.field("computed")?,
+            optional: node.field::<Option<bool>>("optional")?.unwrap_or(false),
+            loc: node.loc()?,
+        })
+    }
+}
+
+impl FromJson for Parent {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "Super" => Parent::Super(FromJson::from_json(json)?),
+            _ => Parent::Expr(FromJson::from_json(json)?),
+        })
+    }
+}
+
+node!(Conditional, "ConditionalExpression", {
+    test: "test",
+    consequent: "consequent",
+    alternate: "alternate",
+});
+
+impl FromJson for Call {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.expect("CallExpression")?;
+        Ok(Call {
+            callee: node.field("callee")?,
+            arguments: node.field("arguments")?,
+            optional: node.field::<Option<bool>>("optional")?.unwrap_or(false),
+            loc: node.loc()?,
+        })
+    }
+}
+
+node!(New, "NewExpression", {
+    callee: "callee",
+    arguments: "arguments",
+});
+
+node!(Sequence, "SequenceExpression", { expressions: "expressions" });
+
+node!(SpreadElement, "SpreadElement", { argument: "argument" });
+
+node!(ArrowFunction, "ArrowFunctionExpression", {
+    id: "id",
+    params: "params",
+    body: "body",
+    generator: "generator",
+    _async: "async",
+});
+
+impl FromJson for ArrowFunctionBody {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "BlockStatement" => ArrowFunctionBody::Block(FromJson::from_json(json)?),
+            _ => ArrowFunctionBody::Expr(FromJson::from_json(json)?),
+        })
+    }
+}
+
+node!(Yield, "YieldExpression", {
+    argument: "argument",
+    delegate: "delegate",
+});
+
+node!(Await, "AwaitExpression", { argument: "argument" });
+
+node!(Chain, "ChainExpression", { expression: "expression" });
+
+node!(TemplateLiteral, "TemplateLiteral", {
+    quasis: "quasis",
+    expressions: "expressions",
+});
+
+node!(TaggedTemplate, "TaggedTemplateExpression", {
+    tag: "tag",
+    quasi: "quasi",
+});
+
+node!(TemplateElement, "TemplateElement", {
+    tail: "tail",
+    value: "value",
+});
+
+impl FromJson for TemplateValue {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let value = json.object()?;
+        Ok(TemplateValue {
+            // tagged templates may hold invalid escapes, which have
+            // no cooked value
+            cooked: value.field::<Option<String>>("cooked")?.unwrap_or_default(),
+            raw: value.field("raw")?,
+        })
+    }
+}
+
+node!(MetaProperty, "MetaProperty", {
+    meta: "meta",
+    property: "property",
+});
+
+impl FromJson for Statement {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.object()?;
+        Ok(match node.ty()? {
+            "ExpressionStatement" => Statement::Expr(node.field("expression")?),
+            "BlockStatement" => Statement::Block(FromJson::from_json(json)?),
+            "EmptyStatement" => Statement::Empty(FromJson::from_json(json)?),
+            "DebuggerStatement" => Statement::Debugger(FromJson::from_json(json)?),
+            "WithStatement" => Statement::With(FromJson::from_json(json)?),
+            "ReturnStatement" => Statement::Return(FromJson::from_json(json)?),
+            "LabeledStatement" => Statement::Labeled(FromJson::from_json(json)?),
+            "BreakStatement" => Statement::Break(FromJson::from_json(json)?),
+            "ContinueStatement" => Statement::Continue(FromJson::from_json(json)?),
+            "IfStatement" => Statement::If(FromJson::from_json(json)?),
+            "SwitchStatement" => Statement::Switch(FromJson::from_json(json)?),
+            "ThrowStatement" => Statement::Throw(FromJson::from_json(json)?),
+            "TryStatement" => Statement::Try(FromJson::from_json(json)?),
+            "WhileStatement" => Statement::While(FromJson::from_json(json)?),
+            "DoWhileStatement" => Statement::DoWhile(FromJson::from_json(json)?),
+            "ForStatement" => Statement::For(FromJson::from_json(json)?),
+            "ForInStatement" => Statement::ForIn(FromJson::from_json(json)?),
+            "ForOfStatement" => Statement::ForOf(FromJson::from_json(json)?),
+            "FunctionDeclaration" | "VariableDeclaration" | "ClassDeclaration" => {
+                Statement::Decl(FromJson::from_json(json)?)
+            }
+            ty => return Err(json.unknown(ty, "a statement")),
+        })
+    }
+}
+
+node!(Block, "BlockStatement", { body: "body" });
+
+node!(FunctionBody, "BlockStatement", { body: "body" });
+
+impl FromJson for FunctionBodyPart {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        let node = json.object()?;
+        Ok(
+            if node.ty()? == "ExpressionStatement" && node.has("directive") {
+                FunctionBodyPart::Directive(FromJson::from_json(json)?)
+            } else {
+                FunctionBodyPart::Statement(FromJson::from_json(json)?)
+            },
+        )
+    }
+}
+
+node!(Empty, "EmptyStatement", {});
+
+node!(Debugger, "DebuggerStatement", {});
+
+node!(With, "WithStatement", {
+    object: "object",
+    body: "body",
+});
+
+node!(Return, "ReturnStatement", { argument: "argument" });
+
+node!(Labeled, "LabeledStatement", {
+    label: "label",
+    body: "body",
+});
+
+node!(Break, "BreakStatement", { label: "label" });
+
+node!(Continue, "ContinueStatement", { label: "label" });
+
+node!(If, "IfStatement", {
+    test: "test",
+    consequent: "consequent",
+    alternate: "alternate",
+});
+
+node!(Switch, "SwitchStatement", {
+    discriminant: "discriminant",
+    cases: "cases",
+});
+
+node!(SwitchCase, "SwitchCase", {
+    test: "test",
+    consequent: "consequent",
+});
+
+node!(Throw, "ThrowStatement", { argument: "argument" });
+
+node!(Try, "TryStatement", {
+    block: "block",
+    handler: "handler",
+    finalizer: "finalizer",
+});
+
+node!(CatchClause, "CatchClause", {
+    param: "param",
+    body: "body",
+});
+
+node!(While, "WhileStatement", {
+    test: "test",
+    body: "body",
+});
+
+node!(DoWhile, "DoWhileStatement", {
+    body: "body",
+    test: "test",
+});
+
+node!(For, "ForStatement", {
+    init: "init",
+    test: "test",
+    update: "update",
+    body: "body",
+});
+
+impl FromJson for ForInit {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        if json.value.is_null() {
+            return Ok(ForInit::Null);
+        }
+        Ok(match json.object()?.ty()? {
+            "VariableDeclaration" => ForInit::Var(FromJson::from_json(json)?),
+            _ => ForInit::Expr(FromJson::from_json(json)?),
+        })
+    }
+
+    fn missing() -> Option<Self> {
+        Some(ForInit::Null)
+    }
+}
+
+node!(ForIn, "ForInStatement", {
+    left: "left",
+    right: "right",
+    body: "body",
+});
+
+node!(ForOf, "ForOfStatement", {
+    left: "left",
+    right: "right",
+    body: "body",
+    _await: "await",
+});
+
+impl FromJson for ForLeft {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "VariableDeclaration" => ForLeft::Var(FromJson::from_json(json)?),
+            _ => ForLeft::Pat(FromJson::from_json(json)?),
+        })
+    }
+}
+
+impl FromJson for Declaration {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "FunctionDeclaration" => Declaration::Function(function(json, "FunctionDeclaration")?),
+            "VariableDeclaration" => Declaration::Variable(FromJson::from_json(json)?),
+            "ClassDeclaration" => Declaration::Class(class(json, "ClassDeclaration")?),
+            ty => return Err(json.unknown(ty, "a declaration")),
+        })
+    }
+}
+
+node!(VariableDeclaration, "VariableDeclaration", {
+    declarations: "declarations",
+    kind: "kind",
+});
+
+node!(VariableDeclarator, "VariableDeclarator", {
+    id: "id",
+    init: "init",
+});
+
+impl FromJson for ModuleDeclaration {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "ImportDeclaration" => ModuleDeclaration::Import(FromJson::from_json(json)?),
+            _ => ModuleDeclaration::Export(FromJson::from_json(json)?),
+        })
+    }
+}
+
+node!(ImportDeclaration, "ImportDeclaration", {
+    specifiers: "specifiers",
+    source: "source",
+});
+
+impl FromJson for ImportSpecifier {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "ImportSpecifier" => ImportSpecifier::Named(FromJson::from_json(json)?),
+            "ImportDefaultSpecifier" => ImportSpecifier::Default(FromJson::from_json(json)?),
+            "ImportNamespaceSpecifier" => ImportSpecifier::NameSpace(FromJson::from_json(json)?),
+            ty => return Err(json.unknown(ty, "an import specifier")),
+        })
+    }
+}
+
+node!(Import, "ImportSpecifier", {
+    import: "imported",
+    local: "local",
+});
+
+node!(ImportDefault, "ImportDefaultSpecifier", { local: "local" });
+
+node!(ImportNamespace, "ImportNamespaceSpecifier", { local: "local" });
+
+impl FromJson for ExportDeclaration {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "ExportNamedDeclaration" => ExportDeclaration::Named(FromJson::from_json(json)?),
+            "ExportDefaultDeclaration" => ExportDeclaration::Default(FromJson::from_json(json)?),
+            "ExportAllDeclaration" => ExportDeclaration::All(FromJson::from_json(json)?),
+            ty => return Err(json.unknown(ty, "a module declaration")),
+        })
+    }
+}
+
+node!(ExportNamed, "ExportNamedDeclaration", {
+    declaration: "declaration",
+    specifiers: "specifiers",
+    source: "source",
+});
+
+node!(ExportSpecifier, "ExportSpecifier", {
+    local: "local",
+    exported: "exported",
+});
+
+node!(ExportDefault, "ExportDefaultDeclaration", { declaration: "declaration" });
+
+impl FromJson for ExportDecl {
+    fn from_json(json: Json) -> Result<Self, Error> {
+        Ok(match json.object()?.ty()? {
+            "FunctionDeclaration" | "VariableDeclaration" | "ClassDeclaration" => {
+                ExportDecl::Decl(FromJson::from_json(json)?)
+            }
+            _ => ExportDecl::Expr(FromJson::from_json(json)?),
+        })
+    }
+}
+
+node!(ExportAll, "ExportAllDeclaration", { source: "source" });
@@ -1,5 +1,8 @@
-use crate::{Class, Function, FunctionBody, Node, NodeKind, Pattern, SourceLocation};
-#[derive(Debug)]
+use crate::{
+    Children, Class, Function, FunctionBody, Node, NodeKind, Pattern, SourceLocation, StableHasher,
+    Structural, StructuralOptions,
+};
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum Expression {
     This(This),
     Array(Array),
@@ -15,15 +18,16 @@ pub enum Expression {
     Call(Box<Call>),
     New(Box<New>),
     Sequence(Sequence),
-    Spread(Box<Expression>),
+    Spread(Box<SpreadElement>),
     ArrowFunction(Box<ArrowFunction>),
-    Yield(Box<Expression>),
-    Await(Box<Expression>),
+    Yield(Box<Yield>),
+    Await(Box<Await>),
     Identifier(Box<Identifier>),
     Literal(Box<Literal>),
     TaggedTemplate(Box<TaggedTemplate>),
     Class(Box<Class>),
     Meta(MetaProperty),
+    Chain(Box<Chain>),
 }
 
 impl Node for Expression {
@@ -32,7 +36,7 @@ impl Node for Expression {
             Expression::This(ref inner) => inner.loc(),
             Expression::Array(ref inner) => inner.loc(),
             Expression::Object(ref inner) => inner.loc(),
-            Expression::Function(ref inner) => inner.loc.clone(),
+            Expression::Function(ref inner) => inner.loc,
             Expression::Unary(ref inner) => inner.loc(),
             Expression::Update(ref inner) => inner.loc(),
             Expression::Binary(ref inner) => inner.loc(),
@@ -46,11 +50,12 @@ impl Node for Expression {
             Expression::Spread(ref inner) => inner.loc(),
             Expression::Yield(ref inner) => inner.loc(),
             Expression::Await(ref inner) => inner.loc(),
+            Expression::Chain(ref inner) => inner.loc(),
             Expression::Identifier(ref inner) => inner.loc(),
             Expression::Literal(ref inner) => inner.loc(),
             Expression::ArrowFunction(ref inner) => inner.loc(),
             Expression::TaggedTemplate(ref inner) => inner.loc(),
-            Expression::Class(ref inner) => inner.loc.clone(),
+            Expression::Class(ref inner) => inner.loc,
             Expression::Meta(ref inner) => inner.loc(),
         }
     }
@@ -73,6 +78,7 @@ impl Node for Expression {
             Expression::Spread(ref inner) => inner.kind(),
             Expression::Yield(ref inner) => inner.kind(),
             Expression::Await(ref inner) => inner.kind(),
+            Expression::Chain(ref inner) => inner.kind(),
             Expression::Identifier(ref inner) => inner.kind(),
             Expression::Literal(ref inner) => inner.kind(),
             Expression::ArrowFunction(ref inner) => inner.kind(),
@@ -81,18 +87,82 @@ impl Node for Expression {
             Expression::Meta(ref inner) => inner.kind(),
         }
     }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        match self {
+            Expression::This(ref inner) => Some(inner),
+            Expression::Array(ref inner) => Some(inner),
+            Expression::Object(ref inner) => Some(inner),
+            Expression::Function(_) => None,
+            Expression::Unary(ref inner) => Some(&**inner),
+            Expression::Update(ref inner) => Some(&**inner),
+            Expression::Binary(ref inner) => Some(&**inner),
+            Expression::Assignment(ref inner) => Some(&**inner),
+            Expression::Logical(ref inner) => Some(&**inner),
+            Expression::Member(ref inner) => Some(&**inner),
+            Expression::Conditional(ref inner) => Some(&**inner),
+            Expression::Call(ref inner) => Some(&**inner),
+            Expression::New(ref inner) => Some(&**inner),
+            Expression::Sequence(ref inner) => Some(inner),
+            Expression::Spread(ref inner) => Some(&**inner),
+            Expression::ArrowFunction(ref inner) => Some(&**inner),
+            Expression::Yield(ref inner) => Some(&**inner),
+            Expression::Await(ref inner) => Some(&**inner),
+            Expression::Identifier(ref inner) => Some(&**inner),
+            Expression::Literal(ref inner) => Some(&**inner),
+            Expression::TaggedTemplate(ref inner) => Some(&**inner),
+            Expression::Class(_) => None,
+            Expression::Meta(ref inner) => Some(inner),
+            Expression::Chain(ref inner) => Some(&**inner),
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            Expression::This(ref inner) => inner.for_each_child(f),
+            Expression::Array(ref inner) => inner.for_each_child(f),
+            Expression::Object(ref inner) => inner.for_each_child(f),
+            Expression::Function(ref inner) => inner.each_node(f),
+            Expression::Unary(ref inner) => inner.for_each_child(f),
+            Expression::Update(ref inner) => inner.for_each_child(f),
+            Expression::Binary(ref inner) => inner.for_each_child(f),
+            Expression::Assignment(ref inner) => inner.for_each_child(f),
+            Expression::Logical(ref inner) => inner.for_each_child(f),
+            Expression::Member(ref inner) => inner.for_each_child(f),
+            Expression::Conditional(ref inner) => inner.for_each_child(f),
+            Expression::Call(ref inner) => inner.for_each_child(f),
+            Expression::New(ref inner) => inner.for_each_child(f),
+            Expression::Sequence(ref inner) => inner.for_each_child(f),
+            Expression::Spread(ref inner) => inner.for_each_child(f),
+            Expression::Yield(ref inner) => inner.for_each_child(f),
+            Expression::Await(ref inner) => inner.for_each_child(f),
+            Expression::Chain(ref inner) => inner.for_each_child(f),
+            Expression::Identifier(ref inner) => inner.for_each_child(f),
+            Expression::Literal(ref inner) => inner.for_each_child(f),
+            Expression::ArrowFunction(ref inner) => inner.for_each_child(f),
+            Expression::TaggedTemplate(ref inner) => inner.for_each_child(f),
+            Expression::Class(ref inner) => inner.each_node(f),
+            Expression::Meta(ref inner) => inner.for_each_child(f),
+        }
+    }
+}
+
+impl Children for Expression {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Identifier {
     pub name: String,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Literal {
     pub value: LiteralValue,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum LiteralValue {
     String(String),
     Boolean(bool),
@@ -101,7 +171,16 @@ pub enum LiteralValue {
     RegExp(RegExpLiteral),
     TemplateLiteral(TemplateLiteral),
 }
-#[derive(Debug)]
+// The pattern and flags of a regex are part of its literal's value
+// rather than child nodes
+impl Children for LiteralValue {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        if let LiteralValue::TemplateLiteral(ref inner) = self {
+            inner.each_node(f);
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct RegExpLiteral {
     pub regex: RegEx,
     pub loc: SourceLocation,
@@ -109,14 +188,24 @@ pub struct RegExpLiteral {
 
 impl Node for RegExpLiteral {
     fn loc(&self) -> SourceLocation {
-        self.loc.clone()
+        self.loc
     }
 
     fn kind(&self) -> NodeKind {
         NodeKind::Literal
     }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(&self.regex)
+    }
 }
-#[derive(Debug)]
+
+impl Children for RegExpLiteral {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct RegEx {
     pub pattern: String,
     pub flags: String,
@@ -125,39 +214,55 @@ pub struct RegEx {
 
 impl Node for RegEx {
     fn loc(&self) -> SourceLocation {
-        self.loc.clone()
+        self.loc
     }
 
     fn kind(&self) -> NodeKind {
         NodeKind::Literal
     }
 }
+
+impl Children for RegEx {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct This {
     // pub type: "ThisExpression"
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Super {}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Array {
     pub elements: Vec<Option<Spreadable>>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum Spreadable {
     Expr(Expression),
     Spread(SpreadElement),
 }
+
+impl Children for Spreadable {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            Spreadable::Expr(ref inner) => inner.each_node(f),
+            Spreadable::Spread(ref inner) => inner.each_node(f),
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Object {
     pub properties: Vec<Property>,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Property {
+    #[structural(name_unless = "computed")]
     pub key: Expression,
     pub value: Expression,
     pub kind: PropertyKind,
@@ -165,20 +270,33 @@ pub struct Property {
     pub shorthand: bool,
     pub computed: bool,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum PropertyKind {
     Init,
     Get,
     Set,
 }
+
+impl Children for PropertyKind {}
+
+impl PropertyKind {
+    /// The ESTree name for this kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PropertyKind::Init => "init",
+            PropertyKind::Get => "get",
+            PropertyKind::Set => "set",
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Unary {
     pub operator: UnaryOperator,
     pub prefix: bool,
     pub argument: Expression,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum UnaryOperator {
     Minus,
     Plus,
@@ -188,27 +306,56 @@ pub enum UnaryOperator {
     Void,
     Delete,
 }
+
+impl Children for UnaryOperator {}
+
+impl UnaryOperator {
+    /// The source text for this operator
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnaryOperator::Minus => "-",
+            UnaryOperator::Plus => "+",
+            UnaryOperator::Not => "!",
+            UnaryOperator::Tilde => "~",
+            UnaryOperator::TypeOf => "typeof",
+            UnaryOperator::Void => "void",
+            UnaryOperator::Delete => "delete",
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Update {
     pub operator: UpdateOperator,
     pub argument: Expression,
     pub prefix: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum UpdateOperator {
     Decrement,
     Increment,
 }
+
+impl Children for UpdateOperator {}
+
+impl UpdateOperator {
+    /// The source text for this operator
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateOperator::Decrement => "--",
+            UpdateOperator::Increment => "++",
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Binary {
     pub operator: BinaryOperator,
     pub left: Expression,
     pub right: Expression,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum BinaryOperator {
     And,
     GreaterThan,
@@ -232,19 +379,59 @@ pub enum BinaryOperator {
     UnsignedRightShift,
     XOr,
 }
+
+impl Children for BinaryOperator {}
+
+impl BinaryOperator {
+    /// The source text for this operator
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOperator::And => "&",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanEqual => ">=",
+            BinaryOperator::Div => "/",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::In => "in",
+            BinaryOperator::InstanceOf => "instanceof",
+            BinaryOperator::LeftShift => "<<",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanEqual => "<=",
+            BinaryOperator::Minus => "-",
+            BinaryOperator::Mod => "%",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::Or => "|",
+            BinaryOperator::Plus => "+",
+            BinaryOperator::RightShift => ">>",
+            BinaryOperator::StrictEqual => "===",
+            BinaryOperator::StrictNotEqual => "!==",
+            BinaryOperator::Times => "*",
+            BinaryOperator::UnsignedRightShift => ">>>",
+            BinaryOperator::XOr => "^",
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Assignment {
     pub operator: AssignmentOperator,
     pub left: PatOrExpr,
     pub right: Expression,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum PatOrExpr {
     Pat(Pattern),
     Expr(Expression),
 }
-#[derive(Debug)]
+
+impl Children for PatOrExpr {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            PatOrExpr::Pat(ref inner) => inner.each_node(f),
+            PatOrExpr::Expr(ref inner) => inner.each_node(f),
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum AssignmentOperator {
     AddAssign,
     AndAssign,
@@ -259,101 +446,174 @@ pub enum AssignmentOperator {
     URHSAssign,
     XOrAssign,
 }
+
+impl Children for AssignmentOperator {}
+
+impl AssignmentOperator {
+    /// The source text for this operator
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssignmentOperator::AddAssign => "+=",
+            AssignmentOperator::AndAssign => "&=",
+            AssignmentOperator::Assign => "=",
+            AssignmentOperator::DivAssign => "/=",
+            AssignmentOperator::LHSAssign => "<<=",
+            AssignmentOperator::MulAssign => "*=",
+            AssignmentOperator::ModAssign => "%=",
+            AssignmentOperator::OrAssign => "|=",
+            AssignmentOperator::RHSAssign => ">>=",
+            AssignmentOperator::SubAssign => "-=",
+            AssignmentOperator::URHSAssign => ">>>=",
+            AssignmentOperator::XOrAssign => "^=",
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Logical {
     pub operator: LogicalOperator,
     pub left: Expression,
     pub right: Expression,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum LogicalOperator {
     Or,
     And,
 }
+
+impl Children for LogicalOperator {}
+
+impl LogicalOperator {
+    /// The source text for this operator
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogicalOperator::Or => "||",
+            LogicalOperator::And => "&&",
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Member {
     pub object: Parent,
+    #[structural(name_unless = "computed")]
     pub property: Expression,
     pub computed: bool,
+    /// If accessed with `?.`
+    pub optional: bool,
 }
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Conditional {
     pub test: Expression,
     pub alternate: Expression,
     pub consequent: Expression,
 }
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Call {
     pub callee: Parent,
     pub arguments: Vec<Spreadable>,
+    /// If called with `?.()`
+    pub optional: bool,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum Parent {
     Expr(Expression),
     Super(Super),
 }
+
+impl Children for Parent {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            Parent::Expr(ref inner) => inner.each_node(f),
+            Parent::Super(ref inner) => inner.each_node(f),
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct New {
     pub callee: Expression,
     pub arguments: Vec<Spreadable>,
 }
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Sequence {
     pub expressions: Vec<Expression>,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct SpreadElement {
     pub argument: Expression,
 }
 #[inherit(Function, Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ArrowFunction {
     pub body: ArrowFunctionBody,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum ArrowFunctionBody {
     Expr(Expression),
     Block(FunctionBody),
 }
+
+impl Children for ArrowFunctionBody {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ArrowFunctionBody::Expr(ref inner) => inner.each_node(f),
+            ArrowFunctionBody::Block(ref inner) => inner.each_node(f),
+        }
+    }
+}
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Yield {
     pub argument: Option<Expression>,
     pub delegate: bool,
 }
+#[inherit(Expression)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
+pub struct Await {
+    pub argument: Expression,
+}
+/// The outermost member or call of an optional chain, everything
+/// a `?.` inside of it short-circuits
+#[inherit(Expression)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
+pub struct Chain {
+    pub expression: Expression,
+}
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct TemplateLiteral {
     pub quasis: Vec<TemplateElement>,
     pub expressions: Vec<Expression>,
 }
 #[inherit(Expression)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct TaggedTemplate {
     pub tag: Expression,
     pub quasi: TemplateLiteral,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct TemplateElement {
     pub tail: bool,
     pub value: TemplateValue,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct TemplateValue {
     pub cooked: String,
     pub raw: String,
 }
+
+impl Children for TemplateValue {}
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct MetaProperty {
+    #[structural(name)]
     pub meta: Identifier,
+    #[structural(name)]
     pub property: Identifier,
 }
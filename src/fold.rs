@@ -0,0 +1,2385 @@
+//! An owned transformation from one tree to another.
+//!
+//! Every node type has a `fold_*` method on `Fold` that takes the
+//! node by value and returns its replacement, by default the
+//! result of the matching `walk_*` function which folds each of
+//! its fields. Since the replacement only has to share a type with
+//! the original, an `Expression::ArrowFunction` can come back as
+//! an `Expression::Function`. Returning a node without calling its
+//! `walk_*` function leaves its children untouched.
+//!
+//! Anywhere a list of statements appears the `fold_*_many` methods
+//! are used instead, allowing one statement to be replaced by zero
+//! or more statements.
+//!
+//! `TryFold` is the same walk for folds that may need to stop
+//! partway through, with `try_fold_*` methods and `try_walk_*`
+//! functions returning a `ControlFlow`.
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+
+use crate::expression::{
+    self, ArrowFunction, ArrowFunctionBody, Await, Binary, Call, Chain, Conditional, Expression,
+    Identifier, Literal, LiteralValue, Logical, Member, MetaProperty, New, Parent, PatOrExpr,
+    Property, Sequence, SpreadElement, Spreadable, Super, TaggedTemplate, TemplateElement,
+    TemplateLiteral, This, Unary, Update, Yield,
+};
+use crate::module::{
+    ExportAll, ExportDecl, ExportDeclaration, ExportDefault, ExportNamed, ExportSpecifier, Import,
+    ImportDeclaration, ImportDefault, ImportNamespace, ImportSpecifier, ModuleDeclaration,
+};
+use crate::node::Node;
+use crate::statement::{
+    Block, Break, CatchClause, Continue, Debugger, Declaration, DoWhile, Empty, For, ForIn,
+    ForInit, ForLeft, ForOf, FunctionBody, FunctionBodyPart, If, Labeled, Return, Statement,
+    Switch, SwitchCase, Throw, Try, VariableDeclaration, VariableDeclarator, While, With,
+};
+use crate::{
+    Array, Assignment, AssignmentProperty, Class, ClassBody, Directive, Function, MethodDefinition,
+    Object, Pattern, Program, ProgramPart, RestElement,
+};
+
+pub trait Fold {
+    fn fold_program(&mut self, program: Program) -> Program {
+        walk_program(self, program)
+    }
+    fn fold_program_part_many(&mut self, part: ProgramPart) -> Vec<ProgramPart> {
+        walk_program_part_many(self, part)
+    }
+    fn fold_program_part(&mut self, part: ProgramPart) -> ProgramPart {
+        walk_program_part(self, part)
+    }
+    fn fold_directive(&mut self, directive: Directive) -> Directive {
+        walk_directive(self, directive)
+    }
+    fn fold_function(&mut self, function: Function) -> Function {
+        walk_function(self, function)
+    }
+    fn fold_function_body(&mut self, body: FunctionBody) -> FunctionBody {
+        walk_function_body(self, body)
+    }
+    fn fold_function_body_part_many(&mut self, part: FunctionBodyPart) -> Vec<FunctionBodyPart> {
+        walk_function_body_part_many(self, part)
+    }
+    fn fold_function_body_part(&mut self, part: FunctionBodyPart) -> FunctionBodyPart {
+        walk_function_body_part(self, part)
+    }
+    fn fold_class(&mut self, class: Class) -> Class {
+        walk_class(self, class)
+    }
+    fn fold_class_body(&mut self, body: ClassBody) -> ClassBody {
+        walk_class_body(self, body)
+    }
+    fn fold_method_definition(&mut self, method: MethodDefinition) -> MethodDefinition {
+        walk_method_definition(self, method)
+    }
+    fn fold_pattern(&mut self, pattern: Pattern) -> Pattern {
+        walk_pattern(self, pattern)
+    }
+    fn fold_object_pattern(&mut self, object: Object) -> Object {
+        walk_object_pattern(self, object)
+    }
+    fn fold_assignment_property(&mut self, property: AssignmentProperty) -> AssignmentProperty {
+        walk_assignment_property(self, property)
+    }
+    fn fold_array_pattern(&mut self, array: Array) -> Array {
+        walk_array_pattern(self, array)
+    }
+    fn fold_rest_element(&mut self, rest: RestElement) -> RestElement {
+        walk_rest_element(self, rest)
+    }
+    fn fold_assignment_pattern(&mut self, assignment: Assignment) -> Assignment {
+        walk_assignment_pattern(self, assignment)
+    }
+    fn fold_expression(&mut self, expr: Expression) -> Expression {
+        walk_expression(self, expr)
+    }
+    fn fold_identifier(&mut self, ident: Identifier) -> Identifier {
+        ident
+    }
+    fn fold_literal(&mut self, literal: Literal) -> Literal {
+        walk_literal(self, literal)
+    }
+    fn fold_this(&mut self, this: This) -> This {
+        this
+    }
+    fn fold_super(&mut self, sup: Super) -> Super {
+        sup
+    }
+    fn fold_array_expression(&mut self, array: expression::Array) -> expression::Array {
+        walk_array_expression(self, array)
+    }
+    fn fold_spreadable(&mut self, spreadable: Spreadable) -> Spreadable {
+        walk_spreadable(self, spreadable)
+    }
+    fn fold_spread_element(&mut self, spread: SpreadElement) -> SpreadElement {
+        walk_spread_element(self, spread)
+    }
+    fn fold_object_expression(&mut self, object: expression::Object) -> expression::Object {
+        walk_object_expression(self, object)
+    }
+    fn fold_property(&mut self, property: Property) -> Property {
+        walk_property(self, property)
+    }
+    fn fold_unary(&mut self, unary: Unary) -> Unary {
+        walk_unary(self, unary)
+    }
+    fn fold_update(&mut self, update: Update) -> Update {
+        walk_update(self, update)
+    }
+    fn fold_binary(&mut self, binary: Binary) -> Binary {
+        walk_binary(self, binary)
+    }
+    fn fold_assignment_expression(
+        &mut self,
+        assignment: expression::Assignment,
+    ) -> expression::Assignment {
+        walk_assignment_expression(self, assignment)
+    }
+    fn fold_pat_or_expr(&mut self, pat_or_expr: PatOrExpr) -> PatOrExpr {
+        walk_pat_or_expr(self, pat_or_expr)
+    }
+    fn fold_logical(&mut self, logical: Logical) -> Logical {
+        walk_logical(self, logical)
+    }
+    fn fold_member(&mut self, member: Member) -> Member {
+        walk_member(self, member)
+    }
+    fn fold_parent(&mut self, parent: Parent) -> Parent {
+        walk_parent(self, parent)
+    }
+    fn fold_conditional(&mut self, conditional: Conditional) -> Conditional {
+        walk_conditional(self, conditional)
+    }
+    fn fold_call(&mut self, call: Call) -> Call {
+        walk_call(self, call)
+    }
+    fn fold_new(&mut self, new: New) -> New {
+        walk_new(self, new)
+    }
+    fn fold_sequence(&mut self, sequence: Sequence) -> Sequence {
+        walk_sequence(self, sequence)
+    }
+    fn fold_arrow_function(&mut self, arrow: ArrowFunction) -> ArrowFunction {
+        walk_arrow_function(self, arrow)
+    }
+    fn fold_arrow_function_body(&mut self, body: ArrowFunctionBody) -> ArrowFunctionBody {
+        walk_arrow_function_body(self, body)
+    }
+    fn fold_yield(&mut self, expr: Yield) -> Yield {
+        walk_yield(self, expr)
+    }
+    fn fold_await(&mut self, expr: Await) -> Await {
+        walk_await(self, expr)
+    }
+    fn fold_chain(&mut self, chain: Chain) -> Chain {
+        walk_chain(self, chain)
+    }
+    fn fold_template_literal(&mut self, template: TemplateLiteral) -> TemplateLiteral {
+        walk_template_literal(self, template)
+    }
+    fn fold_template_element(&mut self, element: TemplateElement) -> TemplateElement {
+        element
+    }
+    fn fold_tagged_template(&mut self, tagged: TaggedTemplate) -> TaggedTemplate {
+        walk_tagged_template(self, tagged)
+    }
+    fn fold_meta_property(&mut self, meta: MetaProperty) -> MetaProperty {
+        walk_meta_property(self, meta)
+    }
+    fn fold_statement_many(&mut self, stmt: Statement) -> Vec<Statement> {
+        vec![self.fold_statement(stmt)]
+    }
+    fn fold_statement(&mut self, stmt: Statement) -> Statement {
+        walk_statement(self, stmt)
+    }
+    fn fold_block(&mut self, block: Block) -> Block {
+        walk_block(self, block)
+    }
+    fn fold_empty(&mut self, empty: Empty) -> Empty {
+        empty
+    }
+    fn fold_debugger(&mut self, debugger: Debugger) -> Debugger {
+        debugger
+    }
+    fn fold_with(&mut self, with: With) -> With {
+        walk_with(self, with)
+    }
+    fn fold_return(&mut self, ret: Return) -> Return {
+        walk_return(self, ret)
+    }
+    fn fold_labeled(&mut self, labeled: Labeled) -> Labeled {
+        walk_labeled(self, labeled)
+    }
+    fn fold_break(&mut self, brk: Break) -> Break {
+        walk_break(self, brk)
+    }
+    fn fold_continue(&mut self, cont: Continue) -> Continue {
+        walk_continue(self, cont)
+    }
+    fn fold_if(&mut self, stmt: If) -> If {
+        walk_if(self, stmt)
+    }
+    fn fold_switch(&mut self, switch: Switch) -> Switch {
+        walk_switch(self, switch)
+    }
+    fn fold_switch_case(&mut self, case: SwitchCase) -> SwitchCase {
+        walk_switch_case(self, case)
+    }
+    fn fold_throw(&mut self, throw: Throw) -> Throw {
+        walk_throw(self, throw)
+    }
+    fn fold_try(&mut self, stmt: Try) -> Try {
+        walk_try(self, stmt)
+    }
+    fn fold_catch_clause(&mut self, clause: CatchClause) -> CatchClause {
+        walk_catch_clause(self, clause)
+    }
+    fn fold_while(&mut self, stmt: While) -> While {
+        walk_while(self, stmt)
+    }
+    fn fold_do_while(&mut self, stmt: DoWhile) -> DoWhile {
+        walk_do_while(self, stmt)
+    }
+    fn fold_for(&mut self, stmt: For) -> For {
+        walk_for(self, stmt)
+    }
+    fn fold_for_init(&mut self, init: ForInit) -> ForInit {
+        walk_for_init(self, init)
+    }
+    fn fold_for_in(&mut self, stmt: ForIn) -> ForIn {
+        walk_for_in(self, stmt)
+    }
+    fn fold_for_of(&mut self, stmt: ForOf) -> ForOf {
+        walk_for_of(self, stmt)
+    }
+    fn fold_for_left(&mut self, left: ForLeft) -> ForLeft {
+        walk_for_left(self, left)
+    }
+    fn fold_declaration(&mut self, decl: Declaration) -> Declaration {
+        walk_declaration(self, decl)
+    }
+    fn fold_variable_declaration(&mut self, decl: VariableDeclaration) -> VariableDeclaration {
+        walk_variable_declaration(self, decl)
+    }
+    fn fold_variable_declarator(&mut self, decl: VariableDeclarator) -> VariableDeclarator {
+        walk_variable_declarator(self, decl)
+    }
+    fn fold_module_declaration(&mut self, decl: ModuleDeclaration) -> ModuleDeclaration {
+        walk_module_declaration(self, decl)
+    }
+    fn fold_import_declaration(&mut self, decl: ImportDeclaration) -> ImportDeclaration {
+        walk_import_declaration(self, decl)
+    }
+    fn fold_import_specifier(&mut self, spec: ImportSpecifier) -> ImportSpecifier {
+        walk_import_specifier(self, spec)
+    }
+    fn fold_import(&mut self, import: Import) -> Import {
+        walk_import(self, import)
+    }
+    fn fold_import_default(&mut self, import: ImportDefault) -> ImportDefault {
+        walk_import_default(self, import)
+    }
+    fn fold_import_namespace(&mut self, import: ImportNamespace) -> ImportNamespace {
+        walk_import_namespace(self, import)
+    }
+    fn fold_export_declaration(&mut self, decl: ExportDeclaration) -> ExportDeclaration {
+        walk_export_declaration(self, decl)
+    }
+    fn fold_export_named(&mut self, export: ExportNamed) -> ExportNamed {
+        walk_export_named(self, export)
+    }
+    fn fold_export_specifier(&mut self, spec: ExportSpecifier) -> ExportSpecifier {
+        walk_export_specifier(self, spec)
+    }
+    fn fold_export_default(&mut self, export: ExportDefault) -> ExportDefault {
+        walk_export_default(self, export)
+    }
+    fn fold_export_decl(&mut self, decl: ExportDecl) -> ExportDecl {
+        walk_export_decl(self, decl)
+    }
+    fn fold_export_all(&mut self, export: ExportAll) -> ExportAll {
+        walk_export_all(self, export)
+    }
+}
+/// A `Fold` that can stop partway through. Every `try_fold_*`
+/// method returns a `ControlFlow`, and a `Break` from any of them
+/// ends the whole fold, dropping the tree and handing the value back
+/// to the caller. Every `Fold` is a `TryFold<Infallible>`.
+pub trait TryFold<B> {
+    fn try_fold_program(&mut self, program: Program) -> ControlFlow<B, Program> {
+        try_walk_program(self, program)
+    }
+    fn try_fold_program_part_many(
+        &mut self,
+        part: ProgramPart,
+    ) -> ControlFlow<B, Vec<ProgramPart>> {
+        try_walk_program_part_many(self, part)
+    }
+    fn try_fold_program_part(&mut self, part: ProgramPart) -> ControlFlow<B, ProgramPart> {
+        try_walk_program_part(self, part)
+    }
+    fn try_fold_directive(&mut self, directive: Directive) -> ControlFlow<B, Directive> {
+        try_walk_directive(self, directive)
+    }
+    fn try_fold_function(&mut self, function: Function) -> ControlFlow<B, Function> {
+        try_walk_function(self, function)
+    }
+    fn try_fold_function_body(&mut self, body: FunctionBody) -> ControlFlow<B, FunctionBody> {
+        try_walk_function_body(self, body)
+    }
+    fn try_fold_function_body_part_many(
+        &mut self,
+        part: FunctionBodyPart,
+    ) -> ControlFlow<B, Vec<FunctionBodyPart>> {
+        try_walk_function_body_part_many(self, part)
+    }
+    fn try_fold_function_body_part(
+        &mut self,
+        part: FunctionBodyPart,
+    ) -> ControlFlow<B, FunctionBodyPart> {
+        try_walk_function_body_part(self, part)
+    }
+    fn try_fold_class(&mut self, class: Class) -> ControlFlow<B, Class> {
+        try_walk_class(self, class)
+    }
+    fn try_fold_class_body(&mut self, body: ClassBody) -> ControlFlow<B, ClassBody> {
+        try_walk_class_body(self, body)
+    }
+    fn try_fold_method_definition(
+        &mut self,
+        method: MethodDefinition,
+    ) -> ControlFlow<B, MethodDefinition> {
+        try_walk_method_definition(self, method)
+    }
+    fn try_fold_pattern(&mut self, pattern: Pattern) -> ControlFlow<B, Pattern> {
+        try_walk_pattern(self, pattern)
+    }
+    fn try_fold_object_pattern(&mut self, object: Object) -> ControlFlow<B, Object> {
+        try_walk_object_pattern(self, object)
+    }
+    fn try_fold_assignment_property(
+        &mut self,
+        property: AssignmentProperty,
+    ) -> ControlFlow<B, AssignmentProperty> {
+        try_walk_assignment_property(self, property)
+    }
+    fn try_fold_array_pattern(&mut self, array: Array) -> ControlFlow<B, Array> {
+        try_walk_array_pattern(self, array)
+    }
+    fn try_fold_rest_element(&mut self, rest: RestElement) -> ControlFlow<B, RestElement> {
+        try_walk_rest_element(self, rest)
+    }
+    fn try_fold_assignment_pattern(
+        &mut self,
+        assignment: Assignment,
+    ) -> ControlFlow<B, Assignment> {
+        try_walk_assignment_pattern(self, assignment)
+    }
+    fn try_fold_expression(&mut self, expr: Expression) -> ControlFlow<B, Expression> {
+        try_walk_expression(self, expr)
+    }
+    fn try_fold_identifier(&mut self, ident: Identifier) -> ControlFlow<B, Identifier> {
+        ControlFlow::Continue(ident)
+    }
+    fn try_fold_literal(&mut self, literal: Literal) -> ControlFlow<B, Literal> {
+        try_walk_literal(self, literal)
+    }
+    fn try_fold_this(&mut self, this: This) -> ControlFlow<B, This> {
+        ControlFlow::Continue(this)
+    }
+    fn try_fold_super(&mut self, sup: Super) -> ControlFlow<B, Super> {
+        ControlFlow::Continue(sup)
+    }
+    fn try_fold_array_expression(
+        &mut self,
+        array: expression::Array,
+    ) -> ControlFlow<B, expression::Array> {
+        try_walk_array_expression(self, array)
+    }
+    fn try_fold_spreadable(&mut self, spreadable: Spreadable) -> ControlFlow<B, Spreadable> {
+        try_walk_spreadable(self, spreadable)
+    }
+    fn try_fold_spread_element(&mut self, spread: SpreadElement) -> ControlFlow<B, SpreadElement> {
+        try_walk_spread_element(self, spread)
+    }
+    fn try_fold_object_expression(
+        &mut self,
+        object: expression::Object,
+    ) -> ControlFlow<B, expression::Object> {
+        try_walk_object_expression(self, object)
+    }
+    fn try_fold_property(&mut self, property: Property) -> ControlFlow<B, Property> {
+        try_walk_property(self, property)
+    }
+    fn try_fold_unary(&mut self, unary: Unary) -> ControlFlow<B, Unary> {
+        try_walk_unary(self, unary)
+    }
+    fn try_fold_update(&mut self, update: Update) -> ControlFlow<B, Update> {
+        try_walk_update(self, update)
+    }
+    fn try_fold_binary(&mut self, binary: Binary) -> ControlFlow<B, Binary> {
+        try_walk_binary(self, binary)
+    }
+    fn try_fold_assignment_expression(
+        &mut self,
+        assignment: expression::Assignment,
+    ) -> ControlFlow<B, expression::Assignment> {
+        try_walk_assignment_expression(self, assignment)
+    }
+    fn try_fold_pat_or_expr(&mut self, pat_or_expr: PatOrExpr) -> ControlFlow<B, PatOrExpr> {
+        try_walk_pat_or_expr(self, pat_or_expr)
+    }
+    fn try_fold_logical(&mut self, logical: Logical) -> ControlFlow<B, Logical> {
+        try_walk_logical(self, logical)
+    }
+    fn try_fold_member(&mut self, member: Member) -> ControlFlow<B, Member> {
+        try_walk_member(self, member)
+    }
+    fn try_fold_parent(&mut self, parent: Parent) -> ControlFlow<B, Parent> {
+        try_walk_parent(self, parent)
+    }
+    fn try_fold_conditional(&mut self, conditional: Conditional) -> ControlFlow<B, Conditional> {
+        try_walk_conditional(self, conditional)
+    }
+    fn try_fold_call(&mut self, call: Call) -> ControlFlow<B, Call> {
+        try_walk_call(self, call)
+    }
+    fn try_fold_new(&mut self, new: New) -> ControlFlow<B, New> {
+        try_walk_new(self, new)
+    }
+    fn try_fold_sequence(&mut self, sequence: Sequence) -> ControlFlow<B, Sequence> {
+        try_walk_sequence(self, sequence)
+    }
+    fn try_fold_arrow_function(&mut self, arrow: ArrowFunction) -> ControlFlow<B, ArrowFunction> {
+        try_walk_arrow_function(self, arrow)
+    }
+    fn try_fold_arrow_function_body(
+        &mut self,
+        body: ArrowFunctionBody,
+    ) -> ControlFlow<B, ArrowFunctionBody> {
+        try_walk_arrow_function_body(self, body)
+    }
+    fn try_fold_yield(&mut self, expr: Yield) -> ControlFlow<B, Yield> {
+        try_walk_yield(self, expr)
+    }
+    fn try_fold_await(&mut self, expr: Await) -> ControlFlow<B, Await> {
+        try_walk_await(self, expr)
+    }
+    fn try_fold_chain(&mut self, chain: Chain) -> ControlFlow<B, Chain> {
+        try_walk_chain(self, chain)
+    }
+    fn try_fold_template_literal(
+        &mut self,
+        template: TemplateLiteral,
+    ) -> ControlFlow<B, TemplateLiteral> {
+        try_walk_template_literal(self, template)
+    }
+    fn try_fold_template_element(
+        &mut self,
+        element: TemplateElement,
+    ) -> ControlFlow<B, TemplateElement> {
+        ControlFlow::Continue(element)
+    }
+    fn try_fold_tagged_template(
+        &mut self,
+        tagged: TaggedTemplate,
+    ) -> ControlFlow<B, TaggedTemplate> {
+        try_walk_tagged_template(self, tagged)
+    }
+    fn try_fold_meta_property(&mut self, meta: MetaProperty) -> ControlFlow<B, MetaProperty> {
+        try_walk_meta_property(self, meta)
+    }
+    fn try_fold_statement_many(&mut self, stmt: Statement) -> ControlFlow<B, Vec<Statement>> {
+        ControlFlow::Continue(vec![self.try_fold_statement(stmt)?])
+    }
+    fn try_fold_statement(&mut self, stmt: Statement) -> ControlFlow<B, Statement> {
+        try_walk_statement(self, stmt)
+    }
+    fn try_fold_block(&mut self, block: Block) -> ControlFlow<B, Block> {
+        try_walk_block(self, block)
+    }
+    fn try_fold_empty(&mut self, empty: Empty) -> ControlFlow<B, Empty> {
+        ControlFlow::Continue(empty)
+    }
+    fn try_fold_debugger(&mut self, debugger: Debugger) -> ControlFlow<B, Debugger> {
+        ControlFlow::Continue(debugger)
+    }
+    fn try_fold_with(&mut self, with: With) -> ControlFlow<B, With> {
+        try_walk_with(self, with)
+    }
+    fn try_fold_return(&mut self, ret: Return) -> ControlFlow<B, Return> {
+        try_walk_return(self, ret)
+    }
+    fn try_fold_labeled(&mut self, labeled: Labeled) -> ControlFlow<B, Labeled> {
+        try_walk_labeled(self, labeled)
+    }
+    fn try_fold_break(&mut self, brk: Break) -> ControlFlow<B, Break> {
+        try_walk_break(self, brk)
+    }
+    fn try_fold_continue(&mut self, cont: Continue) -> ControlFlow<B, Continue> {
+        try_walk_continue(self, cont)
+    }
+    fn try_fold_if(&mut self, stmt: If) -> ControlFlow<B, If> {
+        try_walk_if(self, stmt)
+    }
+    fn try_fold_switch(&mut self, switch: Switch) -> ControlFlow<B, Switch> {
+        try_walk_switch(self, switch)
+    }
+    fn try_fold_switch_case(&mut self, case: SwitchCase) -> ControlFlow<B, SwitchCase> {
+        try_walk_switch_case(self, case)
+    }
+    fn try_fold_throw(&mut self, throw: Throw) -> ControlFlow<B, Throw> {
+        try_walk_throw(self, throw)
+    }
+    fn try_fold_try(&mut self, stmt: Try) -> ControlFlow<B, Try> {
+        try_walk_try(self, stmt)
+    }
+    fn try_fold_catch_clause(&mut self, clause: CatchClause) -> ControlFlow<B, CatchClause> {
+        try_walk_catch_clause(self, clause)
+    }
+    fn try_fold_while(&mut self, stmt: While) -> ControlFlow<B, While> {
+        try_walk_while(self, stmt)
+    }
+    fn try_fold_do_while(&mut self, stmt: DoWhile) -> ControlFlow<B, DoWhile> {
+        try_walk_do_while(self, stmt)
+    }
+    fn try_fold_for(&mut self, stmt: For) -> ControlFlow<B, For> {
+        try_walk_for(self, stmt)
+    }
+    fn try_fold_for_init(&mut self, init: ForInit) -> ControlFlow<B, ForInit> {
+        try_walk_for_init(self, init)
+    }
+    fn try_fold_for_in(&mut self, stmt: ForIn) -> ControlFlow<B, ForIn> {
+        try_walk_for_in(self, stmt)
+    }
+    fn try_fold_for_of(&mut self, stmt: ForOf) -> ControlFlow<B, ForOf> {
+        try_walk_for_of(self, stmt)
+    }
+    fn try_fold_for_left(&mut self, left: ForLeft) -> ControlFlow<B, ForLeft> {
+        try_walk_for_left(self, left)
+    }
+    fn try_fold_declaration(&mut self, decl: Declaration) -> ControlFlow<B, Declaration> {
+        try_walk_declaration(self, decl)
+    }
+    fn try_fold_variable_declaration(
+        &mut self,
+        decl: VariableDeclaration,
+    ) -> ControlFlow<B, VariableDeclaration> {
+        try_walk_variable_declaration(self, decl)
+    }
+    fn try_fold_variable_declarator(
+        &mut self,
+        decl: VariableDeclarator,
+    ) -> ControlFlow<B, VariableDeclarator> {
+        try_walk_variable_declarator(self, decl)
+    }
+    fn try_fold_module_declaration(
+        &mut self,
+        decl: ModuleDeclaration,
+    ) -> ControlFlow<B, ModuleDeclaration> {
+        try_walk_module_declaration(self, decl)
+    }
+    fn try_fold_import_declaration(
+        &mut self,
+        decl: ImportDeclaration,
+    ) -> ControlFlow<B, ImportDeclaration> {
+        try_walk_import_declaration(self, decl)
+    }
+    fn try_fold_import_specifier(
+        &mut self,
+        spec: ImportSpecifier,
+    ) -> ControlFlow<B, ImportSpecifier> {
+        try_walk_import_specifier(self, spec)
+    }
+    fn try_fold_import(&mut self, import: Import) -> ControlFlow<B, Import> {
+        try_walk_import(self, import)
+    }
+    fn try_fold_import_default(&mut self, import: ImportDefault) -> ControlFlow<B, ImportDefault> {
+        try_walk_import_default(self, import)
+    }
+    fn try_fold_import_namespace(
+        &mut self,
+        import: ImportNamespace,
+    ) -> ControlFlow<B, ImportNamespace> {
+        try_walk_import_namespace(self, import)
+    }
+    fn try_fold_export_declaration(
+        &mut self,
+        decl: ExportDeclaration,
+    ) -> ControlFlow<B, ExportDeclaration> {
+        try_walk_export_declaration(self, decl)
+    }
+    fn try_fold_export_named(&mut self, export: ExportNamed) -> ControlFlow<B, ExportNamed> {
+        try_walk_export_named(self, export)
+    }
+    fn try_fold_export_specifier(
+        &mut self,
+        spec: ExportSpecifier,
+    ) -> ControlFlow<B, ExportSpecifier> {
+        try_walk_export_specifier(self, spec)
+    }
+    fn try_fold_export_default(&mut self, export: ExportDefault) -> ControlFlow<B, ExportDefault> {
+        try_walk_export_default(self, export)
+    }
+    fn try_fold_export_decl(&mut self, decl: ExportDecl) -> ControlFlow<B, ExportDecl> {
+        try_walk_export_decl(self, decl)
+    }
+    fn try_fold_export_all(&mut self, export: ExportAll) -> ControlFlow<B, ExportAll> {
+        try_walk_export_all(self, export)
+    }
+}
+
+impl<F: Fold + ?Sized> TryFold<Infallible> for F {
+    fn try_fold_program(&mut self, program: Program) -> ControlFlow<Infallible, Program> {
+        ControlFlow::Continue(self.fold_program(program))
+    }
+    fn try_fold_program_part_many(
+        &mut self,
+        part: ProgramPart,
+    ) -> ControlFlow<Infallible, Vec<ProgramPart>> {
+        ControlFlow::Continue(self.fold_program_part_many(part))
+    }
+    fn try_fold_program_part(&mut self, part: ProgramPart) -> ControlFlow<Infallible, ProgramPart> {
+        ControlFlow::Continue(self.fold_program_part(part))
+    }
+    fn try_fold_directive(&mut self, directive: Directive) -> ControlFlow<Infallible, Directive> {
+        ControlFlow::Continue(self.fold_directive(directive))
+    }
+    fn try_fold_function(&mut self, function: Function) -> ControlFlow<Infallible, Function> {
+        ControlFlow::Continue(self.fold_function(function))
+    }
+    fn try_fold_function_body(
+        &mut self,
+        body: FunctionBody,
+    ) -> ControlFlow<Infallible, FunctionBody> {
+        ControlFlow::Continue(self.fold_function_body(body))
+    }
+    fn try_fold_function_body_part_many(
+        &mut self,
+        part: FunctionBodyPart,
+    ) -> ControlFlow<Infallible, Vec<FunctionBodyPart>> {
+        ControlFlow::Continue(self.fold_function_body_part_many(part))
+    }
+    fn try_fold_function_body_part(
+        &mut self,
+        part: FunctionBodyPart,
+    ) -> ControlFlow<Infallible, FunctionBodyPart> {
+        ControlFlow::Continue(self.fold_function_body_part(part))
+    }
+    fn try_fold_class(&mut self, class: Class) -> ControlFlow<Infallible, Class> {
+        ControlFlow::Continue(self.fold_class(class))
+    }
+    fn try_fold_class_body(&mut self, body: ClassBody) -> ControlFlow<Infallible, ClassBody> {
+        ControlFlow::Continue(self.fold_class_body(body))
+    }
+    fn try_fold_method_definition(
+        &mut self,
+        method: MethodDefinition,
+    ) -> ControlFlow<Infallible, MethodDefinition> {
+        ControlFlow::Continue(self.fold_method_definition(method))
+    }
+    fn try_fold_pattern(&mut self, pattern: Pattern) -> ControlFlow<Infallible, Pattern> {
+        ControlFlow::Continue(self.fold_pattern(pattern))
+    }
+    fn try_fold_object_pattern(&mut self, object: Object) -> ControlFlow<Infallible, Object> {
+        ControlFlow::Continue(self.fold_object_pattern(object))
+    }
+    fn try_fold_assignment_property(
+        &mut self,
+        property: AssignmentProperty,
+    ) -> ControlFlow<Infallible, AssignmentProperty> {
+        ControlFlow::Continue(self.fold_assignment_property(property))
+    }
+    fn try_fold_array_pattern(&mut self, array: Array) -> ControlFlow<Infallible, Array> {
+        ControlFlow::Continue(self.fold_array_pattern(array))
+    }
+    fn try_fold_rest_element(&mut self, rest: RestElement) -> ControlFlow<Infallible, RestElement> {
+        ControlFlow::Continue(self.fold_rest_element(rest))
+    }
+    fn try_fold_assignment_pattern(
+        &mut self,
+        assignment: Assignment,
+    ) -> ControlFlow<Infallible, Assignment> {
+        ControlFlow::Continue(self.fold_assignment_pattern(assignment))
+    }
+    fn try_fold_expression(&mut self, expr: Expression) -> ControlFlow<Infallible, Expression> {
+        ControlFlow::Continue(self.fold_expression(expr))
+    }
+    fn try_fold_identifier(&mut self, ident: Identifier) -> ControlFlow<Infallible, Identifier> {
+        ControlFlow::Continue(self.fold_identifier(ident))
+    }
+    fn try_fold_literal(&mut self, literal: Literal) -> ControlFlow<Infallible, Literal> {
+        ControlFlow::Continue(self.fold_literal(literal))
+    }
+    fn try_fold_this(&mut self, this: This) -> ControlFlow<Infallible, This> {
+        ControlFlow::Continue(self.fold_this(this))
+    }
+    fn try_fold_super(&mut self, sup: Super) -> ControlFlow<Infallible, Super> {
+        ControlFlow::Continue(self.fold_super(sup))
+    }
+    fn try_fold_array_expression(
+        &mut self,
+        array: expression::Array,
+    ) -> ControlFlow<Infallible, expression::Array> {
+        ControlFlow::Continue(self.fold_array_expression(array))
+    }
+    fn try_fold_spreadable(
+        &mut self,
+        spreadable: Spreadable,
+    ) -> ControlFlow<Infallible, Spreadable> {
+        ControlFlow::Continue(self.fold_spreadable(spreadable))
+    }
+    fn try_fold_spread_element(
+        &mut self,
+        spread: SpreadElement,
+    ) -> ControlFlow<Infallible, SpreadElement> {
+        ControlFlow::Continue(self.fold_spread_element(spread))
+    }
+    fn try_fold_object_expression(
+        &mut self,
+        object: expression::Object,
+    ) -> ControlFlow<Infallible, expression::Object> {
+        ControlFlow::Continue(self.fold_object_expression(object))
+    }
+    fn try_fold_property(&mut self, property: Property) -> ControlFlow<Infallible, Property> {
+        ControlFlow::Continue(self.fold_property(property))
+    }
+    fn try_fold_unary(&mut self, unary: Unary) -> ControlFlow<Infallible, Unary> {
+        ControlFlow::Continue(self.fold_unary(unary))
+    }
+    fn try_fold_update(&mut self, update: Update) -> ControlFlow<Infallible, Update> {
+        ControlFlow::Continue(self.fold_update(update))
+    }
+    fn try_fold_binary(&mut self, binary: Binary) -> ControlFlow<Infallible, Binary> {
+        ControlFlow::Continue(self.fold_binary(binary))
+    }
+    fn try_fold_assignment_expression(
+        &mut self,
+        assignment: expression::Assignment,
+    ) -> ControlFlow<Infallible, expression::Assignment> {
+        ControlFlow::Continue(self.fold_assignment_expression(assignment))
+    }
+    fn try_fold_pat_or_expr(
+        &mut self,
+        pat_or_expr: PatOrExpr,
+    ) -> ControlFlow<Infallible, PatOrExpr> {
+        ControlFlow::Continue(self.fold_pat_or_expr(pat_or_expr))
+    }
+    fn try_fold_logical(&mut self, logical: Logical) -> ControlFlow<Infallible, Logical> {
+        ControlFlow::Continue(self.fold_logical(logical))
+    }
+    fn try_fold_member(&mut self, member: Member) -> ControlFlow<Infallible, Member> {
+        ControlFlow::Continue(self.fold_member(member))
+    }
+    fn try_fold_parent(&mut self, parent: Parent) -> ControlFlow<Infallible, Parent> {
+        ControlFlow::Continue(self.fold_parent(parent))
+    }
+    fn try_fold_conditional(
+        &mut self,
+        conditional: Conditional,
+    ) -> ControlFlow<Infallible, Conditional> {
+        ControlFlow::Continue(self.fold_conditional(conditional))
+    }
+    fn try_fold_call(&mut self, call: Call) -> ControlFlow<Infallible, Call> {
+        ControlFlow::Continue(self.fold_call(call))
+    }
+    fn try_fold_new(&mut self, new: New) -> ControlFlow<Infallible, New> {
+        ControlFlow::Continue(self.fold_new(new))
+    }
+    fn try_fold_sequence(&mut self, sequence: Sequence) -> ControlFlow<Infallible, Sequence> {
+        ControlFlow::Continue(self.fold_sequence(sequence))
+    }
+    fn try_fold_arrow_function(
+        &mut self,
+        arrow: ArrowFunction,
+    ) -> ControlFlow<Infallible, ArrowFunction> {
+        ControlFlow::Continue(self.fold_arrow_function(arrow))
+    }
+    fn try_fold_arrow_function_body(
+        &mut self,
+        body: ArrowFunctionBody,
+    ) -> ControlFlow<Infallible, ArrowFunctionBody> {
+        ControlFlow::Continue(self.fold_arrow_function_body(body))
+    }
+    fn try_fold_yield(&mut self, expr: Yield) -> ControlFlow<Infallible, Yield> {
+        ControlFlow::Continue(self.fold_yield(expr))
+    }
+    fn try_fold_await(&mut self, expr: Await) -> ControlFlow<Infallible, Await> {
+        ControlFlow::Continue(self.fold_await(expr))
+    }
+    fn try_fold_chain(&mut self, chain: Chain) -> ControlFlow<Infallible, Chain> {
+        ControlFlow::Continue(self.fold_chain(chain))
+    }
+    fn try_fold_template_literal(
+        &mut self,
+        template: TemplateLiteral,
+    ) -> ControlFlow<Infallible, TemplateLiteral> {
+        ControlFlow::Continue(self.fold_template_literal(template))
+    }
+    fn try_fold_template_element(
+        &mut self,
+        element: TemplateElement,
+    ) -> ControlFlow<Infallible, TemplateElement> {
+        ControlFlow::Continue(self.fold_template_element(element))
+    }
+    fn try_fold_tagged_template(
+        &mut self,
+        tagged: TaggedTemplate,
+    ) -> ControlFlow<Infallible, TaggedTemplate> {
+        ControlFlow::Continue(self.fold_tagged_template(tagged))
+    }
+    fn try_fold_meta_property(
+        &mut self,
+        meta: MetaProperty,
+    ) -> ControlFlow<Infallible, MetaProperty> {
+        ControlFlow::Continue(self.fold_meta_property(meta))
+    }
+    fn try_fold_statement_many(
+        &mut self,
+        stmt: Statement,
+    ) -> ControlFlow<Infallible, Vec<Statement>> {
+        ControlFlow::Continue(self.fold_statement_many(stmt))
+    }
+    fn try_fold_statement(&mut self, stmt: Statement) -> ControlFlow<Infallible, Statement> {
+        ControlFlow::Continue(self.fold_statement(stmt))
+    }
+    fn try_fold_block(&mut self, block: Block) -> ControlFlow<Infallible, Block> {
+        ControlFlow::Continue(self.fold_block(block))
+    }
+    fn try_fold_empty(&mut self, empty: Empty) -> ControlFlow<Infallible, Empty> {
+        ControlFlow::Continue(self.fold_empty(empty))
+    }
+    fn try_fold_debugger(&mut self, debugger: Debugger) -> ControlFlow<Infallible, Debugger> {
+        ControlFlow::Continue(self.fold_debugger(debugger))
+    }
+    fn try_fold_with(&mut self, with: With) -> ControlFlow<Infallible, With> {
+        ControlFlow::Continue(self.fold_with(with))
+    }
+    fn try_fold_return(&mut self, ret: Return) -> ControlFlow<Infallible, Return> {
+        ControlFlow::Continue(self.fold_return(ret))
+    }
+    fn try_fold_labeled(&mut self, labeled: Labeled) -> ControlFlow<Infallible, Labeled> {
+        ControlFlow::Continue(self.fold_labeled(labeled))
+    }
+    fn try_fold_break(&mut self, brk: Break) -> ControlFlow<Infallible, Break> {
+        ControlFlow::Continue(self.fold_break(brk))
+    }
+    fn try_fold_continue(&mut self, cont: Continue) -> ControlFlow<Infallible, Continue> {
+        ControlFlow::Continue(self.fold_continue(cont))
+    }
+    fn try_fold_if(&mut self, stmt: If) -> ControlFlow<Infallible, If> {
+        ControlFlow::Continue(self.fold_if(stmt))
+    }
+    fn try_fold_switch(&mut self, switch: Switch) -> ControlFlow<Infallible, Switch> {
+        ControlFlow::Continue(self.fold_switch(switch))
+    }
+    fn try_fold_switch_case(&mut self, case: SwitchCase) -> ControlFlow<Infallible, SwitchCase> {
+        ControlFlow::Continue(self.fold_switch_case(case))
+    }
+    fn try_fold_throw(&mut self, throw: Throw) -> ControlFlow<Infallible, Throw> {
+        ControlFlow::Continue(self.fold_throw(throw))
+    }
+    fn try_fold_try(&mut self, stmt: Try) -> ControlFlow<Infallible, Try> {
+        ControlFlow::Continue(self.fold_try(stmt))
+    }
+    fn try_fold_catch_clause(
+        &mut self,
+        clause: CatchClause,
+    ) -> ControlFlow<Infallible, CatchClause> {
+        ControlFlow::Continue(self.fold_catch_clause(clause))
+    }
+    fn try_fold_while(&mut self, stmt: While) -> ControlFlow<Infallible, While> {
+        ControlFlow::Continue(self.fold_while(stmt))
+    }
+    fn try_fold_do_while(&mut self, stmt: DoWhile) -> ControlFlow<Infallible, DoWhile> {
+        ControlFlow::Continue(self.fold_do_while(stmt))
+    }
+    fn try_fold_for(&mut self, stmt: For) -> ControlFlow<Infallible, For> {
+        ControlFlow::Continue(self.fold_for(stmt))
+    }
+    fn try_fold_for_init(&mut self, init: ForInit) -> ControlFlow<Infallible, ForInit> {
+        ControlFlow::Continue(self.fold_for_init(init))
+    }
+    fn try_fold_for_in(&mut self, stmt: ForIn) -> ControlFlow<Infallible, ForIn> {
+        ControlFlow::Continue(self.fold_for_in(stmt))
+    }
+    fn try_fold_for_of(&mut self, stmt: ForOf) -> ControlFlow<Infallible, ForOf> {
+        ControlFlow::Continue(self.fold_for_of(stmt))
+    }
+    fn try_fold_for_left(&mut self, left: ForLeft) -> ControlFlow<Infallible, ForLeft> {
+        ControlFlow::Continue(self.fold_for_left(left))
+    }
+    fn try_fold_declaration(&mut self, decl: Declaration) -> ControlFlow<Infallible, Declaration> {
+        ControlFlow::Continue(self.fold_declaration(decl))
+    }
+    fn try_fold_variable_declaration(
+        &mut self,
+        decl: VariableDeclaration,
+    ) -> ControlFlow<Infallible, VariableDeclaration> {
+        ControlFlow::Continue(self.fold_variable_declaration(decl))
+    }
+    fn try_fold_variable_declarator(
+        &mut self,
+        decl: VariableDeclarator,
+    ) -> ControlFlow<Infallible, VariableDeclarator> {
+        ControlFlow::Continue(self.fold_variable_declarator(decl))
+    }
+    fn try_fold_module_declaration(
+        &mut self,
+        decl: ModuleDeclaration,
+    ) -> ControlFlow<Infallible, ModuleDeclaration> {
+        ControlFlow::Continue(self.fold_module_declaration(decl))
+    }
+    fn try_fold_import_declaration(
+        &mut self,
+        decl: ImportDeclaration,
+    ) -> ControlFlow<Infallible, ImportDeclaration> {
+        ControlFlow::Continue(self.fold_import_declaration(decl))
+    }
+    fn try_fold_import_specifier(
+        &mut self,
+        spec: ImportSpecifier,
+    ) -> ControlFlow<Infallible, ImportSpecifier> {
+        ControlFlow::Continue(self.fold_import_specifier(spec))
+    }
+    fn try_fold_import(&mut self, import: Import) -> ControlFlow<Infallible, Import> {
+        ControlFlow::Continue(self.fold_import(import))
+    }
+    fn try_fold_import_default(
+        &mut self,
+        import: ImportDefault,
+    ) -> ControlFlow<Infallible, ImportDefault> {
+        ControlFlow::Continue(self.fold_import_default(import))
+    }
+    fn try_fold_import_namespace(
+        &mut self,
+        import: ImportNamespace,
+    ) -> ControlFlow<Infallible, ImportNamespace> {
+        ControlFlow::Continue(self.fold_import_namespace(import))
+    }
+    fn try_fold_export_declaration(
+        &mut self,
+        decl: ExportDeclaration,
+    ) -> ControlFlow<Infallible, ExportDeclaration> {
+        ControlFlow::Continue(self.fold_export_declaration(decl))
+    }
+    fn try_fold_export_named(
+        &mut self,
+        export: ExportNamed,
+    ) -> ControlFlow<Infallible, ExportNamed> {
+        ControlFlow::Continue(self.fold_export_named(export))
+    }
+    fn try_fold_export_specifier(
+        &mut self,
+        spec: ExportSpecifier,
+    ) -> ControlFlow<Infallible, ExportSpecifier> {
+        ControlFlow::Continue(self.fold_export_specifier(spec))
+    }
+    fn try_fold_export_default(
+        &mut self,
+        export: ExportDefault,
+    ) -> ControlFlow<Infallible, ExportDefault> {
+        ControlFlow::Continue(self.fold_export_default(export))
+    }
+    fn try_fold_export_decl(&mut self, decl: ExportDecl) -> ControlFlow<Infallible, ExportDecl> {
+        ControlFlow::Continue(self.fold_export_decl(decl))
+    }
+    fn try_fold_export_all(&mut self, export: ExportAll) -> ControlFlow<Infallible, ExportAll> {
+        ControlFlow::Continue(self.fold_export_all(export))
+    }
+}
+
+pub fn walk_program<F: Fold + ?Sized>(folder: &mut F, program: Program) -> Program {
+    finish(try_walk_program(folder, program))
+}
+
+pub fn walk_program_part_many<F: Fold + ?Sized>(
+    folder: &mut F,
+    part: ProgramPart,
+) -> Vec<ProgramPart> {
+    finish(try_walk_program_part_many(folder, part))
+}
+
+pub fn walk_program_part<F: Fold + ?Sized>(folder: &mut F, part: ProgramPart) -> ProgramPart {
+    finish(try_walk_program_part(folder, part))
+}
+
+pub fn walk_directive<F: Fold + ?Sized>(folder: &mut F, directive: Directive) -> Directive {
+    finish(try_walk_directive(folder, directive))
+}
+
+pub fn walk_function<F: Fold + ?Sized>(folder: &mut F, function: Function) -> Function {
+    finish(try_walk_function(folder, function))
+}
+
+pub fn walk_function_body<F: Fold + ?Sized>(folder: &mut F, body: FunctionBody) -> FunctionBody {
+    finish(try_walk_function_body(folder, body))
+}
+
+pub fn walk_function_body_part_many<F: Fold + ?Sized>(
+    folder: &mut F,
+    part: FunctionBodyPart,
+) -> Vec<FunctionBodyPart> {
+    finish(try_walk_function_body_part_many(folder, part))
+}
+
+pub fn walk_function_body_part<F: Fold + ?Sized>(
+    folder: &mut F,
+    part: FunctionBodyPart,
+) -> FunctionBodyPart {
+    finish(try_walk_function_body_part(folder, part))
+}
+
+pub fn walk_class<F: Fold + ?Sized>(folder: &mut F, class: Class) -> Class {
+    finish(try_walk_class(folder, class))
+}
+
+pub fn walk_class_body<F: Fold + ?Sized>(folder: &mut F, body: ClassBody) -> ClassBody {
+    finish(try_walk_class_body(folder, body))
+}
+
+pub fn walk_method_definition<F: Fold + ?Sized>(
+    folder: &mut F,
+    method: MethodDefinition,
+) -> MethodDefinition {
+    finish(try_walk_method_definition(folder, method))
+}
+
+pub fn walk_pattern<F: Fold + ?Sized>(folder: &mut F, pattern: Pattern) -> Pattern {
+    finish(try_walk_pattern(folder, pattern))
+}
+
+pub fn walk_object_pattern<F: Fold + ?Sized>(folder: &mut F, object: Object) -> Object {
+    finish(try_walk_object_pattern(folder, object))
+}
+
+pub fn walk_assignment_property<F: Fold + ?Sized>(
+    folder: &mut F,
+    property: AssignmentProperty,
+) -> AssignmentProperty {
+    finish(try_walk_assignment_property(folder, property))
+}
+
+pub fn walk_array_pattern<F: Fold + ?Sized>(folder: &mut F, array: Array) -> Array {
+    finish(try_walk_array_pattern(folder, array))
+}
+
+pub fn walk_rest_element<F: Fold + ?Sized>(folder: &mut F, rest: RestElement) -> RestElement {
+    finish(try_walk_rest_element(folder, rest))
+}
+
+pub fn walk_assignment_pattern<F: Fold + ?Sized>(
+    folder: &mut F,
+    assignment: Assignment,
+) -> Assignment {
+    finish(try_walk_assignment_pattern(folder, assignment))
+}
+
+pub fn walk_expression<F: Fold + ?Sized>(folder: &mut F, expr: Expression) -> Expression {
+    finish(try_walk_expression(folder, expr))
+}
+
+pub fn walk_literal<F: Fold + ?Sized>(folder: &mut F, literal: Literal) -> Literal {
+    finish(try_walk_literal(folder, literal))
+}
+
+pub fn walk_array_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    array: expression::Array,
+) -> expression::Array {
+    finish(try_walk_array_expression(folder, array))
+}
+
+pub fn walk_spreadable<F: Fold + ?Sized>(folder: &mut F, spreadable: Spreadable) -> Spreadable {
+    finish(try_walk_spreadable(folder, spreadable))
+}
+
+pub fn walk_spread_element<F: Fold + ?Sized>(
+    folder: &mut F,
+    spread: SpreadElement,
+) -> SpreadElement {
+    finish(try_walk_spread_element(folder, spread))
+}
+
+pub fn walk_object_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    object: expression::Object,
+) -> expression::Object {
+    finish(try_walk_object_expression(folder, object))
+}
+
+pub fn walk_property<F: Fold + ?Sized>(folder: &mut F, property: Property) -> Property {
+    finish(try_walk_property(folder, property))
+}
+
+pub fn walk_unary<F: Fold + ?Sized>(folder: &mut F, unary: Unary) -> Unary {
+    finish(try_walk_unary(folder, unary))
+}
+
+pub fn walk_update<F: Fold + ?Sized>(folder: &mut F, update: Update) -> Update {
+    finish(try_walk_update(folder, update))
+}
+
+pub fn walk_binary<F: Fold + ?Sized>(folder: &mut F, binary: Binary) -> Binary {
+    finish(try_walk_binary(folder, binary))
+}
+
+pub fn walk_assignment_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    assignment: expression::Assignment,
+) -> expression::Assignment {
+    finish(try_walk_assignment_expression(folder, assignment))
+}
+
+pub fn walk_pat_or_expr<F: Fold + ?Sized>(folder: &mut F, pat_or_expr: PatOrExpr) -> PatOrExpr {
+    finish(try_walk_pat_or_expr(folder, pat_or_expr))
+}
+
+pub fn walk_logical<F: Fold + ?Sized>(folder: &mut F, logical: Logical) -> Logical {
+    finish(try_walk_logical(folder, logical))
+}
+
+pub fn walk_member<F: Fold + ?Sized>(folder: &mut F, member: Member) -> Member {
+    finish(try_walk_member(folder, member))
+}
+
+pub fn walk_parent<F: Fold + ?Sized>(folder: &mut F, parent: Parent) -> Parent {
+    finish(try_walk_parent(folder, parent))
+}
+
+pub fn walk_conditional<F: Fold + ?Sized>(folder: &mut F, conditional: Conditional) -> Conditional {
+    finish(try_walk_conditional(folder, conditional))
+}
+
+pub fn walk_call<F: Fold + ?Sized>(folder: &mut F, call: Call) -> Call {
+    finish(try_walk_call(folder, call))
+}
+
+pub fn walk_new<F: Fold + ?Sized>(folder: &mut F, new: New) -> New {
+    finish(try_walk_new(folder, new))
+}
+
+pub fn walk_sequence<F: Fold + ?Sized>(folder: &mut F, sequence: Sequence) -> Sequence {
+    finish(try_walk_sequence(folder, sequence))
+}
+
+pub fn walk_arrow_function<F: Fold + ?Sized>(
+    folder: &mut F,
+    arrow: ArrowFunction,
+) -> ArrowFunction {
+    finish(try_walk_arrow_function(folder, arrow))
+}
+
+pub fn walk_arrow_function_body<F: Fold + ?Sized>(
+    folder: &mut F,
+    body: ArrowFunctionBody,
+) -> ArrowFunctionBody {
+    finish(try_walk_arrow_function_body(folder, body))
+}
+
+pub fn walk_yield<F: Fold + ?Sized>(folder: &mut F, expr: Yield) -> Yield {
+    finish(try_walk_yield(folder, expr))
+}
+
+pub fn walk_await<F: Fold + ?Sized>(folder: &mut F, expr: Await) -> Await {
+    finish(try_walk_await(folder, expr))
+}
+
+pub fn walk_chain<F: Fold + ?Sized>(folder: &mut F, chain: Chain) -> Chain {
+    finish(try_walk_chain(folder, chain))
+}
+
+pub fn walk_template_literal<F: Fold + ?Sized>(
+    folder: &mut F,
+    template: TemplateLiteral,
+) -> TemplateLiteral {
+    finish(try_walk_template_literal(folder, template))
+}
+
+pub fn walk_tagged_template<F: Fold + ?Sized>(
+    folder: &mut F,
+    tagged: TaggedTemplate,
+) -> TaggedTemplate {
+    finish(try_walk_tagged_template(folder, tagged))
+}
+
+pub fn walk_meta_property<F: Fold + ?Sized>(folder: &mut F, meta: MetaProperty) -> MetaProperty {
+    finish(try_walk_meta_property(folder, meta))
+}
+
+pub fn walk_statement<F: Fold + ?Sized>(folder: &mut F, stmt: Statement) -> Statement {
+    finish(try_walk_statement(folder, stmt))
+}
+
+pub fn walk_block<F: Fold + ?Sized>(folder: &mut F, block: Block) -> Block {
+    finish(try_walk_block(folder, block))
+}
+
+pub fn walk_with<F: Fold + ?Sized>(folder: &mut F, with: With) -> With {
+    finish(try_walk_with(folder, with))
+}
+
+pub fn walk_return<F: Fold + ?Sized>(folder: &mut F, ret: Return) -> Return {
+    finish(try_walk_return(folder, ret))
+}
+
+pub fn walk_labeled<F: Fold + ?Sized>(folder: &mut F, labeled: Labeled) -> Labeled {
+    finish(try_walk_labeled(folder, labeled))
+}
+
+pub fn walk_break<F: Fold + ?Sized>(folder: &mut F, brk: Break) -> Break {
+    finish(try_walk_break(folder, brk))
+}
+
+pub fn walk_continue<F: Fold + ?Sized>(folder: &mut F, cont: Continue) -> Continue {
+    finish(try_walk_continue(folder, cont))
+}
+
+pub fn walk_if<F: Fold + ?Sized>(folder: &mut F, stmt: If) -> If {
+    finish(try_walk_if(folder, stmt))
+}
+
+pub fn walk_switch<F: Fold + ?Sized>(folder: &mut F, switch: Switch) -> Switch {
+    finish(try_walk_switch(folder, switch))
+}
+
+pub fn walk_switch_case<F: Fold + ?Sized>(folder: &mut F, case: SwitchCase) -> SwitchCase {
+    finish(try_walk_switch_case(folder, case))
+}
+
+pub fn walk_throw<F: Fold + ?Sized>(folder: &mut F, throw: Throw) -> Throw {
+    finish(try_walk_throw(folder, throw))
+}
+
+pub fn walk_try<F: Fold + ?Sized>(folder: &mut F, stmt: Try) -> Try {
+    finish(try_walk_try(folder, stmt))
+}
+
+pub fn walk_catch_clause<F: Fold + ?Sized>(folder: &mut F, clause: CatchClause) -> CatchClause {
+    finish(try_walk_catch_clause(folder, clause))
+}
+
+pub fn walk_while<F: Fold + ?Sized>(folder: &mut F, stmt: While) -> While {
+    finish(try_walk_while(folder, stmt))
+}
+
+pub fn walk_do_while<F: Fold + ?Sized>(folder: &mut F, stmt: DoWhile) -> DoWhile {
+    finish(try_walk_do_while(folder, stmt))
+}
+
+pub fn walk_for<F: Fold + ?Sized>(folder: &mut F, stmt: For) -> For {
+    finish(try_walk_for(folder, stmt))
+}
+
+pub fn walk_for_init<F: Fold + ?Sized>(folder: &mut F, init: ForInit) -> ForInit {
+    finish(try_walk_for_init(folder, init))
+}
+
+pub fn walk_for_in<F: Fold + ?Sized>(folder: &mut F, stmt: ForIn) -> ForIn {
+    finish(try_walk_for_in(folder, stmt))
+}
+
+pub fn walk_for_of<F: Fold + ?Sized>(folder: &mut F, stmt: ForOf) -> ForOf {
+    finish(try_walk_for_of(folder, stmt))
+}
+
+pub fn walk_for_left<F: Fold + ?Sized>(folder: &mut F, left: ForLeft) -> ForLeft {
+    finish(try_walk_for_left(folder, left))
+}
+
+pub fn walk_declaration<F: Fold + ?Sized>(folder: &mut F, decl: Declaration) -> Declaration {
+    finish(try_walk_declaration(folder, decl))
+}
+
+pub fn walk_variable_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    decl: VariableDeclaration,
+) -> VariableDeclaration {
+    finish(try_walk_variable_declaration(folder, decl))
+}
+
+pub fn walk_variable_declarator<F: Fold + ?Sized>(
+    folder: &mut F,
+    decl: VariableDeclarator,
+) -> VariableDeclarator {
+    finish(try_walk_variable_declarator(folder, decl))
+}
+
+pub fn walk_module_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    decl: ModuleDeclaration,
+) -> ModuleDeclaration {
+    finish(try_walk_module_declaration(folder, decl))
+}
+
+pub fn walk_import_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    decl: ImportDeclaration,
+) -> ImportDeclaration {
+    finish(try_walk_import_declaration(folder, decl))
+}
+
+pub fn walk_import_specifier<F: Fold + ?Sized>(
+    folder: &mut F,
+    spec: ImportSpecifier,
+) -> ImportSpecifier {
+    finish(try_walk_import_specifier(folder, spec))
+}
+
+pub fn walk_import<F: Fold + ?Sized>(folder: &mut F, import: Import) -> Import {
+    finish(try_walk_import(folder, import))
+}
+
+pub fn walk_import_default<F: Fold + ?Sized>(
+    folder: &mut F,
+    import: ImportDefault,
+) -> ImportDefault {
+    finish(try_walk_import_default(folder, import))
+}
+
+pub fn walk_import_namespace<F: Fold + ?Sized>(
+    folder: &mut F,
+    import: ImportNamespace,
+) -> ImportNamespace {
+    finish(try_walk_import_namespace(folder, import))
+}
+
+pub fn walk_export_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    decl: ExportDeclaration,
+) -> ExportDeclaration {
+    finish(try_walk_export_declaration(folder, decl))
+}
+
+pub fn walk_export_named<F: Fold + ?Sized>(folder: &mut F, export: ExportNamed) -> ExportNamed {
+    finish(try_walk_export_named(folder, export))
+}
+
+pub fn walk_export_specifier<F: Fold + ?Sized>(
+    folder: &mut F,
+    spec: ExportSpecifier,
+) -> ExportSpecifier {
+    finish(try_walk_export_specifier(folder, spec))
+}
+
+pub fn walk_export_default<F: Fold + ?Sized>(
+    folder: &mut F,
+    export: ExportDefault,
+) -> ExportDefault {
+    finish(try_walk_export_default(folder, export))
+}
+
+pub fn walk_export_decl<F: Fold + ?Sized>(folder: &mut F, decl: ExportDecl) -> ExportDecl {
+    finish(try_walk_export_decl(folder, decl))
+}
+
+pub fn walk_export_all<F: Fold + ?Sized>(folder: &mut F, export: ExportAll) -> ExportAll {
+    finish(try_walk_export_all(folder, export))
+}
+
+fn finish<T>(flow: ControlFlow<Infallible, T>) -> T {
+    match flow {
+        ControlFlow::Continue(value) => value,
+        ControlFlow::Break(never) => match never {},
+    }
+}
+
+fn try_map<B, T, U>(
+    items: Vec<T>,
+    mut f: impl FnMut(T) -> ControlFlow<B, U>,
+) -> ControlFlow<B, Vec<U>> {
+    let mut out = Vec::with_capacity(items.len());
+    for item in items {
+        out.push(f(item)?);
+    }
+    ControlFlow::Continue(out)
+}
+
+fn try_flat_map<B, T, U>(
+    items: Vec<T>,
+    mut f: impl FnMut(T) -> ControlFlow<B, Vec<U>>,
+) -> ControlFlow<B, Vec<U>> {
+    let mut out = Vec::with_capacity(items.len());
+    for item in items {
+        out.extend(f(item)?);
+    }
+    ControlFlow::Continue(out)
+}
+
+fn try_opt<B, T, U>(
+    item: Option<T>,
+    f: impl FnOnce(T) -> ControlFlow<B, U>,
+) -> ControlFlow<B, Option<U>> {
+    ControlFlow::Continue(match item {
+        Some(item) => Some(f(item)?),
+        None => None,
+    })
+}
+
+/// Fold a statement that sits in a single statement slot, like
+/// the body of a loop. Zero statements become an `Empty` and more
+/// than one are wrapped in a `Block`.
+fn fold_body<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    stmt: Statement,
+) -> ControlFlow<B, Statement> {
+    let loc = stmt.loc();
+    let mut stmts = folder.try_fold_statement_many(stmt)?;
+    ControlFlow::Continue(match stmts.len() {
+        0 => Statement::Empty(Empty { loc }),
+        1 => stmts.remove(0),
+        _ => Statement::Block(Block { body: stmts, loc }),
+    })
+}
+
+fn fold_statements<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    stmts: Vec<Statement>,
+) -> ControlFlow<B, Vec<Statement>> {
+    try_flat_map(stmts, |stmt| folder.try_fold_statement_many(stmt))
+}
+
+fn fold_opt_ident<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    id: Option<Identifier>,
+) -> ControlFlow<B, Option<Identifier>> {
+    try_opt(id, |id| folder.try_fold_identifier(id))
+}
+
+fn fold_opt_expr<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    expr: Option<Expression>,
+) -> ControlFlow<B, Option<Expression>> {
+    try_opt(expr, |expr| folder.try_fold_expression(expr))
+}
+
+pub fn try_walk_program<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    program: Program,
+) -> ControlFlow<B, Program> {
+    ControlFlow::Continue(Program {
+        body: try_flat_map(program.body, |part| folder.try_fold_program_part_many(part))?,
+        ..program
+    })
+}
+
+pub fn try_walk_program_part_many<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    part: ProgramPart,
+) -> ControlFlow<B, Vec<ProgramPart>> {
+    ControlFlow::Continue(match part {
+        ProgramPart::Statement(s) => folder
+            .try_fold_statement_many(s)?
+            .into_iter()
+            .map(ProgramPart::Statement)
+            .collect(),
+        part => vec![folder.try_fold_program_part(part)?],
+    })
+}
+
+pub fn try_walk_program_part<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    part: ProgramPart,
+) -> ControlFlow<B, ProgramPart> {
+    ControlFlow::Continue(match part {
+        ProgramPart::Directive(d) => ProgramPart::Directive(folder.try_fold_directive(d)?),
+        ProgramPart::Statement(s) => ProgramPart::Statement(folder.try_fold_statement(s)?),
+        ProgramPart::ModuleDecl(m) => {
+            ProgramPart::ModuleDecl(folder.try_fold_module_declaration(m)?)
+        }
+    })
+}
+
+pub fn try_walk_directive<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    directive: Directive,
+) -> ControlFlow<B, Directive> {
+    ControlFlow::Continue(Directive {
+        expression: folder.try_fold_literal(directive.expression)?,
+        ..directive
+    })
+}
+
+pub fn try_walk_function<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    function: Function,
+) -> ControlFlow<B, Function> {
+    ControlFlow::Continue(Function {
+        id: fold_opt_ident(folder, function.id)?,
+        params: try_map(function.params, |p| folder.try_fold_pattern(p))?,
+        body: folder.try_fold_function_body(function.body)?,
+        ..function
+    })
+}
+
+pub fn try_walk_function_body<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    body: FunctionBody,
+) -> ControlFlow<B, FunctionBody> {
+    ControlFlow::Continue(FunctionBody {
+        body: try_flat_map(body.body, |part| {
+            folder.try_fold_function_body_part_many(part)
+        })?,
+        ..body
+    })
+}
+
+pub fn try_walk_function_body_part_many<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    part: FunctionBodyPart,
+) -> ControlFlow<B, Vec<FunctionBodyPart>> {
+    ControlFlow::Continue(match part {
+        FunctionBodyPart::Statement(s) => folder
+            .try_fold_statement_many(s)?
+            .into_iter()
+            .map(FunctionBodyPart::Statement)
+            .collect(),
+        part => vec![folder.try_fold_function_body_part(part)?],
+    })
+}
+
+pub fn try_walk_function_body_part<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    part: FunctionBodyPart,
+) -> ControlFlow<B, FunctionBodyPart> {
+    ControlFlow::Continue(match part {
+        FunctionBodyPart::Directive(d) => {
+            FunctionBodyPart::Directive(folder.try_fold_directive(d)?)
+        }
+        FunctionBodyPart::Statement(s) => {
+            FunctionBodyPart::Statement(folder.try_fold_statement(s)?)
+        }
+    })
+}
+
+pub fn try_walk_class<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    class: Class,
+) -> ControlFlow<B, Class> {
+    ControlFlow::Continue(Class {
+        id: fold_opt_ident(folder, class.id)?,
+        super_class: fold_opt_expr(folder, class.super_class)?,
+        body: folder.try_fold_class_body(class.body)?,
+        ..class
+    })
+}
+
+pub fn try_walk_class_body<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    body: ClassBody,
+) -> ControlFlow<B, ClassBody> {
+    ControlFlow::Continue(ClassBody {
+        body: try_map(body.body, |m| folder.try_fold_method_definition(m))?,
+        ..body
+    })
+}
+
+pub fn try_walk_method_definition<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    method: MethodDefinition,
+) -> ControlFlow<B, MethodDefinition> {
+    ControlFlow::Continue(MethodDefinition {
+        key: folder.try_fold_expression(method.key)?,
+        value: folder.try_fold_function(method.value)?,
+        ..method
+    })
+}
+
+pub fn try_walk_pattern<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    pattern: Pattern,
+) -> ControlFlow<B, Pattern> {
+    ControlFlow::Continue(match pattern {
+        Pattern::Ident(i) => Pattern::Ident(folder.try_fold_identifier(i)?),
+        Pattern::Object(o) => Pattern::Object(folder.try_fold_object_pattern(o)?),
+        Pattern::Array(a) => Pattern::Array(folder.try_fold_array_pattern(a)?),
+        Pattern::RestElement(r) => {
+            Pattern::RestElement(Box::new(folder.try_fold_rest_element(*r)?))
+        }
+        Pattern::Assignment(a) => {
+            Pattern::Assignment(Box::new(folder.try_fold_assignment_pattern(*a)?))
+        }
+    })
+}
+
+pub fn try_walk_object_pattern<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    object: Object,
+) -> ControlFlow<B, Object> {
+    ControlFlow::Continue(Object {
+        properties: try_map(object.properties, |p| {
+            folder.try_fold_assignment_property(p)
+        })?,
+        ..object
+    })
+}
+
+pub fn try_walk_assignment_property<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    property: AssignmentProperty,
+) -> ControlFlow<B, AssignmentProperty> {
+    ControlFlow::Continue(AssignmentProperty {
+        key: folder.try_fold_expression(property.key)?,
+        value: folder.try_fold_pattern(property.value)?,
+        ..property
+    })
+}
+
+pub fn try_walk_array_pattern<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    array: Array,
+) -> ControlFlow<B, Array> {
+    ControlFlow::Continue(Array {
+        elements: try_map(array.elements, |e| {
+            try_opt(e, |p| folder.try_fold_pattern(p))
+        })?,
+        ..array
+    })
+}
+
+pub fn try_walk_rest_element<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    rest: RestElement,
+) -> ControlFlow<B, RestElement> {
+    ControlFlow::Continue(RestElement {
+        argument: folder.try_fold_pattern(rest.argument)?,
+        ..rest
+    })
+}
+
+pub fn try_walk_assignment_pattern<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    assignment: Assignment,
+) -> ControlFlow<B, Assignment> {
+    ControlFlow::Continue(Assignment {
+        left: folder.try_fold_pattern(assignment.left)?,
+        right: folder.try_fold_expression(assignment.right)?,
+        ..assignment
+    })
+}
+
+pub fn try_walk_expression<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    expr: Expression,
+) -> ControlFlow<B, Expression> {
+    ControlFlow::Continue(match expr {
+        Expression::This(inner) => Expression::This(folder.try_fold_this(inner)?),
+        Expression::Array(inner) => Expression::Array(folder.try_fold_array_expression(inner)?),
+        Expression::Object(inner) => Expression::Object(folder.try_fold_object_expression(inner)?),
+        Expression::Function(inner) => {
+            Expression::Function(Box::new(folder.try_fold_function(*inner)?))
+        }
+        Expression::Unary(inner) => Expression::Unary(Box::new(folder.try_fold_unary(*inner)?)),
+        Expression::Update(inner) => Expression::Update(Box::new(folder.try_fold_update(*inner)?)),
+        Expression::Binary(inner) => Expression::Binary(Box::new(folder.try_fold_binary(*inner)?)),
+        Expression::Assignment(inner) => {
+            Expression::Assignment(Box::new(folder.try_fold_assignment_expression(*inner)?))
+        }
+        Expression::Logical(inner) => {
+            Expression::Logical(Box::new(folder.try_fold_logical(*inner)?))
+        }
+        Expression::Member(inner) => Expression::Member(Box::new(folder.try_fold_member(*inner)?)),
+        Expression::Conditional(inner) => {
+            Expression::Conditional(Box::new(folder.try_fold_conditional(*inner)?))
+        }
+        Expression::Call(inner) => Expression::Call(Box::new(folder.try_fold_call(*inner)?)),
+        Expression::New(inner) => Expression::New(Box::new(folder.try_fold_new(*inner)?)),
+        Expression::Sequence(inner) => Expression::Sequence(folder.try_fold_sequence(inner)?),
+        Expression::Spread(inner) => {
+            Expression::Spread(Box::new(folder.try_fold_spread_element(*inner)?))
+        }
+        Expression::ArrowFunction(inner) => {
+            Expression::ArrowFunction(Box::new(folder.try_fold_arrow_function(*inner)?))
+        }
+        Expression::Yield(inner) => Expression::Yield(Box::new(folder.try_fold_yield(*inner)?)),
+        Expression::Await(inner) => Expression::Await(Box::new(folder.try_fold_await(*inner)?)),
+        Expression::Chain(inner) => Expression::Chain(Box::new(folder.try_fold_chain(*inner)?)),
+        Expression::Identifier(inner) => {
+            Expression::Identifier(Box::new(folder.try_fold_identifier(*inner)?))
+        }
+        Expression::Literal(inner) => {
+            Expression::Literal(Box::new(folder.try_fold_literal(*inner)?))
+        }
+        Expression::TaggedTemplate(inner) => {
+            Expression::TaggedTemplate(Box::new(folder.try_fold_tagged_template(*inner)?))
+        }
+        Expression::Class(inner) => Expression::Class(Box::new(folder.try_fold_class(*inner)?)),
+        Expression::Meta(inner) => Expression::Meta(folder.try_fold_meta_property(inner)?),
+    })
+}
+
+pub fn try_walk_literal<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    literal: Literal,
+) -> ControlFlow<B, Literal> {
+    ControlFlow::Continue(match literal.value {
+        LiteralValue::TemplateLiteral(template) => Literal {
+            value: LiteralValue::TemplateLiteral(folder.try_fold_template_literal(template)?),
+            loc: literal.loc,
+        },
+        _ => literal,
+    })
+}
+
+pub fn try_walk_array_expression<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    array: expression::Array,
+) -> ControlFlow<B, expression::Array> {
+    ControlFlow::Continue(expression::Array {
+        elements: try_map(array.elements, |e| {
+            try_opt(e, |e| folder.try_fold_spreadable(e))
+        })?,
+        ..array
+    })
+}
+
+pub fn try_walk_spreadable<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    spreadable: Spreadable,
+) -> ControlFlow<B, Spreadable> {
+    ControlFlow::Continue(match spreadable {
+        Spreadable::Expr(e) => Spreadable::Expr(folder.try_fold_expression(e)?),
+        Spreadable::Spread(s) => Spreadable::Spread(folder.try_fold_spread_element(s)?),
+    })
+}
+
+pub fn try_walk_spread_element<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    spread: SpreadElement,
+) -> ControlFlow<B, SpreadElement> {
+    ControlFlow::Continue(SpreadElement {
+        argument: folder.try_fold_expression(spread.argument)?,
+        ..spread
+    })
+}
+
+pub fn try_walk_object_expression<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    object: expression::Object,
+) -> ControlFlow<B, expression::Object> {
+    ControlFlow::Continue(expression::Object {
+        properties: try_map(object.properties, |p| folder.try_fold_property(p))?,
+        ..object
+    })
+}
+
+pub fn try_walk_property<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    property: Property,
+) -> ControlFlow<B, Property> {
+    ControlFlow::Continue(Property {
+        key: folder.try_fold_expression(property.key)?,
+        value: folder.try_fold_expression(property.value)?,
+        ..property
+    })
+}
+
+pub fn try_walk_unary<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    unary: Unary,
+) -> ControlFlow<B, Unary> {
+    ControlFlow::Continue(Unary {
+        argument: folder.try_fold_expression(unary.argument)?,
+        ..unary
+    })
+}
+
+pub fn try_walk_update<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    update: Update,
+) -> ControlFlow<B, Update> {
+    ControlFlow::Continue(Update {
+        argument: folder.try_fold_expression(update.argument)?,
+        ..update
+    })
+}
+
+pub fn try_walk_binary<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    binary: Binary,
+) -> ControlFlow<B, Binary> {
+    ControlFlow::Continue(Binary {
+        left: folder.try_fold_expression(binary.left)?,
+        right: folder.try_fold_expression(binary.right)?,
+        ..binary
+    })
+}
+
+pub fn try_walk_assignment_expression<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    assignment: expression::Assignment,
+) -> ControlFlow<B, expression::Assignment> {
+    ControlFlow::Continue(expression::Assignment {
+        left: folder.try_fold_pat_or_expr(assignment.left)?,
+        right: folder.try_fold_expression(assignment.right)?,
+        ..assignment
+    })
+}
+
+pub fn try_walk_pat_or_expr<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    pat_or_expr: PatOrExpr,
+) -> ControlFlow<B, PatOrExpr> {
+    ControlFlow::Continue(match pat_or_expr {
+        PatOrExpr::Pat(p) => PatOrExpr::Pat(folder.try_fold_pattern(p)?),
+        PatOrExpr::Expr(e) => PatOrExpr::Expr(folder.try_fold_expression(e)?),
+    })
+}
+
+pub fn try_walk_logical<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    logical: Logical,
+) -> ControlFlow<B, Logical> {
+    ControlFlow::Continue(Logical {
+        left: folder.try_fold_expression(logical.left)?,
+        right: folder.try_fold_expression(logical.right)?,
+        ..logical
+    })
+}
+
+pub fn try_walk_member<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    member: Member,
+) -> ControlFlow<B, Member> {
+    ControlFlow::Continue(Member {
+        object: folder.try_fold_parent(member.object)?,
+        property: folder.try_fold_expression(member.property)?,
+        ..member
+    })
+}
+
+pub fn try_walk_parent<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    parent: Parent,
+) -> ControlFlow<B, Parent> {
+    ControlFlow::Continue(match parent {
+        Parent::Expr(e) => Parent::Expr(folder.try_fold_expression(e)?),
+        Parent::Super(s) => Parent::Super(folder.try_fold_super(s)?),
+    })
+}
+
+pub fn try_walk_conditional<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    conditional: Conditional,
+) -> ControlFlow<B, Conditional> {
+    ControlFlow::Continue(Conditional {
+        test: folder.try_fold_expression(conditional.test)?,
+        consequent: folder.try_fold_expression(conditional.consequent)?,
+        alternate: folder.try_fold_expression(conditional.alternate)?,
+        ..conditional
+    })
+}
+
+pub fn try_walk_call<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    call: Call,
+) -> ControlFlow<B, Call> {
+    ControlFlow::Continue(Call {
+        callee: folder.try_fold_parent(call.callee)?,
+        arguments: try_map(call.arguments, |a| folder.try_fold_spreadable(a))?,
+        ..call
+    })
+}
+
+pub fn try_walk_new<B, F: TryFold<B> + ?Sized>(folder: &mut F, new: New) -> ControlFlow<B, New> {
+    ControlFlow::Continue(New {
+        callee: folder.try_fold_expression(new.callee)?,
+        arguments: try_map(new.arguments, |a| folder.try_fold_spreadable(a))?,
+        ..new
+    })
+}
+
+pub fn try_walk_sequence<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    sequence: Sequence,
+) -> ControlFlow<B, Sequence> {
+    ControlFlow::Continue(Sequence {
+        expressions: try_map(sequence.expressions, |e| folder.try_fold_expression(e))?,
+        ..sequence
+    })
+}
+
+pub fn try_walk_arrow_function<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    arrow: ArrowFunction,
+) -> ControlFlow<B, ArrowFunction> {
+    ControlFlow::Continue(ArrowFunction {
+        id: fold_opt_ident(folder, arrow.id)?,
+        params: try_map(arrow.params, |p| folder.try_fold_pattern(p))?,
+        body: folder.try_fold_arrow_function_body(arrow.body)?,
+        ..arrow
+    })
+}
+
+pub fn try_walk_arrow_function_body<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    body: ArrowFunctionBody,
+) -> ControlFlow<B, ArrowFunctionBody> {
+    ControlFlow::Continue(match body {
+        ArrowFunctionBody::Expr(e) => ArrowFunctionBody::Expr(folder.try_fold_expression(e)?),
+        ArrowFunctionBody::Block(b) => ArrowFunctionBody::Block(folder.try_fold_function_body(b)?),
+    })
+}
+
+pub fn try_walk_yield<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    expr: Yield,
+) -> ControlFlow<B, Yield> {
+    ControlFlow::Continue(Yield {
+        argument: fold_opt_expr(folder, expr.argument)?,
+        ..expr
+    })
+}
+
+pub fn try_walk_await<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    expr: Await,
+) -> ControlFlow<B, Await> {
+    ControlFlow::Continue(Await {
+        argument: folder.try_fold_expression(expr.argument)?,
+        ..expr
+    })
+}
+
+pub fn try_walk_chain<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    chain: Chain,
+) -> ControlFlow<B, Chain> {
+    ControlFlow::Continue(Chain {
+        expression: folder.try_fold_expression(chain.expression)?,
+        ..chain
+    })
+}
+
+pub fn try_walk_template_literal<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    template: TemplateLiteral,
+) -> ControlFlow<B, TemplateLiteral> {
+    ControlFlow::Continue(TemplateLiteral {
+        quasis: try_map(template.quasis, |q| folder.try_fold_template_element(q))?,
+        expressions: try_map(template.expressions, |e| folder.try_fold_expression(e))?,
+        ..template
+    })
+}
+
+pub fn try_walk_tagged_template<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    tagged: TaggedTemplate,
+) -> ControlFlow<B, TaggedTemplate> {
+    ControlFlow::Continue(TaggedTemplate {
+        tag: folder.try_fold_expression(tagged.tag)?,
+        quasi: folder.try_fold_template_literal(tagged.quasi)?,
+        ..tagged
+    })
+}
+
+pub fn try_walk_meta_property<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    meta: MetaProperty,
+) -> ControlFlow<B, MetaProperty> {
+    ControlFlow::Continue(MetaProperty {
+        meta: folder.try_fold_identifier(meta.meta)?,
+        property: folder.try_fold_identifier(meta.property)?,
+        ..meta
+    })
+}
+
+pub fn try_walk_statement<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    stmt: Statement,
+) -> ControlFlow<B, Statement> {
+    ControlFlow::Continue(match stmt {
+        Statement::Expr(inner) => Statement::Expr(folder.try_fold_expression(inner)?),
+        Statement::Block(inner) => Statement::Block(folder.try_fold_block(inner)?),
+        Statement::FuncBody(inner) => Statement::FuncBody(folder.try_fold_function_body(inner)?),
+        Statement::Empty(inner) => Statement::Empty(folder.try_fold_empty(inner)?),
+        Statement::Debugger(inner) => Statement::Debugger(folder.try_fold_debugger(inner)?),
+        Statement::With(inner) => Statement::With(Box::new(folder.try_fold_with(*inner)?)),
+        Statement::Return(inner) => Statement::Return(folder.try_fold_return(inner)?),
+        Statement::Labeled(inner) => Statement::Labeled(Box::new(folder.try_fold_labeled(*inner)?)),
+        Statement::Break(inner) => Statement::Break(folder.try_fold_break(inner)?),
+        Statement::Continue(inner) => Statement::Continue(folder.try_fold_continue(inner)?),
+        Statement::If(inner) => Statement::If(Box::new(folder.try_fold_if(*inner)?)),
+        Statement::Switch(inner) => Statement::Switch(folder.try_fold_switch(inner)?),
+        Statement::Throw(inner) => Statement::Throw(folder.try_fold_throw(inner)?),
+        Statement::Try(inner) => Statement::Try(folder.try_fold_try(inner)?),
+        Statement::While(inner) => Statement::While(Box::new(folder.try_fold_while(*inner)?)),
+        Statement::DoWhile(inner) => {
+            Statement::DoWhile(Box::new(folder.try_fold_do_while(*inner)?))
+        }
+        Statement::For(inner) => Statement::For(Box::new(folder.try_fold_for(*inner)?)),
+        Statement::ForIn(inner) => Statement::ForIn(Box::new(folder.try_fold_for_in(*inner)?)),
+        Statement::ForOf(inner) => Statement::ForOf(Box::new(folder.try_fold_for_of(*inner)?)),
+        Statement::Decl(inner) => Statement::Decl(folder.try_fold_declaration(inner)?),
+    })
+}
+
+pub fn try_walk_block<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    block: Block,
+) -> ControlFlow<B, Block> {
+    ControlFlow::Continue(Block {
+        body: fold_statements(folder, block.body)?,
+        ..block
+    })
+}
+
+pub fn try_walk_with<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    with: With,
+) -> ControlFlow<B, With> {
+    ControlFlow::Continue(With {
+        object: folder.try_fold_expression(with.object)?,
+        body: fold_body(folder, with.body)?,
+        ..with
+    })
+}
+
+pub fn try_walk_return<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    ret: Return,
+) -> ControlFlow<B, Return> {
+    ControlFlow::Continue(Return {
+        argument: fold_opt_expr(folder, ret.argument)?,
+        ..ret
+    })
+}
+
+pub fn try_walk_labeled<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    labeled: Labeled,
+) -> ControlFlow<B, Labeled> {
+    ControlFlow::Continue(Labeled {
+        label: folder.try_fold_identifier(labeled.label)?,
+        body: fold_body(folder, labeled.body)?,
+        ..labeled
+    })
+}
+
+pub fn try_walk_break<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    brk: Break,
+) -> ControlFlow<B, Break> {
+    ControlFlow::Continue(Break {
+        label: fold_opt_ident(folder, brk.label)?,
+        ..brk
+    })
+}
+
+pub fn try_walk_continue<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    cont: Continue,
+) -> ControlFlow<B, Continue> {
+    ControlFlow::Continue(Continue {
+        label: fold_opt_ident(folder, cont.label)?,
+        ..cont
+    })
+}
+
+pub fn try_walk_if<B, F: TryFold<B> + ?Sized>(folder: &mut F, stmt: If) -> ControlFlow<B, If> {
+    ControlFlow::Continue(If {
+        test: folder.try_fold_expression(stmt.test)?,
+        consequent: fold_body(folder, stmt.consequent)?,
+        alternate: try_opt(stmt.alternate, |alt| fold_body(folder, alt))?,
+        ..stmt
+    })
+}
+
+pub fn try_walk_switch<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    switch: Switch,
+) -> ControlFlow<B, Switch> {
+    ControlFlow::Continue(Switch {
+        discriminant: folder.try_fold_expression(switch.discriminant)?,
+        cases: try_map(switch.cases, |c| folder.try_fold_switch_case(c))?,
+        ..switch
+    })
+}
+
+pub fn try_walk_switch_case<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    case: SwitchCase,
+) -> ControlFlow<B, SwitchCase> {
+    ControlFlow::Continue(SwitchCase {
+        test: fold_opt_expr(folder, case.test)?,
+        consequent: fold_statements(folder, case.consequent)?,
+        ..case
+    })
+}
+
+pub fn try_walk_throw<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    throw: Throw,
+) -> ControlFlow<B, Throw> {
+    ControlFlow::Continue(Throw {
+        argument: folder.try_fold_expression(throw.argument)?,
+        ..throw
+    })
+}
+
+pub fn try_walk_try<B, F: TryFold<B> + ?Sized>(folder: &mut F, stmt: Try) -> ControlFlow<B, Try> {
+    ControlFlow::Continue(Try {
+        block: folder.try_fold_block(stmt.block)?,
+        handler: try_opt(stmt.handler, |h| folder.try_fold_catch_clause(h))?,
+        finalizer: try_opt(stmt.finalizer, |b| folder.try_fold_block(b))?,
+        ..stmt
+    })
+}
+
+pub fn try_walk_catch_clause<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    clause: CatchClause,
+) -> ControlFlow<B, CatchClause> {
+    ControlFlow::Continue(CatchClause {
+        param: folder.try_fold_pattern(clause.param)?,
+        body: folder.try_fold_block(clause.body)?,
+        ..clause
+    })
+}
+
+pub fn try_walk_while<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    stmt: While,
+) -> ControlFlow<B, While> {
+    ControlFlow::Continue(While {
+        test: folder.try_fold_expression(stmt.test)?,
+        body: fold_body(folder, stmt.body)?,
+        ..stmt
+    })
+}
+
+pub fn try_walk_do_while<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    stmt: DoWhile,
+) -> ControlFlow<B, DoWhile> {
+    ControlFlow::Continue(DoWhile {
+        body: fold_body(folder, stmt.body)?,
+        test: folder.try_fold_expression(stmt.test)?,
+        ..stmt
+    })
+}
+
+pub fn try_walk_for<B, F: TryFold<B> + ?Sized>(folder: &mut F, stmt: For) -> ControlFlow<B, For> {
+    ControlFlow::Continue(For {
+        init: folder.try_fold_for_init(stmt.init)?,
+        test: fold_opt_expr(folder, stmt.test)?,
+        update: fold_opt_expr(folder, stmt.update)?,
+        body: fold_body(folder, stmt.body)?,
+        ..stmt
+    })
+}
+
+pub fn try_walk_for_init<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    init: ForInit,
+) -> ControlFlow<B, ForInit> {
+    ControlFlow::Continue(match init {
+        ForInit::Var(v) => ForInit::Var(folder.try_fold_variable_declaration(v)?),
+        ForInit::Expr(e) => ForInit::Expr(folder.try_fold_expression(e)?),
+        ForInit::Null => ForInit::Null,
+    })
+}
+
+pub fn try_walk_for_in<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    stmt: ForIn,
+) -> ControlFlow<B, ForIn> {
+    ControlFlow::Continue(ForIn {
+        left: folder.try_fold_for_left(stmt.left)?,
+        right: folder.try_fold_expression(stmt.right)?,
+        body: fold_body(folder, stmt.body)?,
+        ..stmt
+    })
+}
+
+pub fn try_walk_for_of<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    stmt: ForOf,
+) -> ControlFlow<B, ForOf> {
+    ControlFlow::Continue(ForOf {
+        left: folder.try_fold_for_left(stmt.left)?,
+        right: folder.try_fold_expression(stmt.right)?,
+        body: fold_body(folder, stmt.body)?,
+        ..stmt
+    })
+}
+
+pub fn try_walk_for_left<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    left: ForLeft,
+) -> ControlFlow<B, ForLeft> {
+    ControlFlow::Continue(match left {
+        ForLeft::Var(v) => ForLeft::Var(folder.try_fold_variable_declaration(v)?),
+        ForLeft::Pat(p) => ForLeft::Pat(folder.try_fold_pattern(p)?),
+    })
+}
+
+pub fn try_walk_declaration<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    decl: Declaration,
+) -> ControlFlow<B, Declaration> {
+    ControlFlow::Continue(match decl {
+        Declaration::Function(f) => Declaration::Function(folder.try_fold_function(f)?),
+        Declaration::Variable(v) => Declaration::Variable(folder.try_fold_variable_declaration(v)?),
+        Declaration::Class(c) => Declaration::Class(folder.try_fold_class(c)?),
+    })
+}
+
+pub fn try_walk_variable_declaration<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    decl: VariableDeclaration,
+) -> ControlFlow<B, VariableDeclaration> {
+    ControlFlow::Continue(VariableDeclaration {
+        declarations: try_map(decl.declarations, |d| {
+            folder.try_fold_variable_declarator(d)
+        })?,
+        ..decl
+    })
+}
+
+pub fn try_walk_variable_declarator<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    decl: VariableDeclarator,
+) -> ControlFlow<B, VariableDeclarator> {
+    ControlFlow::Continue(VariableDeclarator {
+        id: folder.try_fold_pattern(decl.id)?,
+        init: fold_opt_expr(folder, decl.init)?,
+        ..decl
+    })
+}
+
+pub fn try_walk_module_declaration<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    decl: ModuleDeclaration,
+) -> ControlFlow<B, ModuleDeclaration> {
+    ControlFlow::Continue(match decl {
+        ModuleDeclaration::Import(i) => {
+            ModuleDeclaration::Import(folder.try_fold_import_declaration(i)?)
+        }
+        ModuleDeclaration::Export(e) => {
+            ModuleDeclaration::Export(folder.try_fold_export_declaration(e)?)
+        }
+    })
+}
+
+pub fn try_walk_import_declaration<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    decl: ImportDeclaration,
+) -> ControlFlow<B, ImportDeclaration> {
+    ControlFlow::Continue(ImportDeclaration {
+        specifiers: try_map(decl.specifiers, |s| folder.try_fold_import_specifier(s))?,
+        source: folder.try_fold_literal(decl.source)?,
+        ..decl
+    })
+}
+
+pub fn try_walk_import_specifier<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    spec: ImportSpecifier,
+) -> ControlFlow<B, ImportSpecifier> {
+    ControlFlow::Continue(match spec {
+        ImportSpecifier::Named(n) => ImportSpecifier::Named(folder.try_fold_import(n)?),
+        ImportSpecifier::Default(d) => ImportSpecifier::Default(folder.try_fold_import_default(d)?),
+        ImportSpecifier::NameSpace(n) => {
+            ImportSpecifier::NameSpace(folder.try_fold_import_namespace(n)?)
+        }
+    })
+}
+
+pub fn try_walk_import<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    import: Import,
+) -> ControlFlow<B, Import> {
+    ControlFlow::Continue(Import {
+        import: folder.try_fold_identifier(import.import)?,
+        local: folder.try_fold_identifier(import.local)?,
+        ..import
+    })
+}
+
+pub fn try_walk_import_default<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    import: ImportDefault,
+) -> ControlFlow<B, ImportDefault> {
+    ControlFlow::Continue(ImportDefault {
+        local: folder.try_fold_identifier(import.local)?,
+        ..import
+    })
+}
+
+pub fn try_walk_import_namespace<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    import: ImportNamespace,
+) -> ControlFlow<B, ImportNamespace> {
+    ControlFlow::Continue(ImportNamespace {
+        local: folder.try_fold_identifier(import.local)?,
+        ..import
+    })
+}
+
+pub fn try_walk_export_declaration<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    decl: ExportDeclaration,
+) -> ControlFlow<B, ExportDeclaration> {
+    ControlFlow::Continue(match decl {
+        ExportDeclaration::Named(n) => ExportDeclaration::Named(folder.try_fold_export_named(n)?),
+        ExportDeclaration::Default(d) => {
+            ExportDeclaration::Default(folder.try_fold_export_default(d)?)
+        }
+        ExportDeclaration::All(a) => ExportDeclaration::All(folder.try_fold_export_all(a)?),
+    })
+}
+
+pub fn try_walk_export_named<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    export: ExportNamed,
+) -> ControlFlow<B, ExportNamed> {
+    ControlFlow::Continue(ExportNamed {
+        declaration: try_opt(export.declaration, |d| folder.try_fold_declaration(d))?,
+        specifiers: try_map(export.specifiers, |s| folder.try_fold_export_specifier(s))?,
+        source: try_opt(export.source, |s| folder.try_fold_literal(s))?,
+        ..export
+    })
+}
+
+pub fn try_walk_export_specifier<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    spec: ExportSpecifier,
+) -> ControlFlow<B, ExportSpecifier> {
+    ControlFlow::Continue(ExportSpecifier {
+        local: folder.try_fold_identifier(spec.local)?,
+        exported: folder.try_fold_identifier(spec.exported)?,
+        ..spec
+    })
+}
+
+pub fn try_walk_export_default<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    export: ExportDefault,
+) -> ControlFlow<B, ExportDefault> {
+    ControlFlow::Continue(ExportDefault {
+        declaration: folder.try_fold_export_decl(export.declaration)?,
+        ..export
+    })
+}
+
+pub fn try_walk_export_decl<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    decl: ExportDecl,
+) -> ControlFlow<B, ExportDecl> {
+    ControlFlow::Continue(match decl {
+        ExportDecl::Decl(d) => ExportDecl::Decl(folder.try_fold_declaration(d)?),
+        ExportDecl::Expr(e) => ExportDecl::Expr(folder.try_fold_expression(e)?),
+    })
+}
+
+pub fn try_walk_export_all<B, F: TryFold<B> + ?Sized>(
+    folder: &mut F,
+    export: ExportAll,
+) -> ControlFlow<B, ExportAll> {
+    ControlFlow::Continue(ExportAll {
+        source: folder.try_fold_literal(export.source)?,
+        ..export
+    })
+}
@@ -1,42 +1,129 @@
 #[macro_use]
 extern crate inherit;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
 
+#[cfg(feature = "serde")]
+pub mod estree;
+pub mod binary;
+pub mod codegen;
 pub mod expression;
+pub mod fold;
+pub mod line_index;
 pub mod module;
 pub mod node;
+pub mod node_id;
+pub mod node_ref;
+pub mod path;
+pub mod source_map;
 pub mod statement;
+pub mod structural;
+pub mod visit;
+pub mod visit_mut;
 
 use expression::{Expression, Identifier, Literal, PropertyKind};
 use module::ModuleDeclaration;
-use node::{Node, NodeKind, SourceLocation};
+use node::{Children, Node, NodeKind, Position, SourceLocation};
 use statement::{FunctionBody, Statement};
+use structural::{StableHasher, Structural, StructuralOptions};
 
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Program {
     pub source_type: SourceType,
     pub body: Vec<ProgramPart>,
 }
-#[derive(Debug)]
+
+impl Program {
+    /// Every node covering `position`, from the `Program` itself
+    /// down to the innermost match
+    pub fn nodes_at(&self, position: Position) -> Vec<&dyn Node> {
+        let mut path = Vec::new();
+        self.each_node_at(position, &mut |node| path.push(node));
+        path
+    }
+    /// The innermost node covering `position`
+    pub fn node_at(&self, position: Position) -> Option<&dyn Node> {
+        let mut innermost = None;
+        self.each_node_at(position, &mut |node| innermost = Some(node));
+        innermost
+    }
+    /// Descend through the first child covering `position` at each
+    /// level, passing every node on the way to `f`
+    fn each_node_at<'a>(&'a self, position: Position, f: &mut dyn FnMut(&'a dyn Node)) {
+        if !self.loc.contains(position) {
+            return;
+        }
+        let mut node: &dyn Node = self;
+        loop {
+            f(node);
+            let mut found = None;
+            node.for_each_child(&mut |child| {
+                if found.is_none() && child.loc().contains(position) {
+                    found = Some(child);
+                }
+            });
+            match found {
+                Some(child) => node = child,
+                None => return,
+            }
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum SourceType {
     Script,
     Module,
 }
-#[derive(Debug)]
+
+impl Children for SourceType {}
+
+impl SourceType {
+    /// The ESTree name for this source type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceType::Script => "script",
+            SourceType::Module => "module",
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
+#[allow(clippy::large_enum_variant)]
 pub enum ProgramPart {
     Directive(Directive),
     Statement(Statement),
     ModuleDecl(ModuleDeclaration),
 }
-#[derive(Debug)]
+
+impl Children for ProgramPart {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ProgramPart::Directive(ref inner) => inner.each_node(f),
+            ProgramPart::Statement(ref inner) => inner.each_node(f),
+            ProgramPart::ModuleDecl(ref inner) => inner.each_node(f),
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Function {
     pub id: Option<Identifier>,
     pub params: Vec<Pattern>,
     pub body: FunctionBody,
     pub generator: bool,
+    pub _async: bool,
     pub loc: SourceLocation,
 }
-#[derive(Debug)]
+
+impl Children for Function {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        self.id.each_node(f);
+        self.params.each_node(f);
+        self.body.each_node(f);
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Directive {
     pub expression: Literal,
     pub directive: String,
@@ -45,14 +132,24 @@ pub struct Directive {
 
 impl Node for Directive {
     fn loc(&self) -> SourceLocation {
-        self.loc.clone()
+        self.loc
     }
 
     fn kind(&self) -> NodeKind {
         NodeKind::ExpressionStatement
     }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(&self.expression)
+    }
 }
-#[derive(Debug)]
+
+impl Children for Directive {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct AssignmentProperty {
     pub key: Expression,
     pub value: Pattern,
@@ -62,7 +159,14 @@ pub struct AssignmentProperty {
     pub computed: bool,
 }
 
-#[derive(Debug)]
+impl Children for AssignmentProperty {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        self.key.each_node(f);
+        self.value.each_node(f);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum Pattern {
     Ident(Identifier),
     Object(Object),
@@ -71,22 +175,22 @@ pub enum Pattern {
     Assignment(Box<Assignment>),
 }
 #[inherit(Pattern)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Object {
     pub properties: Vec<AssignmentProperty>,
 }
 #[inherit(Pattern)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Array {
     pub elements: Vec<Option<Pattern>>,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct RestElement {
     pub argument: Pattern,
 }
 #[inherit(Pattern)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Assignment {
     pub left: Pattern,
     pub right: Expression,
@@ -112,22 +216,57 @@ impl Node for Pattern {
             Pattern::Assignment(ref a) => a.kind(),
         }
     }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        match self {
+            Pattern::Ident(ref inner) => Some(inner),
+            Pattern::Object(ref inner) => Some(inner),
+            Pattern::Array(ref inner) => Some(inner),
+            Pattern::RestElement(ref inner) => Some(&**inner),
+            Pattern::Assignment(ref inner) => Some(&**inner),
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            Pattern::Ident(ref i) => i.for_each_child(f),
+            Pattern::Object(ref o) => o.for_each_child(f),
+            Pattern::Array(ref a) => a.for_each_child(f),
+            Pattern::RestElement(ref r) => r.for_each_child(f),
+            Pattern::Assignment(ref a) => a.for_each_child(f),
+        }
+    }
 }
-#[derive(Debug)]
+
+impl Children for Pattern {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Class {
     pub id: Option<Identifier>,
     pub super_class: Option<Expression>,
     pub body: ClassBody,
     pub loc: SourceLocation,
 }
+
+impl Children for Class {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        self.id.each_node(f);
+        self.super_class.each_node(f);
+        self.body.each_node(f);
+    }
+}
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ClassBody {
     pub body: Vec<MethodDefinition>,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct MethodDefinition {
+    #[structural(name_unless = "computed")]
     pub key: Expression,
     //TODO: FunctionExpression?
     pub value: Function,
@@ -135,10 +274,24 @@ pub struct MethodDefinition {
     pub computed: bool,
     pub _static: bool,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum MethodKind {
     Constructor,
     Method,
     Get,
     Set,
 }
+
+impl Children for MethodKind {}
+
+impl MethodKind {
+    /// The ESTree name for this kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MethodKind::Constructor => "constructor",
+            MethodKind::Method => "method",
+            MethodKind::Get => "get",
+            MethodKind::Set => "set",
+        }
+    }
+}
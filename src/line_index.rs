@@ -0,0 +1,163 @@
+use crate::node::{FileId, Position, Range, SourceLocation};
+use std::num::NonZeroU64;
+/// A map of line starts for a piece of source text, used to move
+/// between byte offsets, `Position`s and UTF-16 columns.
+///
+/// Line terminators follow the JS spec: `\n`, `\r\n`, `\r`,
+/// `\u{2028}` and `\u{2029}`. The `column` of any `Position`
+/// produced or consumed here is a byte offset from the start of
+/// its line.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+    wide_chars: Vec<Vec<WideChar>>,
+    len: usize,
+}
+/// A char on a line whose UTF-8 and UTF-16 lengths differ
+#[derive(Debug, Clone, Copy)]
+struct WideChar {
+    column: usize,
+    len_utf8: usize,
+    len_utf16: usize,
+}
+
+impl WideChar {
+    fn extra_bytes(self) -> usize {
+        self.len_utf8 - self.len_utf16
+    }
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars = vec![Vec::new()];
+        let mut chars = text.char_indices().peekable();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '\r' => {
+                    let end = if let Some(&(_, '\n')) = chars.peek() {
+                        chars.next();
+                        idx + 2
+                    } else {
+                        idx + 1
+                    };
+                    line_starts.push(end);
+                    wide_chars.push(Vec::new());
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => {
+                    line_starts.push(idx + c.len_utf8());
+                    wide_chars.push(Vec::new());
+                }
+                _ => {
+                    if c.len_utf8() > 1 {
+                        let line_start = line_starts[line_starts.len() - 1];
+                        if let Some(line) = wide_chars.last_mut() {
+                            line.push(WideChar {
+                                column: idx - line_start,
+                                len_utf8: c.len_utf8(),
+                                len_utf16: c.len_utf16(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        LineIndex {
+            line_starts,
+            wide_chars,
+            len: text.len(),
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+    /// The byte range of a 1-based line, including its terminator
+    pub fn line_range(&self, line: NonZeroU64) -> Option<Range> {
+        let idx = line.get() as usize - 1;
+        let start = *self.line_starts.get(idx)?;
+        let end = self.line_starts.get(idx + 1).cloned().unwrap_or(self.len);
+        Some(Range::new(start, end))
+    }
+    /// Convert a byte offset into a `Position`, `None` if the
+    /// offset is past the end of the text
+    pub fn position(&self, offset: usize) -> Option<Position> {
+        if offset > self.len {
+            return None;
+        }
+        let idx = match self.line_starts.binary_search(&offset) {
+            Ok(idx) => idx,
+            Err(idx) => idx - 1,
+        };
+        Some(Position {
+            line: NonZeroU64::new(idx as u64 + 1)?,
+            column: (offset - self.line_starts[idx]) as u64,
+        })
+    }
+    /// Convert a `Position` back into a byte offset, `None` if the
+    /// line doesn't exist or the column runs past its end
+    pub fn offset(&self, position: Position) -> Option<usize> {
+        let range = self.line_range(position.line)?;
+        let offset = range.start.checked_add(position.column as usize)?;
+        if offset > range.end {
+            return None;
+        }
+        Some(offset)
+    }
+    /// Build a `SourceLocation` for a byte range
+    pub fn location(&self, range: Range, source: Option<FileId>) -> Option<SourceLocation> {
+        Some(SourceLocation {
+            source,
+            start: self.position(range.start)?,
+            end: self.position(range.end)?,
+            range,
+        })
+    }
+    /// The column of `position` counted in UTF-16 code units, the
+    /// unit used by editors and source maps, `None` if it falls
+    /// inside a character
+    pub fn utf16_column(&self, position: Position) -> Option<u64> {
+        self.offset(position)?;
+        let column = position.column as usize;
+        let mut extra = 0;
+        for c in self.line_wide_chars(position.line) {
+            if column <= c.column {
+                break;
+            }
+            if column < c.column + c.len_utf8 {
+                return None;
+            }
+            extra += c.extra_bytes();
+        }
+        Some((column - extra) as u64)
+    }
+    /// Convert a line and UTF-16 column into a `Position`, `None`
+    /// if the column falls inside a surrogate pair
+    pub fn position_from_utf16(&self, line: NonZeroU64, column: u64) -> Option<Position> {
+        let column = column as usize;
+        let mut extra = 0;
+        for c in self.line_wide_chars(line) {
+            let start = c.column - extra;
+            if column <= start {
+                break;
+            }
+            if column < start + c.len_utf16 {
+                return None;
+            }
+            extra += c.extra_bytes();
+        }
+        let position = Position {
+            line,
+            column: column.checked_add(extra)? as u64,
+        };
+        self.offset(position)?;
+        Some(position)
+    }
+
+    fn line_wide_chars(&self, line: NonZeroU64) -> &[WideChar] {
+        self.wide_chars
+            .get(line.get() as usize - 1)
+            .map(|l| l.as_slice())
+            .unwrap_or(&[])
+    }
+}
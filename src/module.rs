@@ -1,9 +1,11 @@
 use crate::{
     expression::{Expression, Literal},
     statement::Declaration,
-    Identifier, Node, NodeKind, SourceLocation,
+    Children, Identifier, Node, NodeKind, SourceLocation, StableHasher, Structural,
+    StructuralOptions,
 };
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
+#[allow(clippy::large_enum_variant)]
 pub enum ModuleDeclaration {
     Import(ImportDeclaration),
     Export(ExportDeclaration),
@@ -23,33 +25,66 @@ impl Node for ModuleDeclaration {
             ModuleDeclaration::Export(ref inner) => inner.kind(),
         }
     }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        match self {
+            ModuleDeclaration::Import(ref inner) => Some(inner),
+            ModuleDeclaration::Export(ref inner) => Some(inner),
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ModuleDeclaration::Import(ref inner) => inner.for_each_child(f),
+            ModuleDeclaration::Export(ref inner) => inner.for_each_child(f),
+        }
+    }
+}
+
+impl Children for ModuleDeclaration {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
 }
 
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ImportDeclaration {
     pub specifiers: Vec<ImportSpecifier>,
+    pub source: Literal,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum ImportSpecifier {
     Named(Import),
     Default(ImportDefault),
     NameSpace(ImportNamespace),
 }
 
+impl Children for ImportSpecifier {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ImportSpecifier::Named(ref inner) => inner.each_node(f),
+            ImportSpecifier::Default(ref inner) => inner.each_node(f),
+            ImportSpecifier::NameSpace(ref inner) => inner.each_node(f),
+        }
+    }
+}
+
 #[inherit(ImportSpecifier)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Import {
+    #[structural(name)]
     pub import: Identifier,
 }
 #[inherit(ImportSpecifier)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ImportDefault {}
 
 #[inherit(ImportSpecifier)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ImportNamespace {}
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
+#[allow(clippy::large_enum_variant)]
 pub enum ExportDeclaration {
     Named(ExportNamed),
     Default(ExportDefault),
@@ -71,35 +106,68 @@ impl Node for ExportDeclaration {
             ExportDeclaration::All(ref inner) => inner.kind(),
         }
     }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        match self {
+            ExportDeclaration::Named(ref inner) => Some(inner),
+            ExportDeclaration::Default(ref inner) => Some(inner),
+            ExportDeclaration::All(ref inner) => Some(inner),
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ExportDeclaration::Named(ref inner) => inner.for_each_child(f),
+            ExportDeclaration::Default(ref inner) => inner.for_each_child(f),
+            ExportDeclaration::All(ref inner) => inner.for_each_child(f),
+        }
+    }
+}
+
+impl Children for ExportDeclaration {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
 }
 
 #[inherit(Declaration)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ExportNamed {
     pub declaration: Option<Declaration>,
     pub specifiers: Vec<ExportSpecifier>,
     pub source: Option<Literal>,
-    pub exported: Identifier,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ExportSpecifier {
+    pub local: Identifier,
+    #[structural(name)]
     pub exported: Identifier,
 }
 
 #[inherit(Declaration)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ExportDefault {
     pub declaration: ExportDecl,
 }
 
 #[inherit(Declaration)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ExportAll {
     pub source: Literal,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
+#[allow(clippy::large_enum_variant)]
 pub enum ExportDecl {
     Decl(Declaration),
     Expr(Expression),
 }
+
+impl Children for ExportDecl {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ExportDecl::Decl(ref inner) => inner.each_node(f),
+            ExportDecl::Expr(ref inner) => inner.each_node(f),
+        }
+    }
+}
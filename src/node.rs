@@ -1,22 +1,239 @@
+use std::collections::HashMap;
 use std::num::NonZeroU64;
+
+use crate::source_map::{Remapper, SourceMap};
 pub trait Node {
     fn loc(&self) -> SourceLocation;
     fn kind(&self) -> NodeKind;
+    /// Call `f` with each direct child of this node, in source
+    /// order. Nodes without children can leave this out.
+    fn for_each_child<'a>(&'a self, _f: &mut dyn FnMut(&'a dyn Node)) {}
+    /// The node an enum like `Expression` holds in its variant, when
+    /// that node has the same kind and location as the enum
+    fn wrapped(&self) -> Option<&dyn Node> {
+        None
+    }
+    /// The direct children of this node, in source order
+    fn children(&self) -> Vec<&dyn Node> {
+        let mut children = Vec::new();
+        self.for_each_child(&mut |child| children.push(child));
+        children
+    }
+}
+/// Anything that can appear as a field of a node, passing on
+/// the nodes it holds. Nodes pass themselves, containers and
+/// non-node enums pass whatever nodes they hold and leaf values
+/// (flags, operators, names) pass nothing.
+pub trait Children {
+    fn each_node<'a>(&'a self, _f: &mut dyn FnMut(&'a dyn Node)) {}
 }
 
-#[derive(Debug, Clone)]
+impl Children for bool {}
+impl Children for String {}
+
+impl<T: Children> Children for Option<T> {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        if let Some(ref inner) = self {
+            inner.each_node(f);
+        }
+    }
+}
+
+impl<T: Children> Children for Vec<T> {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        for inner in self {
+            inner.each_node(f);
+        }
+    }
+}
+
+impl<T: Children> Children for Box<T> {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        (**self).each_node(f);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct SourceLocation {
-    pub source: Option<String>,
+    pub source: Option<FileId>,
     pub start: Position,
     pub end: Position,
+    pub range: Range,
+}
+
+impl SourceLocation {
+    /// The text this location covers in `source_text`
+    pub fn slice<'a>(&self, source_text: &'a str) -> &'a str {
+        self.range.slice(source_text)
+    }
+    /// The text this location covers in `source_text`, if its range
+    /// fits in it
+    pub fn get<'a>(&self, source_text: &'a str) -> Option<&'a str> {
+        self.range.get(source_text)
+    }
+    /// If `position` falls inside this location, both ends inclusive
+    pub fn contains(&self, position: Position) -> bool {
+        self.start <= position && position <= self.end
+    }
+
+    pub fn contains_loc(&self, other: &SourceLocation) -> bool {
+        self.source == other.source && self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &SourceLocation) -> bool {
+        self.source == other.source && self.start < other.end && other.start < self.end
+    }
+    /// Where this location in a generated file came from according
+    /// to `map`. Use a `source_map::Remapper` to remap many locations
+    /// against the same files.
+    pub fn remap(&self, map: &SourceMap, registry: &SourceRegistry) -> Option<SourceLocation> {
+        Remapper::new(map, registry).remap(self)
+    }
+    /// The smallest location covering both `self` and `other`,
+    /// keeping the `source` of `self`
+    pub fn merge(&self, other: &SourceLocation) -> SourceLocation {
+        SourceLocation {
+            source: self.source,
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+            range: self.range.merge(&other.range),
+        }
+    }
+}
+/// A cheap handle to a file registered in a `SourceRegistry`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(u32);
+
+impl FileId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub(crate) fn from_index(index: u32) -> Self {
+        FileId(index)
+    }
+}
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub text: Option<String>,
 }
-#[derive(Debug, Clone, Copy)]
+/// Owns the names (and optionally the text) of every file a
+/// tree's `SourceLocation`s can refer to
+#[derive(Debug, Clone, Default)]
+pub struct SourceRegistry {
+    files: Vec<SourceFile>,
+    ids: HashMap<String, FileId>,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Register a file by name, returning the existing id if
+    /// the name has already been registered
+    pub fn add(&mut self, name: &str) -> FileId {
+        if let Some(id) = self.lookup(name) {
+            return id;
+        }
+        let id = FileId(self.files.len() as u32);
+        self.files.push(SourceFile {
+            name: name.to_string(),
+            text: None,
+        });
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+    /// Register a file along with its text, replacing any text
+    /// previously stored for that name
+    pub fn add_with_text(&mut self, name: &str, text: String) -> FileId {
+        let id = self.add(name);
+        self.files[id.index()].text = Some(text);
+        id
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<FileId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn get(&self, id: FileId) -> Option<&SourceFile> {
+        self.files.get(id.index())
+    }
+
+    pub fn name(&self, id: FileId) -> Option<&str> {
+        self.get(id).map(|f| f.name.as_str())
+    }
+
+    pub fn text(&self, id: FileId) -> Option<&str> {
+        self.get(id).and_then(|f| f.text.as_deref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| (FileId(idx as u32), f))
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Position {
     pub line: NonZeroU64,
     pub column: u64,
 }
+/// Byte offsets into the original source text, ESTree's `range`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Range {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Range {
+    pub fn new(start: usize, end: usize) -> Self {
+        Range { start, end }
+    }
+
+    /// The length in bytes, or 0 if `end` is before `start`
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The text this range covers. Panics if the range is backwards,
+    /// out of bounds or not on `char` boundaries, see `get`.
+    pub fn slice<'a>(&self, source_text: &'a str) -> &'a str {
+        &source_text[self.start..self.end]
+    }
+    /// The text this range covers, if it is a valid range of
+    /// `source_text`
+    pub fn get<'a>(&self, source_text: &'a str) -> Option<&'a str> {
+        source_text.get(self.start..self.end)
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset <= self.end
+    }
+
+    pub fn contains_range(&self, other: &Range) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn merge(&self, other: &Range) -> Range {
+        Range::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NodeKind {
     Identifier,
     Literal,
@@ -63,6 +280,8 @@ pub enum NodeKind {
     SpreadElement,
     ArrowFunctionExpression,
     YieldExpression,
+    AwaitExpression,
+    ChainExpression,
     TemplateLiteral,
     TaggedTemplateExpression,
     TemplateElement,
@@ -0,0 +1,179 @@
+//! Numeric IDs for the nodes of a tree, so analyses can keep their
+//! results in side tables instead of wrapping the AST.
+//!
+//! `NodeTable::new` numbers every node reachable through
+//! `Node::children` in pre-order, starting with the root at 0. The
+//! same tree always gets the same IDs, so a `NodeMap` built from
+//! one table can be read using another built from an unchanged tree.
+//! A node can be looked up through an enum like `Expression` or
+//! through the node in its variant, as `Visit` passes both.
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+use crate::node::{Node, NodeKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// The IDs, parents and children of every node in a tree
+pub struct NodeTable<'ast> {
+    nodes: Vec<&'ast dyn Node>,
+    parents: Vec<Option<NodeId>>,
+    children: Vec<Vec<NodeId>>,
+    // a node and its first field can share an address, the kind
+    // tells them apart
+    ids: HashMap<(*const (), NodeKind), NodeId>,
+}
+
+impl<'ast> NodeTable<'ast> {
+    pub fn new(root: &'ast dyn Node) -> Self {
+        let mut table = NodeTable {
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            children: Vec::new(),
+            ids: HashMap::new(),
+        };
+        let mut stack = vec![(root, None)];
+        while let Some((node, parent)) = stack.pop() {
+            let id = NodeId(table.nodes.len() as u32);
+            table.nodes.push(node);
+            table.parents.push(parent);
+            table.children.push(Vec::new());
+            let mut wrapper = Some(node);
+            while let Some(node) = wrapper {
+                table.ids.insert(key(node), id);
+                wrapper = node.wrapped();
+            }
+            if let Some(parent) = parent {
+                table.children[parent.index()].push(id);
+            }
+            let first = stack.len();
+            node.for_each_child(&mut |child| stack.push((child, Some(id))));
+            stack[first..].reverse();
+        }
+        table
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&'ast dyn Node> {
+        self.nodes.get(id.index()).cloned()
+    }
+    /// The ID of a node in this tree, `None` if it isn't part of it
+    pub fn id_of(&self, node: &dyn Node) -> Option<NodeId> {
+        match self.ids.get(&key(node)) {
+            Some(id) => Some(*id),
+            None => self.id_of(node.wrapped()?),
+        }
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.parents.get(id.index()).cloned().and_then(|p| p)
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        self.children
+            .get(id.index())
+            .map(|c| c.as_slice())
+            .unwrap_or(&[])
+    }
+    /// Every ancestor of a node, innermost first
+    pub fn ancestors(&self, id: NodeId) -> Vec<NodeId> {
+        let mut ret = Vec::new();
+        let mut current = self.parent(id);
+        while let Some(id) = current {
+            ret.push(id);
+            current = self.parent(id);
+        }
+        ret
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &'ast dyn Node)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (NodeId(idx as u32), *node))
+    }
+}
+
+fn key(node: &dyn Node) -> (*const (), NodeKind) {
+    (node as *const dyn Node as *const (), node.kind())
+}
+
+/// A side table with an optional value for each node ID
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeMap<T> {
+    values: Vec<Option<T>>,
+}
+
+impl<T> Default for NodeMap<T> {
+    fn default() -> Self {
+        NodeMap { values: Vec::new() }
+    }
+}
+
+impl<T> NodeMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns the previous value for `id`, if any
+    pub fn insert(&mut self, id: NodeId, value: T) -> Option<T> {
+        if self.values.len() <= id.index() {
+            self.values.resize_with(id.index() + 1, || None);
+        }
+        self.values[id.index()].replace(value)
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.values.get(id.index()).and_then(|v| v.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.values.get_mut(id.index()).and_then(|v| v.as_mut())
+    }
+
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        self.values.get_mut(id.index()).and_then(|v| v.take())
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, v)| v.as_ref().map(|v| (NodeId(idx as u32), v)))
+    }
+}
+
+impl<T> Index<NodeId> for NodeMap<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &T {
+        self.get(id).expect("no value for node id")
+    }
+}
+
+impl<T> IndexMut<NodeId> for NodeMap<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut T {
+        self.get_mut(id).expect("no value for node id")
+    }
+}
@@ -0,0 +1,192 @@
+//! Borrowed wrappers that can point at any node in a tree.
+//!
+//! `NodeRef` and `NodeMut` have one variant for every type that
+//! implements `Node`, so a heterogeneous collection of nodes can be
+//! stored and matched on later. `Function` and `Class` are reached
+//! through the `Expression`, `Declaration` and `MethodDefinition`
+//! nodes that hold them.
+use crate::expression::{self, *};
+use crate::module::*;
+use crate::node::{Node, NodeKind, SourceLocation};
+use crate::statement::*;
+use crate::{
+    Array, Assignment, ClassBody, Directive, MethodDefinition, Object, Pattern, Program,
+    RestElement,
+};
+
+macro_rules! node_refs {
+    ($($variant:ident($ty:ty),)*) => {
+        #[derive(Debug, Clone, Copy)]
+        pub enum NodeRef<'a> {
+            $($variant(&'a $ty),)*
+        }
+
+        #[derive(Debug)]
+        pub enum NodeMut<'a> {
+            $($variant(&'a mut $ty),)*
+        }
+
+        $(
+            impl<'a> From<&'a $ty> for NodeRef<'a> {
+                fn from(node: &'a $ty) -> Self {
+                    NodeRef::$variant(node)
+                }
+            }
+
+            impl<'a> From<&'a mut $ty> for NodeMut<'a> {
+                fn from(node: &'a mut $ty) -> Self {
+                    NodeMut::$variant(node)
+                }
+            }
+        )*
+
+        impl<'a> NodeRef<'a> {
+            /// View the node as a trait object
+            pub fn as_node(&self) -> &'a dyn Node {
+                match *self {
+                    $(NodeRef::$variant(node) => node,)*
+                }
+            }
+            /// The address of the wrapped node, for identity checks
+            pub(crate) fn addr(&self) -> *const () {
+                match *self {
+                    $(NodeRef::$variant(node) => node as *const $ty as *const (),)*
+                }
+            }
+        }
+
+        impl<'a> NodeMut<'a> {
+            pub fn as_ref(&self) -> NodeRef<'_> {
+                match *self {
+                    $(NodeMut::$variant(ref node) => NodeRef::$variant(node),)*
+                }
+            }
+            /// Give up mutable access, keeping the original lifetime
+            pub fn into_ref(self) -> NodeRef<'a> {
+                match self {
+                    $(NodeMut::$variant(node) => NodeRef::$variant(node),)*
+                }
+            }
+        }
+    };
+}
+
+node_refs! {
+    Program(Program),
+    Directive(Directive),
+    Pattern(Pattern),
+    ObjectPattern(Object),
+    ArrayPattern(Array),
+    RestElement(RestElement),
+    AssignmentPattern(Assignment),
+    ClassBody(ClassBody),
+    MethodDefinition(MethodDefinition),
+    Expression(Expression),
+    Identifier(Identifier),
+    Literal(Literal),
+    RegExpLiteral(RegExpLiteral),
+    RegEx(RegEx),
+    This(This),
+    Super(Super),
+    ArrayExpression(expression::Array),
+    ObjectExpression(expression::Object),
+    Property(Property),
+    Unary(Unary),
+    Update(Update),
+    Binary(Binary),
+    AssignmentExpression(expression::Assignment),
+    Logical(Logical),
+    Member(Member),
+    Conditional(Conditional),
+    Call(Call),
+    New(New),
+    Sequence(Sequence),
+    SpreadElement(SpreadElement),
+    ArrowFunction(ArrowFunction),
+    Yield(Yield),
+    Await(Await),
+    Chain(Chain),
+    TemplateLiteral(TemplateLiteral),
+    TaggedTemplate(TaggedTemplate),
+    TemplateElement(TemplateElement),
+    MetaProperty(MetaProperty),
+    Statement(Statement),
+    Block(Block),
+    FunctionBody(FunctionBody),
+    Empty(Empty),
+    Debugger(Debugger),
+    With(With),
+    Return(Return),
+    Labeled(Labeled),
+    Break(Break),
+    Continue(Continue),
+    If(If),
+    Switch(Switch),
+    SwitchCase(SwitchCase),
+    Throw(Throw),
+    Try(Try),
+    CatchClause(CatchClause),
+    While(While),
+    DoWhile(DoWhile),
+    For(For),
+    ForIn(ForIn),
+    ForOf(ForOf),
+    Declaration(Declaration),
+    VariableDeclaration(VariableDeclaration),
+    VariableDeclarator(VariableDeclarator),
+    ModuleDeclaration(ModuleDeclaration),
+    ImportDeclaration(ImportDeclaration),
+    Import(Import),
+    ImportDefault(ImportDefault),
+    ImportNamespace(ImportNamespace),
+    ExportDeclaration(ExportDeclaration),
+    ExportNamed(ExportNamed),
+    ExportSpecifier(ExportSpecifier),
+    ExportDefault(ExportDefault),
+    ExportAll(ExportAll),
+}
+
+impl<'a> NodeRef<'a> {
+    /// If both refer to the very same node in memory
+    pub fn ptr_eq(&self, other: &NodeRef) -> bool {
+        self.addr() == other.addr()
+            && ::std::mem::discriminant(self) == ::std::mem::discriminant(other)
+    }
+}
+
+impl<'a> Node for NodeRef<'a> {
+    fn loc(&self) -> SourceLocation {
+        self.as_node().loc()
+    }
+
+    fn kind(&self) -> NodeKind {
+        self.as_node().kind()
+    }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        Some(self.as_node())
+    }
+
+    fn for_each_child<'b>(&'b self, f: &mut dyn FnMut(&'b dyn Node)) {
+        let node: &'b dyn Node = self.as_node();
+        node.for_each_child(f)
+    }
+}
+
+impl<'a> Node for NodeMut<'a> {
+    fn loc(&self) -> SourceLocation {
+        self.as_ref().loc()
+    }
+
+    fn kind(&self) -> NodeKind {
+        self.as_ref().kind()
+    }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        Some(self.as_ref().as_node())
+    }
+
+    fn for_each_child<'b>(&'b self, f: &mut dyn FnMut(&'b dyn Node)) {
+        self.as_ref().as_node().for_each_child(f)
+    }
+}
@@ -0,0 +1,667 @@
+//! Paths to the statements, expressions and patterns of a `Program`,
+//! in the spirit of Babel's `NodePath`.
+//!
+//! `Paths::new` walks a program once and records a `StatementPath`
+//! for every item of a statement list (the body of a program,
+//! function, block or switch case) and for every statement that
+//! fills a single statement slot, like the body of a loop. Each path
+//! knows its node, its parent, its siblings and the path of the
+//! statement it is nested in. Every `Expression` and `Pattern` gets a
+//! `NodePath` with its parent and enclosing statement.
+//!
+//! Because the paths borrow the tree, changes are queued up in an
+//! `Edits` and applied in one pass afterwards. Applying keeps the
+//! tree valid: a slot left with no statements gets an `Empty` one
+//! and a slot given more than one is wrapped in a `Block`. Every
+//! edit remembers the kind and location of the node it was queued
+//! for, and `apply` changes nothing if one of them is gone.
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::mem;
+use std::ops::ControlFlow;
+
+use crate::expression::Expression;
+use crate::node::{Node, NodeKind, SourceLocation};
+use crate::node_ref::NodeRef;
+use crate::statement::{Block, Empty, FunctionBody, FunctionBodyPart, Statement, SwitchCase};
+use crate::visit::{self, Visit};
+use crate::visit_mut::{self, VisitMut};
+use crate::{Pattern, Program, ProgramPart};
+
+/// Where a statement lives: the pre-order number of its container
+/// and its index inside of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Address {
+    container: usize,
+    index: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Container<'ast> {
+    Program(&'ast [ProgramPart]),
+    FunctionBody(&'ast [FunctionBodyPart]),
+    Statements(&'ast [Statement]),
+    Slot(&'ast Statement),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StatementPath<'ast> {
+    node: NodeRef<'ast>,
+    parent: NodeRef<'ast>,
+    parent_path: Option<usize>,
+    container: Container<'ast>,
+    address: Address,
+}
+
+impl<'ast> StatementPath<'ast> {
+    /// The `Statement`, `Directive` or `ModuleDeclaration` at this path
+    pub fn node(&self) -> NodeRef<'ast> {
+        self.node
+    }
+    /// The node holding this one, a `Program`, `FunctionBody`,
+    /// `Block` or `SwitchCase` for list items and the owning
+    /// statement for a slot
+    pub fn parent(&self) -> NodeRef<'ast> {
+        self.parent
+    }
+    /// The index of this path's node in its container
+    pub fn index(&self) -> usize {
+        self.address.index
+    }
+    /// If this node sits in a single statement slot instead of a list
+    pub fn is_slot(&self) -> bool {
+        matches!(self.container, Container::Slot(_))
+    }
+    /// Every node in this path's container, including itself
+    pub fn siblings(&self) -> Vec<NodeRef<'ast>> {
+        match self.container {
+            Container::Program(parts) => parts.iter().map(program_part).collect(),
+            Container::FunctionBody(parts) => parts.iter().map(function_body_part).collect(),
+            Container::Statements(stmts) => stmts.iter().map(NodeRef::Statement).collect(),
+            Container::Slot(stmt) => vec![NodeRef::Statement(stmt)],
+        }
+    }
+}
+
+/// The path to an `Expression` or a `Pattern`
+#[derive(Debug)]
+pub struct NodePath<'ast, T> {
+    node: &'ast T,
+    parent: NodeRef<'ast>,
+    statement: Option<usize>,
+    index: usize,
+}
+
+pub type ExpressionPath<'ast> = NodePath<'ast, Expression>;
+pub type PatternPath<'ast> = NodePath<'ast, Pattern>;
+
+impl<'ast, T> Clone for NodePath<'ast, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'ast, T> Copy for NodePath<'ast, T> {}
+
+impl<'ast, T> NodePath<'ast, T> {
+    pub fn node(&self) -> &'ast T {
+        self.node
+    }
+    /// The closest expression, pattern or statement holding this
+    /// node, or the `Program` for the expressions of a directive or
+    /// module declaration at the top level
+    pub fn parent(&self) -> NodeRef<'ast> {
+        self.parent
+    }
+}
+
+/// All of the paths in a program, in source order
+#[derive(Debug, Clone)]
+pub struct Paths<'ast> {
+    paths: Vec<StatementPath<'ast>>,
+    expressions: Vec<ExpressionPath<'ast>>,
+    patterns: Vec<PatternPath<'ast>>,
+}
+
+impl<'ast> Paths<'ast> {
+    pub fn new(program: &'ast Program) -> Self {
+        let mut collect = Collect {
+            program,
+            paths: Vec::new(),
+            expressions: Vec::new(),
+            patterns: Vec::new(),
+            containers: 0,
+            current: None,
+            enclosing: NodeRef::Program(program),
+        };
+        let _ = collect.visit_program(program);
+        Paths {
+            paths: collect.paths,
+            expressions: collect.expressions,
+            patterns: collect.patterns,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&StatementPath<'ast>> {
+        self.paths.get(idx)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatementPath<'ast>> {
+        self.paths.iter()
+    }
+    /// The path of the statement `path` is nested in, `None` for
+    /// the top level of the program
+    pub fn parent_path(&self, path: &StatementPath<'ast>) -> Option<&StatementPath<'ast>> {
+        path.parent_path.and_then(|idx| self.paths.get(idx))
+    }
+    /// Every enclosing statement path, innermost first
+    pub fn ancestors(&self, path: &StatementPath<'ast>) -> Vec<&StatementPath<'ast>> {
+        let mut ret = Vec::new();
+        let mut current = self.parent_path(path);
+        while let Some(path) = current {
+            ret.push(path);
+            current = self.parent_path(path);
+        }
+        ret
+    }
+    /// The path for a node, if it is one of the statements in this program
+    pub fn find(&self, node: NodeRef) -> Option<&StatementPath<'ast>> {
+        self.paths.iter().find(|path| path.node.ptr_eq(&node))
+    }
+
+    pub fn expressions(&self) -> &[ExpressionPath<'ast>] {
+        &self.expressions
+    }
+
+    pub fn patterns(&self) -> &[PatternPath<'ast>] {
+        &self.patterns
+    }
+    /// The path of the statement an expression or pattern is part
+    /// of, `None` outside of any statement
+    pub fn statement_of<T>(&self, path: &NodePath<'ast, T>) -> Option<&StatementPath<'ast>> {
+        path.statement.and_then(|idx| self.paths.get(idx))
+    }
+}
+
+fn program_part(part: &ProgramPart) -> NodeRef<'_> {
+    match part {
+        ProgramPart::Directive(ref d) => NodeRef::Directive(d),
+        ProgramPart::Statement(ref s) => NodeRef::Statement(s),
+        ProgramPart::ModuleDecl(ref m) => NodeRef::ModuleDeclaration(m),
+    }
+}
+
+fn function_body_part(part: &FunctionBodyPart) -> NodeRef<'_> {
+    match part {
+        FunctionBodyPart::Directive(ref d) => NodeRef::Directive(d),
+        FunctionBodyPart::Statement(ref s) => NodeRef::Statement(s),
+    }
+}
+
+// Both passes number containers the same way: a list when it is
+// entered and a slot when its statement is visited. List items are
+// walked directly, so `visit_statement` is only reached for slots.
+struct Collect<'ast> {
+    program: &'ast Program,
+    paths: Vec<StatementPath<'ast>>,
+    expressions: Vec<ExpressionPath<'ast>>,
+    patterns: Vec<PatternPath<'ast>>,
+    containers: usize,
+    current: Option<usize>,
+    // the parent of the next expression or pattern
+    enclosing: NodeRef<'ast>,
+}
+
+impl<'ast> Collect<'ast> {
+    fn enter(&mut self) -> usize {
+        self.containers += 1;
+        self.containers - 1
+    }
+
+    fn item(
+        &mut self,
+        node: NodeRef<'ast>,
+        parent: NodeRef<'ast>,
+        container: Container<'ast>,
+        address: Address,
+        walk: impl FnOnce(&mut Self) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.paths.push(StatementPath {
+            node,
+            parent,
+            parent_path: self.current,
+            container,
+            address,
+        });
+        let prev = self.current.replace(self.paths.len() - 1);
+        let enclosing = mem::replace(&mut self.enclosing, node);
+        let ret = walk(self);
+        self.current = prev;
+        self.enclosing = enclosing;
+        ret
+    }
+
+    fn node_path<T>(&self, node: &'ast T, index: usize) -> NodePath<'ast, T> {
+        NodePath {
+            node,
+            parent: self.enclosing,
+            statement: self.current,
+            index,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Collect<'ast> {
+    fn visit_program(&mut self, program: &'ast Program) -> ControlFlow<()> {
+        let container = self.enter();
+        for (index, part) in program.body.iter().enumerate() {
+            self.item(
+                program_part(part),
+                NodeRef::Program(program),
+                Container::Program(&program.body),
+                Address { container, index },
+                |this| match part {
+                    ProgramPart::Statement(ref s) => visit::walk_statement(this, s),
+                    _ => visit::walk_program_part(this, part),
+                },
+            )?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_function_body(&mut self, body: &'ast FunctionBody) -> ControlFlow<()> {
+        let container = self.enter();
+        for (index, part) in body.body.iter().enumerate() {
+            self.item(
+                function_body_part(part),
+                NodeRef::FunctionBody(body),
+                Container::FunctionBody(&body.body),
+                Address { container, index },
+                |this| match part {
+                    FunctionBodyPart::Statement(ref s) => visit::walk_statement(this, s),
+                    _ => visit::walk_function_body_part(this, part),
+                },
+            )?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) -> ControlFlow<()> {
+        let container = self.enter();
+        for (index, stmt) in block.body.iter().enumerate() {
+            self.item(
+                NodeRef::Statement(stmt),
+                NodeRef::Block(block),
+                Container::Statements(&block.body),
+                Address { container, index },
+                |this| visit::walk_statement(this, stmt),
+            )?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_switch_case(&mut self, case: &'ast SwitchCase) -> ControlFlow<()> {
+        let container = self.enter();
+        if let Some(ref test) = case.test {
+            self.visit_expression(test)?;
+        }
+        for (index, stmt) in case.consequent.iter().enumerate() {
+            self.item(
+                NodeRef::Statement(stmt),
+                NodeRef::SwitchCase(case),
+                Container::Statements(&case.consequent),
+                Address { container, index },
+                |this| visit::walk_statement(this, stmt),
+            )?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_statement(&mut self, stmt: &'ast Statement) -> ControlFlow<()> {
+        let container = self.enter();
+        let parent = match self.current {
+            Some(idx) => self.paths[idx].node,
+            None => NodeRef::Program(self.program),
+        };
+        self.item(
+            NodeRef::Statement(stmt),
+            parent,
+            Container::Slot(stmt),
+            Address {
+                container,
+                index: 0,
+            },
+            |this| visit::walk_statement(this, stmt),
+        )
+    }
+
+    fn visit_expression(&mut self, expr: &'ast Expression) -> ControlFlow<()> {
+        let path = self.node_path(expr, self.expressions.len());
+        self.expressions.push(path);
+        let enclosing = mem::replace(&mut self.enclosing, NodeRef::Expression(expr));
+        let ret = visit::walk_expression(self, expr);
+        self.enclosing = enclosing;
+        ret
+    }
+
+    fn visit_pattern(&mut self, pattern: &'ast Pattern) -> ControlFlow<()> {
+        let path = self.node_path(pattern, self.patterns.len());
+        self.patterns.push(path);
+        let enclosing = mem::replace(&mut self.enclosing, NodeRef::Pattern(pattern));
+        let ret = visit::walk_pattern(self, pattern);
+        self.enclosing = enclosing;
+        ret
+    }
+}
+
+/// What an edit expects to find at its place
+#[derive(Debug, Clone, Copy)]
+struct Expected {
+    kind: NodeKind,
+    loc: SourceLocation,
+}
+
+impl Expected {
+    fn new(node: NodeRef) -> Self {
+        Expected {
+            kind: node.kind(),
+            loc: node.loc(),
+        }
+    }
+
+    fn check(&self, node: NodeRef) -> Result<(), Error> {
+        let found = Expected::new(node);
+        if found.kind != self.kind || found.loc != self.loc {
+            return Err(Error::Mismatch {
+                expected: self.kind,
+                found: found.kind,
+                loc: found.loc,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Why `Edits::apply` left a program unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// A path's place doesn't exist in the program
+    Missing,
+    /// The node at a path's place isn't the one the path was taken
+    /// from, `found` at `loc` instead of `expected`
+    Mismatch {
+        expected: NodeKind,
+        found: NodeKind,
+        loc: SourceLocation,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Missing => write!(f, "edit for a node that isn't in the program"),
+            Error::Mismatch {
+                expected,
+                found,
+                loc,
+            } => write!(
+                f,
+                "edit for {:?} found {:?} at {}..{}",
+                expected, found, loc.range.start, loc.range.end
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[derive(Debug, Default)]
+struct Edit {
+    before: Vec<Statement>,
+    replacement: Option<Vec<Statement>>,
+    after: Vec<Statement>,
+}
+
+/// A set of changes to the statements of a program, built up from
+/// `StatementPath`s and applied all at once with `apply`.
+///
+/// Insertions around the same node are kept in the order they were
+/// added. Replacing or removing a node drops any earlier replacement.
+#[derive(Debug, Default)]
+pub struct Edits {
+    edits: BTreeMap<Address, (Expected, Edit)>,
+    expressions: BTreeMap<usize, (Expected, Expression)>,
+    patterns: BTreeMap<usize, (Expected, Pattern)>,
+}
+
+impl Edits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty() && self.expressions.is_empty() && self.patterns.is_empty()
+    }
+
+    pub fn replace(&mut self, path: &StatementPath, stmt: Statement) {
+        self.replace_many(path, vec![stmt]);
+    }
+
+    pub fn replace_many(&mut self, path: &StatementPath, stmts: Vec<Statement>) {
+        self.edit(path).replacement = Some(stmts);
+    }
+
+    pub fn remove(&mut self, path: &StatementPath) {
+        self.replace_many(path, Vec::new());
+    }
+
+    pub fn insert_before(&mut self, path: &StatementPath, stmt: Statement) {
+        self.edit(path).before.push(stmt);
+    }
+
+    pub fn insert_after(&mut self, path: &StatementPath, stmt: Statement) {
+        self.edit(path).after.push(stmt);
+    }
+
+    pub fn replace_expression(&mut self, path: &ExpressionPath, expr: Expression) {
+        let expected = Expected::new(NodeRef::Expression(path.node));
+        self.expressions.insert(path.index, (expected, expr));
+    }
+
+    pub fn replace_pattern(&mut self, path: &PatternPath, pattern: Pattern) {
+        let expected = Expected::new(NodeRef::Pattern(path.node));
+        self.patterns.insert(path.index, (expected, pattern));
+    }
+    /// Apply the edits to the program the paths were collected from.
+    ///
+    /// Before changing anything, every edit is checked against the
+    /// node now at its place. If the program has changed so that one
+    /// of them is missing, or has another kind or location, the
+    /// program is left as it is and the first mismatch is returned.
+    /// Replacing a node also replaces any edits made inside of it.
+    pub fn apply(self, program: &mut Program) -> Result<(), Error> {
+        self.check(&Paths::new(program))?;
+        let mut apply = Apply {
+            edits: checked(self.edits),
+            expression_edits: checked(self.expressions),
+            pattern_edits: checked(self.patterns),
+            containers: 0,
+            expressions: 0,
+            patterns: 0,
+        };
+        let _ = apply.visit_program(program);
+        Ok(())
+    }
+
+    fn check(&self, paths: &Paths) -> Result<(), Error> {
+        let mut found = 0;
+        for path in paths.iter() {
+            if let Some((expected, _)) = self.edits.get(&path.address) {
+                expected.check(path.node)?;
+                found += 1;
+            }
+        }
+        if found < self.edits.len() {
+            return Err(Error::Missing);
+        }
+        for (&index, (expected, _)) in &self.expressions {
+            let path = paths.expressions.get(index).ok_or(Error::Missing)?;
+            expected.check(NodeRef::Expression(path.node))?;
+        }
+        for (&index, (expected, _)) in &self.patterns {
+            let path = paths.patterns.get(index).ok_or(Error::Missing)?;
+            expected.check(NodeRef::Pattern(path.node))?;
+        }
+        Ok(())
+    }
+
+    fn edit(&mut self, path: &StatementPath) -> &mut Edit {
+        &mut self
+            .edits
+            .entry(path.address)
+            .or_insert_with(|| (Expected::new(path.node), Edit::default()))
+            .1
+    }
+}
+
+fn checked<K: Ord, T>(edits: BTreeMap<K, (Expected, T)>) -> BTreeMap<K, T> {
+    edits.into_iter().map(|(k, (_, v))| (k, v)).collect()
+}
+
+struct Apply {
+    edits: BTreeMap<Address, Edit>,
+    expression_edits: BTreeMap<usize, Expression>,
+    pattern_edits: BTreeMap<usize, Pattern>,
+    containers: usize,
+    expressions: usize,
+    patterns: usize,
+}
+
+impl Apply {
+    fn enter(&mut self) -> usize {
+        self.containers += 1;
+        self.containers - 1
+    }
+
+    fn splice<T>(&mut self, container: usize, items: &mut Vec<T>, wrap: fn(Statement) -> T) {
+        let start = Address {
+            container,
+            index: 0,
+        };
+        let end = Address {
+            container: container + 1,
+            index: 0,
+        };
+        if self.edits.range(start..end).next().is_none() {
+            return;
+        }
+        let old = mem::take(items);
+        for (index, item) in old.into_iter().enumerate() {
+            let edit = match self.edits.remove(&Address { container, index }) {
+                Some(edit) => edit,
+                None => {
+                    items.push(item);
+                    continue;
+                }
+            };
+            items.extend(edit.before.into_iter().map(wrap));
+            match edit.replacement {
+                Some(stmts) => items.extend(stmts.into_iter().map(wrap)),
+                None => items.push(item),
+            }
+            items.extend(edit.after.into_iter().map(wrap));
+        }
+    }
+}
+
+impl VisitMut for Apply {
+    fn visit_program(&mut self, program: &mut Program) -> ControlFlow<()> {
+        let container = self.enter();
+        for part in program.body.iter_mut() {
+            match part {
+                ProgramPart::Statement(ref mut s) => visit_mut::walk_statement(self, s)?,
+                _ => visit_mut::walk_program_part(self, part)?,
+            }
+        }
+        self.splice(container, &mut program.body, ProgramPart::Statement);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_function_body(&mut self, body: &mut FunctionBody) -> ControlFlow<()> {
+        let container = self.enter();
+        for part in body.body.iter_mut() {
+            match part {
+                FunctionBodyPart::Statement(ref mut s) => visit_mut::walk_statement(self, s)?,
+                _ => visit_mut::walk_function_body_part(self, part)?,
+            }
+        }
+        self.splice(container, &mut body.body, FunctionBodyPart::Statement);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_block(&mut self, block: &mut Block) -> ControlFlow<()> {
+        let container = self.enter();
+        for stmt in block.body.iter_mut() {
+            visit_mut::walk_statement(self, stmt)?;
+        }
+        self.splice(container, &mut block.body, |s| s);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_switch_case(&mut self, case: &mut SwitchCase) -> ControlFlow<()> {
+        let container = self.enter();
+        if let Some(ref mut test) = case.test {
+            self.visit_expression(test)?;
+        }
+        for stmt in case.consequent.iter_mut() {
+            visit_mut::walk_statement(self, stmt)?;
+        }
+        self.splice(container, &mut case.consequent, |s| s);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) -> ControlFlow<()> {
+        let container = self.enter();
+        visit_mut::walk_statement(self, stmt)?;
+        let loc = stmt.loc();
+        let mut stmts = vec![mem::replace(stmt, Statement::Empty(Empty { loc }))];
+        self.splice(container, &mut stmts, |s| s);
+        *stmt = match stmts.len() {
+            0 => Statement::Empty(Empty { loc }),
+            1 => stmts.remove(0),
+            _ => Statement::Block(Block { body: stmts, loc }),
+        };
+        ControlFlow::Continue(())
+    }
+    // Expressions and patterns are numbered before their children are
+    // walked, like in `Collect`, and replaced after, so a replacement
+    // doesn't shift the numbers of the nodes that follow it
+    fn visit_expression(&mut self, expr: &mut Expression) -> ControlFlow<()> {
+        let index = self.expressions;
+        self.expressions += 1;
+        visit_mut::walk_expression(self, expr)?;
+        if let Some(replacement) = self.expression_edits.remove(&index) {
+            *expr = replacement;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_pattern(&mut self, pattern: &mut Pattern) -> ControlFlow<()> {
+        let index = self.patterns;
+        self.patterns += 1;
+        visit_mut::walk_pattern(self, pattern)?;
+        if let Some(replacement) = self.pattern_edits.remove(&index) {
+            *pattern = replacement;
+        }
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,487 @@
+//! Source maps, version 3.
+//!
+//! A `SourceMap` holds its mappings decoded, in the order of their
+//! generated positions. Positions here follow the source map format
+//! rather than the rest of the crate: lines start at 1 like any
+//! `Position`, but columns count UTF-16 code units, not bytes. Use
+//! `LineIndex::utf16_column` and `LineIndex::position_from_utf16`
+//! to move between the two.
+//!
+//! Maps are made by `codegen::Printer::with_source_map` and written
+//! out with `to_json`. Existing maps are read with `from_json` (with
+//! the `serde` feature) or `decode_mappings`, and can be chained with
+//! `compose`. A `Remapper` moves whole `SourceLocation`s back through
+//! a map, so they can be written into the nodes of a parsed file.
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::{error, fmt};
+
+use crate::line_index::LineIndex;
+use crate::node::{FileId, Position, Range, SourceLocation, SourceRegistry};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The name of the generated file
+    pub file: Option<String>,
+    pub sources: Vec<String>,
+    /// The text of each of `sources`, where it is known
+    pub sources_content: Vec<Option<String>>,
+    pub names: Vec<String>,
+    pub mappings: Vec<Mapping>,
+}
+/// A position in the generated file, and where it came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Mapping {
+    pub generated: Position,
+    pub original: Option<Original>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Original {
+    /// An index into `sources`
+    pub source: u32,
+    pub position: Position,
+    /// An index into `names`, for the identifier written here
+    pub name: Option<u32>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+    /// The map isn't version 3
+    Version(u64),
+    /// A field that is missing or has the wrong type
+    Field(&'static str),
+    /// A character in the mappings that isn't base64
+    Base64(char),
+    /// A segment without 1, 4 or 5 values, or with a value that is
+    /// cut off or too large
+    Segment,
+    /// A segment pointing to a source or name that doesn't exist,
+    /// or to a negative line or column
+    Index,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "serde")]
+            Error::Json(ref e) => e.fmt(f),
+            Error::Version(version) => write!(f, "source map version {}, expected 3", version),
+            Error::Field(name) => write!(f, "missing or invalid field `{}`", name),
+            Error::Base64(c) => write!(f, "invalid base64 character {:?} in mappings", c),
+            Error::Segment => write!(f, "invalid segment in mappings"),
+            Error::Index => write!(f, "segment index out of range"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            #[cfg(feature = "serde")]
+            Error::Json(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl SourceMap {
+    /// Read a map from JSON. `sourceRoot` is joined onto each source.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<SourceMap, Error> {
+        use serde_json::Value;
+        let value: Value = serde_json::from_str(json).map_err(Error::Json)?;
+        let version = value
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or(Error::Field("version"))?;
+        if version != 3 {
+            return Err(Error::Version(version));
+        }
+        let list = |key: &'static str| match value.get(key) {
+            None | Some(Value::Null) => Ok(Vec::new()),
+            Some(Value::Array(items)) => Ok(items.iter().map(Value::as_str).collect()),
+            Some(_) => Err(Error::Field(key)),
+        };
+        let root = match value.get("sourceRoot").and_then(Value::as_str) {
+            Some(root) if !root.is_empty() && !root.ends_with('/') => format!("{}/", root),
+            root => root.unwrap_or("").to_string(),
+        };
+        let mut map = SourceMap {
+            file: value
+                .get("file")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            sources: list("sources")?
+                .into_iter()
+                .map(|source| source.map(|source| format!("{}{}", root, source)))
+                .collect::<Option<_>>()
+                .ok_or(Error::Field("sources"))?,
+            sources_content: list("sourcesContent")?
+                .into_iter()
+                .map(|content| content.map(str::to_string))
+                .collect(),
+            names: list("names")?
+                .into_iter()
+                .map(|name| name.map(str::to_string))
+                .collect::<Option<_>>()
+                .ok_or(Error::Field("names"))?,
+            mappings: Vec::new(),
+        };
+        let mappings = value
+            .get("mappings")
+            .and_then(Value::as_str)
+            .ok_or(Error::Field("mappings"))?;
+        map.mappings = decode_mappings(mappings)?;
+        let in_range = |original: &Original| {
+            (original.source as usize) < map.sources.len()
+                && original
+                    .name
+                    .is_none_or(|name| (name as usize) < map.names.len())
+        };
+        if !map
+            .mappings
+            .iter()
+            .filter_map(|m| m.original.as_ref())
+            .all(in_range)
+        {
+            return Err(Error::Index);
+        }
+        Ok(map)
+    }
+    /// The mapping that covers `generated`, the last one on its line
+    /// that starts at or before it
+    pub fn find(&self, generated: Position) -> Option<&Mapping> {
+        let idx = self.mappings.partition_point(|m| m.generated <= generated);
+        self.mappings[..idx]
+            .last()
+            .filter(|m| m.generated.line == generated.line)
+    }
+    /// Where the text at `generated` came from
+    pub fn lookup(&self, generated: Position) -> Option<Original> {
+        self.find(generated)?.original
+    }
+    /// Chain `self`, a map from a to b, with `next`, a map from b to
+    /// c, into one map from a to c. Every source in `next` is taken
+    /// to be b. Names come from `self` where it has one, otherwise
+    /// from `next`.
+    pub fn compose(&self, next: &SourceMap) -> SourceMap {
+        let mut map = SourceMap {
+            file: next.file.clone(),
+            sources: self.sources.clone(),
+            sources_content: self.sources_content.clone(),
+            names: self.names.clone(),
+            mappings: Vec::with_capacity(next.mappings.len()),
+        };
+        let mut names: HashMap<String, u32> = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.clone(), idx as u32))
+            .collect();
+        for mapping in &next.mappings {
+            let original = mapping.original.and_then(|middle| {
+                let mut original = self.lookup(middle.position)?;
+                if original.name.is_none() {
+                    let name = middle.name.and_then(|idx| next.names.get(idx as usize));
+                    original.name = name.map(|name| {
+                        let next_idx = map.names.len() as u32;
+                        *names.entry(name.clone()).or_insert_with(|| {
+                            map.names.push(name.clone());
+                            next_idx
+                        })
+                    });
+                }
+                Some(original)
+            });
+            map.mappings.push(Mapping {
+                generated: mapping.generated,
+                original,
+            });
+        }
+        map
+    }
+    /// The `mappings` field, as base64 VLQ segments
+    pub fn encode_mappings(&self) -> String {
+        let mut out = String::new();
+        let mut line = 1;
+        let mut column = 0;
+        let (mut source, mut original_line, mut original_column, mut name) = (0, 0, 0, 0);
+        for (idx, mapping) in self.mappings.iter().enumerate() {
+            let generated = mapping.generated;
+            if generated.line.get() != line {
+                while line < generated.line.get() {
+                    out.push(';');
+                    line += 1;
+                }
+                column = 0;
+            } else if idx > 0 {
+                out.push(',');
+            }
+            vlq(&mut out, generated.column as i64 - column);
+            column = generated.column as i64;
+            if let Some(original) = mapping.original {
+                vlq(&mut out, i64::from(original.source) - source);
+                source = i64::from(original.source);
+                // lines are written from 0
+                let position = original.position;
+                vlq(&mut out, position.line.get() as i64 - 1 - original_line);
+                original_line = position.line.get() as i64 - 1;
+                vlq(&mut out, position.column as i64 - original_column);
+                original_column = position.column as i64;
+                if let Some(idx) = original.name {
+                    vlq(&mut out, i64::from(idx) - name);
+                    name = i64::from(idx);
+                }
+            }
+        }
+        out
+    }
+    /// The map as JSON
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"version\":3");
+        if let Some(ref file) = self.file {
+            out.push_str(",\"file\":");
+            json_string(&mut out, file);
+        }
+        out.push_str(",\"sources\":[");
+        for (idx, source) in self.sources.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            json_string(&mut out, source);
+        }
+        out.push(']');
+        if self.sources_content.iter().any(Option::is_some) {
+            out.push_str(",\"sourcesContent\":[");
+            for (idx, content) in self.sources_content.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                match content {
+                    Some(ref content) => json_string(&mut out, content),
+                    None => out.push_str("null"),
+                }
+            }
+            out.push(']');
+        }
+        out.push_str(",\"names\":[");
+        for (idx, name) in self.names.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            json_string(&mut out, name);
+        }
+        out.push_str("],\"mappings\":\"");
+        out.push_str(&self.encode_mappings());
+        out.push_str("\"}");
+        out
+    }
+}
+
+/// Read the `mappings` field of a map, ordered by generated position
+pub fn decode_mappings(mappings: &str) -> Result<Vec<Mapping>, Error> {
+    let mut out = Vec::new();
+    // generated column, source, original line, original column, name
+    let mut state = [0i64; 5];
+    for (line, text) in mappings.split(';').enumerate() {
+        state[0] = 0;
+        for segment in text.split(',').filter(|segment| !segment.is_empty()) {
+            let mut chars = segment.chars();
+            let mut count = 0;
+            while chars.as_str() != "" {
+                if count == state.len() {
+                    return Err(Error::Segment);
+                }
+                state[count] = state[count]
+                    .checked_add(read_vlq(&mut chars)?)
+                    .ok_or(Error::Index)?;
+                count += 1;
+            }
+            if count == 2 || count == 3 {
+                return Err(Error::Segment);
+            }
+            if state[..count]
+                .iter()
+                .any(|&value| value < 0 || value > i64::from(u32::MAX))
+            {
+                return Err(Error::Index);
+            }
+            let original = if count > 1 {
+                Some(Original {
+                    source: state[1] as u32,
+                    position: Position {
+                        line: NonZeroU64::new(state[2] as u64 + 1).unwrap(),
+                        column: state[3] as u64,
+                    },
+                    name: if count == 5 {
+                        Some(state[4] as u32)
+                    } else {
+                        None
+                    },
+                })
+            } else {
+                None
+            };
+            out.push(Mapping {
+                generated: Position {
+                    line: NonZeroU64::new(line as u64 + 1).unwrap(),
+                    column: state[0] as u64,
+                },
+                original,
+            });
+        }
+    }
+    // segments are meant to be in order, but not every tool does so
+    out.sort_by_key(|mapping| mapping.generated);
+    Ok(out)
+}
+
+fn read_vlq(chars: &mut std::str::Chars) -> Result<i64, Error> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let c = chars.next().ok_or(Error::Segment)?;
+        let digit = BASE64
+            .iter()
+            .position(|&b| b as char == c)
+            .ok_or(Error::Base64(c))? as u64;
+        // only 4 bits of the 13th digit fit in 64
+        if shift > 60 || (shift == 60 && digit & 0b1_1111 > 0b1111) {
+            return Err(Error::Segment);
+        }
+        value |= (digit & 0b1_1111) << shift;
+        shift += 5;
+        if digit & 0b10_0000 == 0 {
+            break;
+        }
+    }
+    let magnitude = (value >> 1) as i64;
+    Ok(if value & 1 == 1 {
+        -magnitude
+    } else {
+        magnitude
+    })
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+/// Append `value` as a base64 VLQ, the sign in the lowest bit and
+/// then 5 bits per digit with the 6th set on all but the last
+fn vlq(out: &mut String, value: i64) {
+    let mut rest = if value < 0 {
+        ((-value as u64) << 1) | 1
+    } else {
+        (value as u64) << 1
+    };
+    loop {
+        let mut digit = rest & 0b1_1111;
+        rest >>= 5;
+        if rest > 0 {
+            digit |= 0b10_0000;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if rest == 0 {
+            break;
+        }
+    }
+}
+
+fn json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Moves `SourceLocation`s in a generated file back to where a
+/// `SourceMap` says they came from.
+///
+/// Both the generated file and the map's sources are looked up in the
+/// registry, the sources by name. Their text, from the registry or
+/// else the map's `sourcesContent`, is used to move between byte and
+/// UTF-16 columns and to fill in `range`. Without it columns are
+/// taken as they are and `range` is left empty.
+#[derive(Debug)]
+pub struct Remapper<'a> {
+    map: &'a SourceMap,
+    registry: &'a SourceRegistry,
+    lines: HashMap<FileId, Option<LineIndex>>,
+}
+
+impl<'a> Remapper<'a> {
+    pub fn new(map: &'a SourceMap, registry: &'a SourceRegistry) -> Self {
+        Remapper {
+            map,
+            registry,
+            lines: HashMap::new(),
+        }
+    }
+    /// Where `loc` came from, if the map covers its start and its
+    /// source is registered. Columns after the start of a mapping are
+    /// carried over, so a token that was copied as is maps exactly.
+    pub fn remap(&mut self, loc: &SourceLocation) -> Option<SourceLocation> {
+        let generated = loc.source?;
+        let (start, source) = self.original(generated, loc.start)?;
+        let end = self
+            .original(generated, loc.end)
+            .filter(|&(end, end_source)| end_source == source && end >= start)
+            .map_or(start, |(end, _)| end);
+        let file = self
+            .registry
+            .lookup(self.map.sources.get(source as usize)?)?;
+        let index = self.line_index(file, Some(source as usize));
+        let to_bytes = |position: Position| match index {
+            Some(index) => index.position_from_utf16(position.line, position.column),
+            None => Some(position),
+        };
+        let (start, end) = (to_bytes(start)?, to_bytes(end)?);
+        let range = match index {
+            Some(index) => Range::new(index.offset(start)?, index.offset(end)?),
+            None => Range::new(0, 0),
+        };
+        Some(SourceLocation {
+            source: Some(file),
+            start,
+            end,
+            range,
+        })
+    }
+    /// The original UTF-16 position and source index of `position`
+    /// in the generated file
+    fn original(&mut self, generated: FileId, position: Position) -> Option<(Position, u32)> {
+        let mut column = position.column;
+        if let Some(index) = self.line_index(generated, None) {
+            column = index.utf16_column(position)?;
+        }
+        let position = Position {
+            line: position.line,
+            column,
+        };
+        let mapping = self.map.find(position)?;
+        let mut original = mapping.original?;
+        original.position.column += column - mapping.generated.column;
+        Some((original.position, original.source))
+    }
+
+    fn line_index(&mut self, file: FileId, source: Option<usize>) -> Option<&LineIndex> {
+        let (registry, map) = (self.registry, self.map);
+        self.lines
+            .entry(file)
+            .or_insert_with(|| {
+                let content = source.and_then(|idx| map.sources_content.get(idx)?.as_deref());
+                registry.text(file).or(content).map(LineIndex::new)
+            })
+            .as_ref()
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
-    expression::Expression, Class, Directive, Function, Identifier, Node, NodeKind, Pattern,
-    SourceLocation,
+    expression::Expression, Children, Class, Directive, Function, Identifier, Node, NodeKind,
+    Pattern, SourceLocation, StableHasher, Structural, StructuralOptions,
 };
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum Statement {
     Expr(Expression),
     Block(Block),
@@ -22,6 +22,7 @@ pub enum Statement {
     DoWhile(Box<DoWhile>),
     For(Box<For>),
     ForIn(Box<ForIn>),
+    ForOf(Box<ForOf>),
     Decl(Declaration),
 }
 
@@ -46,6 +47,7 @@ impl Node for Statement {
             Statement::DoWhile(ref inner) => inner.loc(),
             Statement::For(ref inner) => inner.loc(),
             Statement::ForIn(ref inner) => inner.loc(),
+            Statement::ForOf(ref inner) => inner.loc(),
             Statement::Decl(ref inner) => inner.loc(),
         }
     }
@@ -70,143 +72,249 @@ impl Node for Statement {
             Statement::DoWhile(ref inner) => inner.kind(),
             Statement::For(ref inner) => inner.kind(),
             Statement::ForIn(ref inner) => inner.kind(),
+            Statement::ForOf(ref inner) => inner.kind(),
             Statement::Decl(ref inner) => inner.kind(),
         }
     }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        match self {
+            Statement::Expr(_) => None,
+            Statement::Block(ref inner) => Some(inner),
+            Statement::FuncBody(ref inner) => Some(inner),
+            Statement::Empty(ref inner) => Some(inner),
+            Statement::Debugger(ref inner) => Some(inner),
+            Statement::With(ref inner) => Some(&**inner),
+            Statement::Return(ref inner) => Some(inner),
+            Statement::Labeled(ref inner) => Some(&**inner),
+            Statement::Break(ref inner) => Some(inner),
+            Statement::Continue(ref inner) => Some(inner),
+            Statement::If(ref inner) => Some(&**inner),
+            Statement::Switch(ref inner) => Some(inner),
+            Statement::Throw(ref inner) => Some(inner),
+            Statement::Try(ref inner) => Some(inner),
+            Statement::While(ref inner) => Some(&**inner),
+            Statement::DoWhile(ref inner) => Some(&**inner),
+            Statement::For(ref inner) => Some(&**inner),
+            Statement::ForIn(ref inner) => Some(&**inner),
+            Statement::ForOf(ref inner) => Some(&**inner),
+            Statement::Decl(ref inner) => Some(inner),
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            Statement::Expr(ref inner) => f(inner),
+            Statement::Block(ref inner) => inner.for_each_child(f),
+            Statement::FuncBody(ref inner) => inner.for_each_child(f),
+            Statement::Empty(ref inner) => inner.for_each_child(f),
+            Statement::Debugger(ref inner) => inner.for_each_child(f),
+            Statement::With(ref inner) => inner.for_each_child(f),
+            Statement::Return(ref inner) => inner.for_each_child(f),
+            Statement::Labeled(ref inner) => inner.for_each_child(f),
+            Statement::Break(ref inner) => inner.for_each_child(f),
+            Statement::Continue(ref inner) => inner.for_each_child(f),
+            Statement::If(ref inner) => inner.for_each_child(f),
+            Statement::Switch(ref inner) => inner.for_each_child(f),
+            Statement::Throw(ref inner) => inner.for_each_child(f),
+            Statement::Try(ref inner) => inner.for_each_child(f),
+            Statement::While(ref inner) => inner.for_each_child(f),
+            Statement::DoWhile(ref inner) => inner.for_each_child(f),
+            Statement::For(ref inner) => inner.for_each_child(f),
+            Statement::ForIn(ref inner) => inner.for_each_child(f),
+            Statement::ForOf(ref inner) => inner.for_each_child(f),
+            Statement::Decl(ref inner) => inner.for_each_child(f),
+        }
+    }
+}
+
+impl Children for Statement {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Block {
     pub body: Vec<Statement>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct FunctionBody {
     pub body: Vec<FunctionBodyPart>,
     pub loc: SourceLocation,
 }
 impl Node for FunctionBody {
     fn loc(&self) -> SourceLocation {
-        self.loc.clone()
+        self.loc
     }
 
     fn kind(&self) -> NodeKind {
         NodeKind::BlockStatement
     }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        self.body.each_node(f)
+    }
 }
-#[derive(Debug)]
+
+impl Children for FunctionBody {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum FunctionBodyPart {
     Directive(Directive),
     Statement(Statement),
 }
+
+impl Children for FunctionBodyPart {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            FunctionBodyPart::Directive(ref inner) => inner.each_node(f),
+            FunctionBodyPart::Statement(ref inner) => inner.each_node(f),
+        }
+    }
+}
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Empty {}
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Debugger {}
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct With {
     pub object: Expression,
     pub body: Statement,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Return {
     pub argument: Option<Expression>,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Labeled {
+    #[structural(name)]
     pub label: Identifier,
     pub body: Statement,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Break {
+    #[structural(name)]
     pub label: Option<Identifier>,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Continue {
+    #[structural(name)]
     pub label: Option<Identifier>,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct If {
     pub test: Expression,
     pub consequent: Statement,
     pub alternate: Option<Statement>,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Switch {
     pub discriminant: Expression,
     pub cases: Vec<SwitchCase>,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct SwitchCase {
     pub test: Option<Expression>,
     pub consequent: Vec<Statement>,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Throw {
     pub argument: Expression,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct Try {
     pub block: Block,
     pub handler: Option<CatchClause>,
     pub finalizer: Option<Block>,
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct CatchClause {
     pub param: Pattern,
     pub body: Block,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct While {
     pub test: Expression,
     pub body: Statement,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct DoWhile {
     pub body: Statement,
     pub test: Expression,
 }
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct For {
     pub init: ForInit,
     pub test: Option<Expression>,
     pub update: Option<Expression>,
     pub body: Statement,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum ForInit {
     Var(VariableDeclaration),
     Expr(Expression),
     Null,
 }
+
+impl Children for ForInit {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ForInit::Var(ref inner) => inner.each_node(f),
+            ForInit::Expr(ref inner) => inner.each_node(f),
+            ForInit::Null => (),
+        }
+    }
+}
 #[inherit(Statement)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct ForIn {
     pub left: ForLeft,
     pub right: Expression,
     pub body: Statement,
 }
-#[derive(Debug)]
+#[inherit(Statement)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
+pub struct ForOf {
+    pub left: ForLeft,
+    pub right: Expression,
+    pub body: Statement,
+    pub _await: bool,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum ForLeft {
     Var(VariableDeclaration),
     Pat(Pattern),
 }
-#[derive(Debug)]
+
+impl Children for ForLeft {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            ForLeft::Var(ref inner) => inner.each_node(f),
+            ForLeft::Pat(ref inner) => inner.each_node(f),
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum Declaration {
     Function(Function),
     Variable(VariableDeclaration),
@@ -216,9 +324,9 @@ pub enum Declaration {
 impl Node for Declaration {
     fn loc(&self) -> SourceLocation {
         match self {
-            Declaration::Function(ref inner) => inner.loc.clone(),
+            Declaration::Function(ref inner) => inner.loc,
             Declaration::Variable(ref inner) => inner.loc(),
-            Declaration::Class(ref inner) => inner.loc.clone(),
+            Declaration::Class(ref inner) => inner.loc,
         }
     }
 
@@ -229,21 +337,60 @@ impl Node for Declaration {
             Declaration::Variable(ref inner) => inner.kind(),
         }
     }
+
+    fn wrapped(&self) -> Option<&dyn Node> {
+        match self {
+            Declaration::Function(_) => None,
+            Declaration::Variable(ref inner) => Some(inner),
+            Declaration::Class(_) => None,
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        match self {
+            Declaration::Function(ref inner) => inner.each_node(f),
+            Declaration::Variable(ref inner) => inner.for_each_child(f),
+            Declaration::Class(ref inner) => inner.each_node(f),
+        }
+    }
+}
+
+impl Children for Declaration {
+    fn each_node<'a>(&'a self, f: &mut dyn FnMut(&'a dyn Node)) {
+        f(self);
+    }
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct VariableDeclaration {
     pub declarations: Vec<VariableDeclarator>,
     pub kind: VariableKind,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub enum VariableKind {
     Var,
     Let,
     Const,
+    Using,
+    AwaitUsing,
+}
+
+impl Children for VariableKind {}
+
+impl VariableKind {
+    /// The ESTree name for this kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VariableKind::Var => "var",
+            VariableKind::Let => "let",
+            VariableKind::Const => "const",
+            VariableKind::Using => "using",
+            VariableKind::AwaitUsing => "await using",
+        }
+    }
 }
 #[inherit(Node)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Structural)]
 pub struct VariableDeclarator {
     pub id: Pattern,
     pub init: Option<Expression>,
@@ -0,0 +1,175 @@
+//! Comparing and hashing trees by their shape alone.
+//!
+//! Two nodes are structurally equal when they only differ by their
+//! `SourceLocation`s, and optionally by the names of their
+//! identifiers. `structural_hash` agrees with `structural_eq` and
+//! uses a fixed hash function, so it is the same from run to run and
+//! can be used as a cache key.
+use std::hash::Hasher;
+
+use crate::expression::Identifier;
+use crate::node::SourceLocation;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct StructuralOptions {
+    /// Treat identifiers that bind or refer to a variable as equal, no
+    /// matter their name. Labels, non-computed property keys and the
+    /// names a module imports or exports are still compared.
+    pub ignore_names: bool,
+}
+
+impl StructuralOptions {
+    /// These options for a name that isn't a binding or reference
+    pub(crate) fn keep_names(self) -> Self {
+        StructuralOptions {
+            ignore_names: false,
+        }
+    }
+}
+
+pub trait Structural {
+    fn structural_eq(&self, other: &Self, options: StructuralOptions) -> bool;
+    fn structural_hash(&self, hasher: &mut StableHasher, options: StructuralOptions);
+}
+
+/// Structural equality with the default options
+pub fn structural_eq<T: Structural + ?Sized>(a: &T, b: &T) -> bool {
+    a.structural_eq(b, StructuralOptions::default())
+}
+
+pub fn structural_hash<T: Structural + ?Sized>(node: &T, options: StructuralOptions) -> u64 {
+    let mut hasher = StableHasher::new();
+    node.structural_hash(&mut hasher, options);
+    hasher.finish()
+}
+
+/// 64 bit FNV-1a, which unlike the std `DefaultHasher` is
+/// guaranteed not to change between releases
+#[derive(Debug, Clone, Copy)]
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    pub fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    // the std impls use native endianness, these keep the hash
+    // the same on every platform
+    fn write_u32(&mut self, n: u32) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+}
+
+impl Structural for SourceLocation {
+    fn structural_eq(&self, _other: &Self, _options: StructuralOptions) -> bool {
+        true
+    }
+
+    fn structural_hash(&self, _hasher: &mut StableHasher, _options: StructuralOptions) {}
+}
+
+impl Structural for Identifier {
+    fn structural_eq(&self, other: &Self, options: StructuralOptions) -> bool {
+        options.ignore_names || self.name == other.name
+    }
+
+    fn structural_hash(&self, hasher: &mut StableHasher, options: StructuralOptions) {
+        if !options.ignore_names {
+            self.name.structural_hash(hasher, options);
+        }
+    }
+}
+
+impl Structural for bool {
+    fn structural_eq(&self, other: &Self, _options: StructuralOptions) -> bool {
+        self == other
+    }
+
+    fn structural_hash(&self, hasher: &mut StableHasher, _options: StructuralOptions) {
+        hasher.write_u8(*self as u8);
+    }
+}
+
+impl Structural for String {
+    fn structural_eq(&self, other: &Self, _options: StructuralOptions) -> bool {
+        self == other
+    }
+
+    fn structural_hash(&self, hasher: &mut StableHasher, _options: StructuralOptions) {
+        hasher.write_usize(self.len());
+        hasher.write(self.as_bytes());
+    }
+}
+
+impl<T: Structural> Structural for Option<T> {
+    fn structural_eq(&self, other: &Self, options: StructuralOptions) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.structural_eq(b, options),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn structural_hash(&self, hasher: &mut StableHasher, options: StructuralOptions) {
+        match self {
+            Some(inner) => {
+                hasher.write_u8(1);
+                inner.structural_hash(hasher, options);
+            }
+            None => hasher.write_u8(0),
+        }
+    }
+}
+
+impl<T: Structural> Structural for Vec<T> {
+    fn structural_eq(&self, other: &Self, options: StructuralOptions) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.structural_eq(b, options))
+    }
+
+    fn structural_hash(&self, hasher: &mut StableHasher, options: StructuralOptions) {
+        hasher.write_usize(self.len());
+        for item in self {
+            item.structural_hash(hasher, options);
+        }
+    }
+}
+
+impl<T: Structural + ?Sized> Structural for Box<T> {
+    fn structural_eq(&self, other: &Self, options: StructuralOptions) -> bool {
+        (**self).structural_eq(other, options)
+    }
+
+    fn structural_hash(&self, hasher: &mut StableHasher, options: StructuralOptions) {
+        (**self).structural_hash(hasher, options)
+    }
+}
@@ -0,0 +1,1105 @@
+//! A read-only visitor over a tree.
+//!
+//! Every node type has a `visit_*` method on `Visit` whose default
+//! implementation calls the matching `walk_*` function, which in
+//! turn visits each of that node's fields. Override a method to
+//! act on a node, calling the `walk_*` function from inside the
+//! override to keep descending into its children.
+//!
+//! Each method returns a `ControlFlow`. Returning `Continue`
+//! without calling the `walk_*` function skips the current node's
+//! children, while returning `Break` with a value stops the whole
+//! traversal and hands that value back to the caller.
+use std::ops::ControlFlow;
+
+use crate::expression::{
+    self, ArrowFunction, ArrowFunctionBody, Await, Binary, Call, Chain, Conditional, Expression,
+    Identifier, Literal, LiteralValue, Logical, Member, MetaProperty, New, Parent, PatOrExpr,
+    Property, Sequence, SpreadElement, Spreadable, Super, TaggedTemplate, TemplateElement,
+    TemplateLiteral, This, Unary, Update, Yield,
+};
+use crate::module::{
+    ExportAll, ExportDecl, ExportDeclaration, ExportDefault, ExportNamed, ExportSpecifier, Import,
+    ImportDeclaration, ImportDefault, ImportNamespace, ImportSpecifier, ModuleDeclaration,
+};
+use crate::statement::{
+    Block, Break, CatchClause, Continue, Debugger, Declaration, DoWhile, Empty, For, ForIn,
+    ForInit, ForLeft, ForOf, FunctionBody, FunctionBodyPart, If, Labeled, Return, Statement,
+    Switch, SwitchCase, Throw, Try, VariableDeclaration, VariableDeclarator, While, With,
+};
+use crate::{
+    Array, Assignment, AssignmentProperty, Class, ClassBody, Directive, Function, MethodDefinition,
+    Object, Pattern, Program, ProgramPart, RestElement,
+};
+
+pub trait Visit<'ast, B = ()> {
+    fn visit_program(&mut self, program: &'ast Program) -> ControlFlow<B> {
+        walk_program(self, program)
+    }
+    fn visit_program_part(&mut self, part: &'ast ProgramPart) -> ControlFlow<B> {
+        walk_program_part(self, part)
+    }
+    fn visit_directive(&mut self, directive: &'ast Directive) -> ControlFlow<B> {
+        walk_directive(self, directive)
+    }
+    fn visit_function(&mut self, function: &'ast Function) -> ControlFlow<B> {
+        walk_function(self, function)
+    }
+    fn visit_function_body(&mut self, body: &'ast FunctionBody) -> ControlFlow<B> {
+        walk_function_body(self, body)
+    }
+    fn visit_function_body_part(&mut self, part: &'ast FunctionBodyPart) -> ControlFlow<B> {
+        walk_function_body_part(self, part)
+    }
+    fn visit_class(&mut self, class: &'ast Class) -> ControlFlow<B> {
+        walk_class(self, class)
+    }
+    fn visit_class_body(&mut self, body: &'ast ClassBody) -> ControlFlow<B> {
+        walk_class_body(self, body)
+    }
+    fn visit_method_definition(&mut self, method: &'ast MethodDefinition) -> ControlFlow<B> {
+        walk_method_definition(self, method)
+    }
+    fn visit_pattern(&mut self, pattern: &'ast Pattern) -> ControlFlow<B> {
+        walk_pattern(self, pattern)
+    }
+    fn visit_object_pattern(&mut self, object: &'ast Object) -> ControlFlow<B> {
+        walk_object_pattern(self, object)
+    }
+    fn visit_assignment_property(&mut self, property: &'ast AssignmentProperty) -> ControlFlow<B> {
+        walk_assignment_property(self, property)
+    }
+    fn visit_array_pattern(&mut self, array: &'ast Array) -> ControlFlow<B> {
+        walk_array_pattern(self, array)
+    }
+    fn visit_rest_element(&mut self, rest: &'ast RestElement) -> ControlFlow<B> {
+        walk_rest_element(self, rest)
+    }
+    fn visit_assignment_pattern(&mut self, assignment: &'ast Assignment) -> ControlFlow<B> {
+        walk_assignment_pattern(self, assignment)
+    }
+    fn visit_expression(&mut self, expr: &'ast Expression) -> ControlFlow<B> {
+        walk_expression(self, expr)
+    }
+    fn visit_identifier(&mut self, _ident: &'ast Identifier) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_literal(&mut self, literal: &'ast Literal) -> ControlFlow<B> {
+        walk_literal(self, literal)
+    }
+    fn visit_this(&mut self, _this: &'ast This) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_super(&mut self, _sup: &'ast Super) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_array_expression(&mut self, array: &'ast expression::Array) -> ControlFlow<B> {
+        walk_array_expression(self, array)
+    }
+    fn visit_spreadable(&mut self, spreadable: &'ast Spreadable) -> ControlFlow<B> {
+        walk_spreadable(self, spreadable)
+    }
+    fn visit_spread_element(&mut self, spread: &'ast SpreadElement) -> ControlFlow<B> {
+        walk_spread_element(self, spread)
+    }
+    fn visit_object_expression(&mut self, object: &'ast expression::Object) -> ControlFlow<B> {
+        walk_object_expression(self, object)
+    }
+    fn visit_property(&mut self, property: &'ast Property) -> ControlFlow<B> {
+        walk_property(self, property)
+    }
+    fn visit_unary(&mut self, unary: &'ast Unary) -> ControlFlow<B> {
+        walk_unary(self, unary)
+    }
+    fn visit_update(&mut self, update: &'ast Update) -> ControlFlow<B> {
+        walk_update(self, update)
+    }
+    fn visit_binary(&mut self, binary: &'ast Binary) -> ControlFlow<B> {
+        walk_binary(self, binary)
+    }
+    fn visit_assignment_expression(
+        &mut self,
+        assignment: &'ast expression::Assignment,
+    ) -> ControlFlow<B> {
+        walk_assignment_expression(self, assignment)
+    }
+    fn visit_pat_or_expr(&mut self, pat_or_expr: &'ast PatOrExpr) -> ControlFlow<B> {
+        walk_pat_or_expr(self, pat_or_expr)
+    }
+    fn visit_logical(&mut self, logical: &'ast Logical) -> ControlFlow<B> {
+        walk_logical(self, logical)
+    }
+    fn visit_member(&mut self, member: &'ast Member) -> ControlFlow<B> {
+        walk_member(self, member)
+    }
+    fn visit_parent(&mut self, parent: &'ast Parent) -> ControlFlow<B> {
+        walk_parent(self, parent)
+    }
+    fn visit_conditional(&mut self, conditional: &'ast Conditional) -> ControlFlow<B> {
+        walk_conditional(self, conditional)
+    }
+    fn visit_call(&mut self, call: &'ast Call) -> ControlFlow<B> {
+        walk_call(self, call)
+    }
+    fn visit_new(&mut self, new: &'ast New) -> ControlFlow<B> {
+        walk_new(self, new)
+    }
+    fn visit_sequence(&mut self, sequence: &'ast Sequence) -> ControlFlow<B> {
+        walk_sequence(self, sequence)
+    }
+    fn visit_arrow_function(&mut self, arrow: &'ast ArrowFunction) -> ControlFlow<B> {
+        walk_arrow_function(self, arrow)
+    }
+    fn visit_arrow_function_body(&mut self, body: &'ast ArrowFunctionBody) -> ControlFlow<B> {
+        walk_arrow_function_body(self, body)
+    }
+    fn visit_yield(&mut self, expr: &'ast Yield) -> ControlFlow<B> {
+        walk_yield(self, expr)
+    }
+    fn visit_await(&mut self, expr: &'ast Await) -> ControlFlow<B> {
+        walk_await(self, expr)
+    }
+    fn visit_chain(&mut self, chain: &'ast Chain) -> ControlFlow<B> {
+        walk_chain(self, chain)
+    }
+    fn visit_template_literal(&mut self, template: &'ast TemplateLiteral) -> ControlFlow<B> {
+        walk_template_literal(self, template)
+    }
+    fn visit_template_element(&mut self, _element: &'ast TemplateElement) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_tagged_template(&mut self, tagged: &'ast TaggedTemplate) -> ControlFlow<B> {
+        walk_tagged_template(self, tagged)
+    }
+    fn visit_meta_property(&mut self, meta: &'ast MetaProperty) -> ControlFlow<B> {
+        walk_meta_property(self, meta)
+    }
+    fn visit_statement(&mut self, stmt: &'ast Statement) -> ControlFlow<B> {
+        walk_statement(self, stmt)
+    }
+    fn visit_block(&mut self, block: &'ast Block) -> ControlFlow<B> {
+        walk_block(self, block)
+    }
+    fn visit_empty(&mut self, _empty: &'ast Empty) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_debugger(&mut self, _debugger: &'ast Debugger) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_with(&mut self, with: &'ast With) -> ControlFlow<B> {
+        walk_with(self, with)
+    }
+    fn visit_return(&mut self, ret: &'ast Return) -> ControlFlow<B> {
+        walk_return(self, ret)
+    }
+    fn visit_labeled(&mut self, labeled: &'ast Labeled) -> ControlFlow<B> {
+        walk_labeled(self, labeled)
+    }
+    fn visit_break(&mut self, brk: &'ast Break) -> ControlFlow<B> {
+        walk_break(self, brk)
+    }
+    fn visit_continue(&mut self, cont: &'ast Continue) -> ControlFlow<B> {
+        walk_continue(self, cont)
+    }
+    fn visit_if(&mut self, stmt: &'ast If) -> ControlFlow<B> {
+        walk_if(self, stmt)
+    }
+    fn visit_switch(&mut self, switch: &'ast Switch) -> ControlFlow<B> {
+        walk_switch(self, switch)
+    }
+    fn visit_switch_case(&mut self, case: &'ast SwitchCase) -> ControlFlow<B> {
+        walk_switch_case(self, case)
+    }
+    fn visit_throw(&mut self, throw: &'ast Throw) -> ControlFlow<B> {
+        walk_throw(self, throw)
+    }
+    fn visit_try(&mut self, stmt: &'ast Try) -> ControlFlow<B> {
+        walk_try(self, stmt)
+    }
+    fn visit_catch_clause(&mut self, clause: &'ast CatchClause) -> ControlFlow<B> {
+        walk_catch_clause(self, clause)
+    }
+    fn visit_while(&mut self, stmt: &'ast While) -> ControlFlow<B> {
+        walk_while(self, stmt)
+    }
+    fn visit_do_while(&mut self, stmt: &'ast DoWhile) -> ControlFlow<B> {
+        walk_do_while(self, stmt)
+    }
+    fn visit_for(&mut self, stmt: &'ast For) -> ControlFlow<B> {
+        walk_for(self, stmt)
+    }
+    fn visit_for_init(&mut self, init: &'ast ForInit) -> ControlFlow<B> {
+        walk_for_init(self, init)
+    }
+    fn visit_for_in(&mut self, stmt: &'ast ForIn) -> ControlFlow<B> {
+        walk_for_in(self, stmt)
+    }
+    fn visit_for_of(&mut self, stmt: &'ast ForOf) -> ControlFlow<B> {
+        walk_for_of(self, stmt)
+    }
+    fn visit_for_left(&mut self, left: &'ast ForLeft) -> ControlFlow<B> {
+        walk_for_left(self, left)
+    }
+    fn visit_declaration(&mut self, decl: &'ast Declaration) -> ControlFlow<B> {
+        walk_declaration(self, decl)
+    }
+    fn visit_variable_declaration(&mut self, decl: &'ast VariableDeclaration) -> ControlFlow<B> {
+        walk_variable_declaration(self, decl)
+    }
+    fn visit_variable_declarator(&mut self, decl: &'ast VariableDeclarator) -> ControlFlow<B> {
+        walk_variable_declarator(self, decl)
+    }
+    fn visit_module_declaration(&mut self, decl: &'ast ModuleDeclaration) -> ControlFlow<B> {
+        walk_module_declaration(self, decl)
+    }
+    fn visit_import_declaration(&mut self, decl: &'ast ImportDeclaration) -> ControlFlow<B> {
+        walk_import_declaration(self, decl)
+    }
+    fn visit_import_specifier(&mut self, spec: &'ast ImportSpecifier) -> ControlFlow<B> {
+        walk_import_specifier(self, spec)
+    }
+    fn visit_import(&mut self, import: &'ast Import) -> ControlFlow<B> {
+        walk_import(self, import)
+    }
+    fn visit_import_default(&mut self, import: &'ast ImportDefault) -> ControlFlow<B> {
+        walk_import_default(self, import)
+    }
+    fn visit_import_namespace(&mut self, import: &'ast ImportNamespace) -> ControlFlow<B> {
+        walk_import_namespace(self, import)
+    }
+    fn visit_export_declaration(&mut self, decl: &'ast ExportDeclaration) -> ControlFlow<B> {
+        walk_export_declaration(self, decl)
+    }
+    fn visit_export_named(&mut self, export: &'ast ExportNamed) -> ControlFlow<B> {
+        walk_export_named(self, export)
+    }
+    fn visit_export_specifier(&mut self, spec: &'ast ExportSpecifier) -> ControlFlow<B> {
+        walk_export_specifier(self, spec)
+    }
+    fn visit_export_default(&mut self, export: &'ast ExportDefault) -> ControlFlow<B> {
+        walk_export_default(self, export)
+    }
+    fn visit_export_decl(&mut self, decl: &'ast ExportDecl) -> ControlFlow<B> {
+        walk_export_decl(self, decl)
+    }
+    fn visit_export_all(&mut self, export: &'ast ExportAll) -> ControlFlow<B> {
+        walk_export_all(self, export)
+    }
+}
+
+pub fn walk_program<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    program: &'ast Program,
+) -> ControlFlow<B> {
+    for part in &program.body {
+        visitor.visit_program_part(part)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_program_part<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    part: &'ast ProgramPart,
+) -> ControlFlow<B> {
+    match part {
+        ProgramPart::Directive(ref d) => visitor.visit_directive(d),
+        ProgramPart::Statement(ref s) => visitor.visit_statement(s),
+        ProgramPart::ModuleDecl(ref m) => visitor.visit_module_declaration(m),
+    }
+}
+
+pub fn walk_directive<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    directive: &'ast Directive,
+) -> ControlFlow<B> {
+    visitor.visit_literal(&directive.expression)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_function<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    function: &'ast Function,
+) -> ControlFlow<B> {
+    if let Some(ref id) = function.id {
+        visitor.visit_identifier(id)?;
+    }
+    for param in &function.params {
+        visitor.visit_pattern(param)?;
+    }
+    visitor.visit_function_body(&function.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_function_body<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    body: &'ast FunctionBody,
+) -> ControlFlow<B> {
+    for part in &body.body {
+        visitor.visit_function_body_part(part)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_function_body_part<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    part: &'ast FunctionBodyPart,
+) -> ControlFlow<B> {
+    match part {
+        FunctionBodyPart::Directive(ref d) => visitor.visit_directive(d),
+        FunctionBodyPart::Statement(ref s) => visitor.visit_statement(s),
+    }
+}
+
+pub fn walk_class<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    class: &'ast Class,
+) -> ControlFlow<B> {
+    if let Some(ref id) = class.id {
+        visitor.visit_identifier(id)?;
+    }
+    if let Some(ref super_class) = class.super_class {
+        visitor.visit_expression(super_class)?;
+    }
+    visitor.visit_class_body(&class.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_class_body<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    body: &'ast ClassBody,
+) -> ControlFlow<B> {
+    for method in &body.body {
+        visitor.visit_method_definition(method)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_method_definition<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    method: &'ast MethodDefinition,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&method.key)?;
+    visitor.visit_function(&method.value)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_pattern<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    pattern: &'ast Pattern,
+) -> ControlFlow<B> {
+    match pattern {
+        Pattern::Ident(ref i) => visitor.visit_identifier(i),
+        Pattern::Object(ref o) => visitor.visit_object_pattern(o),
+        Pattern::Array(ref a) => visitor.visit_array_pattern(a),
+        Pattern::RestElement(ref r) => visitor.visit_rest_element(r),
+        Pattern::Assignment(ref a) => visitor.visit_assignment_pattern(a),
+    }
+}
+
+pub fn walk_object_pattern<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    object: &'ast Object,
+) -> ControlFlow<B> {
+    for property in &object.properties {
+        visitor.visit_assignment_property(property)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_assignment_property<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    property: &'ast AssignmentProperty,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&property.key)?;
+    visitor.visit_pattern(&property.value)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_array_pattern<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    array: &'ast Array,
+) -> ControlFlow<B> {
+    for element in array.elements.iter().flatten() {
+        visitor.visit_pattern(element)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_rest_element<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    rest: &'ast RestElement,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&rest.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_assignment_pattern<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    assignment: &'ast Assignment,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&assignment.left)?;
+    visitor.visit_expression(&assignment.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_expression<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    expr: &'ast Expression,
+) -> ControlFlow<B> {
+    match expr {
+        Expression::This(ref inner) => visitor.visit_this(inner),
+        Expression::Array(ref inner) => visitor.visit_array_expression(inner),
+        Expression::Object(ref inner) => visitor.visit_object_expression(inner),
+        Expression::Function(ref inner) => visitor.visit_function(inner),
+        Expression::Unary(ref inner) => visitor.visit_unary(inner),
+        Expression::Update(ref inner) => visitor.visit_update(inner),
+        Expression::Binary(ref inner) => visitor.visit_binary(inner),
+        Expression::Assignment(ref inner) => visitor.visit_assignment_expression(inner),
+        Expression::Logical(ref inner) => visitor.visit_logical(inner),
+        Expression::Member(ref inner) => visitor.visit_member(inner),
+        Expression::Conditional(ref inner) => visitor.visit_conditional(inner),
+        Expression::Call(ref inner) => visitor.visit_call(inner),
+        Expression::New(ref inner) => visitor.visit_new(inner),
+        Expression::Sequence(ref inner) => visitor.visit_sequence(inner),
+        Expression::Spread(ref inner) => visitor.visit_spread_element(inner),
+        Expression::ArrowFunction(ref inner) => visitor.visit_arrow_function(inner),
+        Expression::Yield(ref inner) => visitor.visit_yield(inner),
+        Expression::Await(ref inner) => visitor.visit_await(inner),
+        Expression::Chain(ref inner) => visitor.visit_chain(inner),
+        Expression::Identifier(ref inner) => visitor.visit_identifier(inner),
+        Expression::Literal(ref inner) => visitor.visit_literal(inner),
+        Expression::TaggedTemplate(ref inner) => visitor.visit_tagged_template(inner),
+        Expression::Class(ref inner) => visitor.visit_class(inner),
+        Expression::Meta(ref inner) => visitor.visit_meta_property(inner),
+    }
+}
+
+pub fn walk_literal<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    literal: &'ast Literal,
+) -> ControlFlow<B> {
+    if let LiteralValue::TemplateLiteral(ref template) = literal.value {
+        visitor.visit_template_literal(template)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_array_expression<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    array: &'ast expression::Array,
+) -> ControlFlow<B> {
+    for element in array.elements.iter().flatten() {
+        visitor.visit_spreadable(element)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_spreadable<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    spreadable: &'ast Spreadable,
+) -> ControlFlow<B> {
+    match spreadable {
+        Spreadable::Expr(ref e) => visitor.visit_expression(e),
+        Spreadable::Spread(ref s) => visitor.visit_spread_element(s),
+    }
+}
+
+pub fn walk_spread_element<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    spread: &'ast SpreadElement,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&spread.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_object_expression<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    object: &'ast expression::Object,
+) -> ControlFlow<B> {
+    for property in &object.properties {
+        visitor.visit_property(property)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_property<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    property: &'ast Property,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&property.key)?;
+    visitor.visit_expression(&property.value)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_unary<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    unary: &'ast Unary,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&unary.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_update<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    update: &'ast Update,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&update.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_binary<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    binary: &'ast Binary,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&binary.left)?;
+    visitor.visit_expression(&binary.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_assignment_expression<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    assignment: &'ast expression::Assignment,
+) -> ControlFlow<B> {
+    visitor.visit_pat_or_expr(&assignment.left)?;
+    visitor.visit_expression(&assignment.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_pat_or_expr<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    pat_or_expr: &'ast PatOrExpr,
+) -> ControlFlow<B> {
+    match pat_or_expr {
+        PatOrExpr::Pat(ref p) => visitor.visit_pattern(p),
+        PatOrExpr::Expr(ref e) => visitor.visit_expression(e),
+    }
+}
+
+pub fn walk_logical<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    logical: &'ast Logical,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&logical.left)?;
+    visitor.visit_expression(&logical.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_member<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    member: &'ast Member,
+) -> ControlFlow<B> {
+    visitor.visit_parent(&member.object)?;
+    visitor.visit_expression(&member.property)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_parent<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    parent: &'ast Parent,
+) -> ControlFlow<B> {
+    match parent {
+        Parent::Expr(ref e) => visitor.visit_expression(e),
+        Parent::Super(ref s) => visitor.visit_super(s),
+    }
+}
+
+pub fn walk_conditional<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    conditional: &'ast Conditional,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&conditional.test)?;
+    visitor.visit_expression(&conditional.consequent)?;
+    visitor.visit_expression(&conditional.alternate)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_call<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    call: &'ast Call,
+) -> ControlFlow<B> {
+    visitor.visit_parent(&call.callee)?;
+    for arg in &call.arguments {
+        visitor.visit_spreadable(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_new<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    new: &'ast New,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&new.callee)?;
+    for arg in &new.arguments {
+        visitor.visit_spreadable(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_sequence<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    sequence: &'ast Sequence,
+) -> ControlFlow<B> {
+    for expr in &sequence.expressions {
+        visitor.visit_expression(expr)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_arrow_function<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    arrow: &'ast ArrowFunction,
+) -> ControlFlow<B> {
+    if let Some(ref id) = arrow.id {
+        visitor.visit_identifier(id)?;
+    }
+    for param in &arrow.params {
+        visitor.visit_pattern(param)?;
+    }
+    visitor.visit_arrow_function_body(&arrow.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_arrow_function_body<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    body: &'ast ArrowFunctionBody,
+) -> ControlFlow<B> {
+    match body {
+        ArrowFunctionBody::Expr(ref e) => visitor.visit_expression(e),
+        ArrowFunctionBody::Block(ref b) => visitor.visit_function_body(b),
+    }
+}
+
+pub fn walk_yield<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    expr: &'ast Yield,
+) -> ControlFlow<B> {
+    if let Some(ref arg) = expr.argument {
+        visitor.visit_expression(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_await<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    expr: &'ast Await,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&expr.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_chain<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    chain: &'ast Chain,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&chain.expression)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_template_literal<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    template: &'ast TemplateLiteral,
+) -> ControlFlow<B> {
+    for quasi in &template.quasis {
+        visitor.visit_template_element(quasi)?;
+    }
+    for expr in &template.expressions {
+        visitor.visit_expression(expr)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_tagged_template<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    tagged: &'ast TaggedTemplate,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&tagged.tag)?;
+    visitor.visit_template_literal(&tagged.quasi)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_meta_property<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    meta: &'ast MetaProperty,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&meta.meta)?;
+    visitor.visit_identifier(&meta.property)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_statement<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast Statement,
+) -> ControlFlow<B> {
+    match stmt {
+        Statement::Expr(ref inner) => visitor.visit_expression(inner),
+        Statement::Block(ref inner) => visitor.visit_block(inner),
+        Statement::FuncBody(ref inner) => visitor.visit_function_body(inner),
+        Statement::Empty(ref inner) => visitor.visit_empty(inner),
+        Statement::Debugger(ref inner) => visitor.visit_debugger(inner),
+        Statement::With(ref inner) => visitor.visit_with(inner),
+        Statement::Return(ref inner) => visitor.visit_return(inner),
+        Statement::Labeled(ref inner) => visitor.visit_labeled(inner),
+        Statement::Break(ref inner) => visitor.visit_break(inner),
+        Statement::Continue(ref inner) => visitor.visit_continue(inner),
+        Statement::If(ref inner) => visitor.visit_if(inner),
+        Statement::Switch(ref inner) => visitor.visit_switch(inner),
+        Statement::Throw(ref inner) => visitor.visit_throw(inner),
+        Statement::Try(ref inner) => visitor.visit_try(inner),
+        Statement::While(ref inner) => visitor.visit_while(inner),
+        Statement::DoWhile(ref inner) => visitor.visit_do_while(inner),
+        Statement::For(ref inner) => visitor.visit_for(inner),
+        Statement::ForIn(ref inner) => visitor.visit_for_in(inner),
+        Statement::ForOf(ref inner) => visitor.visit_for_of(inner),
+        Statement::Decl(ref inner) => visitor.visit_declaration(inner),
+    }
+}
+
+pub fn walk_block<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    block: &'ast Block,
+) -> ControlFlow<B> {
+    for stmt in &block.body {
+        visitor.visit_statement(stmt)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_with<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    with: &'ast With,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&with.object)?;
+    visitor.visit_statement(&with.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_return<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    ret: &'ast Return,
+) -> ControlFlow<B> {
+    if let Some(ref arg) = ret.argument {
+        visitor.visit_expression(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_labeled<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    labeled: &'ast Labeled,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&labeled.label)?;
+    visitor.visit_statement(&labeled.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_break<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    brk: &'ast Break,
+) -> ControlFlow<B> {
+    if let Some(ref label) = brk.label {
+        visitor.visit_identifier(label)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_continue<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    cont: &'ast Continue,
+) -> ControlFlow<B> {
+    if let Some(ref label) = cont.label {
+        visitor.visit_identifier(label)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_if<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast If,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&stmt.test)?;
+    visitor.visit_statement(&stmt.consequent)?;
+    if let Some(ref alt) = stmt.alternate {
+        visitor.visit_statement(alt)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_switch<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    switch: &'ast Switch,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&switch.discriminant)?;
+    for case in &switch.cases {
+        visitor.visit_switch_case(case)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_switch_case<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    case: &'ast SwitchCase,
+) -> ControlFlow<B> {
+    if let Some(ref test) = case.test {
+        visitor.visit_expression(test)?;
+    }
+    for stmt in &case.consequent {
+        visitor.visit_statement(stmt)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_throw<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    throw: &'ast Throw,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&throw.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_try<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast Try,
+) -> ControlFlow<B> {
+    visitor.visit_block(&stmt.block)?;
+    if let Some(ref handler) = stmt.handler {
+        visitor.visit_catch_clause(handler)?;
+    }
+    if let Some(ref finalizer) = stmt.finalizer {
+        visitor.visit_block(finalizer)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_catch_clause<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    clause: &'ast CatchClause,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&clause.param)?;
+    visitor.visit_block(&clause.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_while<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast While,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&stmt.test)?;
+    visitor.visit_statement(&stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_do_while<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast DoWhile,
+) -> ControlFlow<B> {
+    visitor.visit_statement(&stmt.body)?;
+    visitor.visit_expression(&stmt.test)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast For,
+) -> ControlFlow<B> {
+    visitor.visit_for_init(&stmt.init)?;
+    if let Some(ref test) = stmt.test {
+        visitor.visit_expression(test)?;
+    }
+    if let Some(ref update) = stmt.update {
+        visitor.visit_expression(update)?;
+    }
+    visitor.visit_statement(&stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for_init<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    init: &'ast ForInit,
+) -> ControlFlow<B> {
+    match init {
+        ForInit::Var(ref v) => visitor.visit_variable_declaration(v),
+        ForInit::Expr(ref e) => visitor.visit_expression(e),
+        ForInit::Null => ControlFlow::Continue(()),
+    }
+}
+
+pub fn walk_for_in<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast ForIn,
+) -> ControlFlow<B> {
+    visitor.visit_for_left(&stmt.left)?;
+    visitor.visit_expression(&stmt.right)?;
+    visitor.visit_statement(&stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for_of<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast ForOf,
+) -> ControlFlow<B> {
+    visitor.visit_for_left(&stmt.left)?;
+    visitor.visit_expression(&stmt.right)?;
+    visitor.visit_statement(&stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for_left<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    left: &'ast ForLeft,
+) -> ControlFlow<B> {
+    match left {
+        ForLeft::Var(ref v) => visitor.visit_variable_declaration(v),
+        ForLeft::Pat(ref p) => visitor.visit_pattern(p),
+    }
+}
+
+pub fn walk_declaration<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    decl: &'ast Declaration,
+) -> ControlFlow<B> {
+    match decl {
+        Declaration::Function(ref f) => visitor.visit_function(f),
+        Declaration::Variable(ref v) => visitor.visit_variable_declaration(v),
+        Declaration::Class(ref c) => visitor.visit_class(c),
+    }
+}
+
+pub fn walk_variable_declaration<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    decl: &'ast VariableDeclaration,
+) -> ControlFlow<B> {
+    for declarator in &decl.declarations {
+        visitor.visit_variable_declarator(declarator)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_variable_declarator<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    decl: &'ast VariableDeclarator,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&decl.id)?;
+    if let Some(ref init) = decl.init {
+        visitor.visit_expression(init)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_module_declaration<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    decl: &'ast ModuleDeclaration,
+) -> ControlFlow<B> {
+    match decl {
+        ModuleDeclaration::Import(ref i) => visitor.visit_import_declaration(i),
+        ModuleDeclaration::Export(ref e) => visitor.visit_export_declaration(e),
+    }
+}
+
+pub fn walk_import_declaration<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    decl: &'ast ImportDeclaration,
+) -> ControlFlow<B> {
+    for spec in &decl.specifiers {
+        visitor.visit_import_specifier(spec)?;
+    }
+    visitor.visit_literal(&decl.source)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_import_specifier<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    spec: &'ast ImportSpecifier,
+) -> ControlFlow<B> {
+    match spec {
+        ImportSpecifier::Named(ref n) => visitor.visit_import(n),
+        ImportSpecifier::Default(ref d) => visitor.visit_import_default(d),
+        ImportSpecifier::NameSpace(ref n) => visitor.visit_import_namespace(n),
+    }
+}
+
+pub fn walk_import<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    import: &'ast Import,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&import.import)?;
+    visitor.visit_identifier(&import.local)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_import_default<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    import: &'ast ImportDefault,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&import.local)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_import_namespace<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    import: &'ast ImportNamespace,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&import.local)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_declaration<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    decl: &'ast ExportDeclaration,
+) -> ControlFlow<B> {
+    match decl {
+        ExportDeclaration::Named(ref n) => visitor.visit_export_named(n),
+        ExportDeclaration::Default(ref d) => visitor.visit_export_default(d),
+        ExportDeclaration::All(ref a) => visitor.visit_export_all(a),
+    }
+}
+
+pub fn walk_export_named<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    export: &'ast ExportNamed,
+) -> ControlFlow<B> {
+    if let Some(ref decl) = export.declaration {
+        visitor.visit_declaration(decl)?;
+    }
+    for spec in &export.specifiers {
+        visitor.visit_export_specifier(spec)?;
+    }
+    if let Some(ref source) = export.source {
+        visitor.visit_literal(source)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_specifier<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    spec: &'ast ExportSpecifier,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&spec.local)?;
+    visitor.visit_identifier(&spec.exported)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_default<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    export: &'ast ExportDefault,
+) -> ControlFlow<B> {
+    visitor.visit_export_decl(&export.declaration)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_decl<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    decl: &'ast ExportDecl,
+) -> ControlFlow<B> {
+    match decl {
+        ExportDecl::Decl(ref d) => visitor.visit_declaration(d),
+        ExportDecl::Expr(ref e) => visitor.visit_expression(e),
+    }
+}
+
+pub fn walk_export_all<'ast, B, V: Visit<'ast, B> + ?Sized>(
+    visitor: &mut V,
+    export: &'ast ExportAll,
+) -> ControlFlow<B> {
+    visitor.visit_literal(&export.source)?;
+    ControlFlow::Continue(())
+}
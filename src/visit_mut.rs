@@ -0,0 +1,1070 @@
+//! A visitor that can rewrite a tree in place.
+//!
+//! Mirrors `visit::Visit`, with each method taking its node by
+//! `&mut` so identifiers, operators or literals can be changed
+//! as they are visited.
+//!
+//! Methods return a `ControlFlow`, read as described on `Visit`.
+use std::ops::ControlFlow;
+
+use crate::expression::{
+    self, ArrowFunction, ArrowFunctionBody, Await, Binary, Call, Chain, Conditional, Expression,
+    Identifier, Literal, LiteralValue, Logical, Member, MetaProperty, New, Parent, PatOrExpr,
+    Property, Sequence, SpreadElement, Spreadable, Super, TaggedTemplate, TemplateElement,
+    TemplateLiteral, This, Unary, Update, Yield,
+};
+use crate::module::{
+    ExportAll, ExportDecl, ExportDeclaration, ExportDefault, ExportNamed, ExportSpecifier, Import,
+    ImportDeclaration, ImportDefault, ImportNamespace, ImportSpecifier, ModuleDeclaration,
+};
+use crate::statement::{
+    Block, Break, CatchClause, Continue, Debugger, Declaration, DoWhile, Empty, For, ForIn,
+    ForInit, ForLeft, ForOf, FunctionBody, FunctionBodyPart, If, Labeled, Return, Statement,
+    Switch, SwitchCase, Throw, Try, VariableDeclaration, VariableDeclarator, While, With,
+};
+use crate::{
+    Array, Assignment, AssignmentProperty, Class, ClassBody, Directive, Function, MethodDefinition,
+    Object, Pattern, Program, ProgramPart, RestElement,
+};
+
+pub trait VisitMut<B = ()> {
+    fn visit_program(&mut self, program: &mut Program) -> ControlFlow<B> {
+        walk_program(self, program)
+    }
+    fn visit_program_part(&mut self, part: &mut ProgramPart) -> ControlFlow<B> {
+        walk_program_part(self, part)
+    }
+    fn visit_directive(&mut self, directive: &mut Directive) -> ControlFlow<B> {
+        walk_directive(self, directive)
+    }
+    fn visit_function(&mut self, function: &mut Function) -> ControlFlow<B> {
+        walk_function(self, function)
+    }
+    fn visit_function_body(&mut self, body: &mut FunctionBody) -> ControlFlow<B> {
+        walk_function_body(self, body)
+    }
+    fn visit_function_body_part(&mut self, part: &mut FunctionBodyPart) -> ControlFlow<B> {
+        walk_function_body_part(self, part)
+    }
+    fn visit_class(&mut self, class: &mut Class) -> ControlFlow<B> {
+        walk_class(self, class)
+    }
+    fn visit_class_body(&mut self, body: &mut ClassBody) -> ControlFlow<B> {
+        walk_class_body(self, body)
+    }
+    fn visit_method_definition(&mut self, method: &mut MethodDefinition) -> ControlFlow<B> {
+        walk_method_definition(self, method)
+    }
+    fn visit_pattern(&mut self, pattern: &mut Pattern) -> ControlFlow<B> {
+        walk_pattern(self, pattern)
+    }
+    fn visit_object_pattern(&mut self, object: &mut Object) -> ControlFlow<B> {
+        walk_object_pattern(self, object)
+    }
+    fn visit_assignment_property(&mut self, property: &mut AssignmentProperty) -> ControlFlow<B> {
+        walk_assignment_property(self, property)
+    }
+    fn visit_array_pattern(&mut self, array: &mut Array) -> ControlFlow<B> {
+        walk_array_pattern(self, array)
+    }
+    fn visit_rest_element(&mut self, rest: &mut RestElement) -> ControlFlow<B> {
+        walk_rest_element(self, rest)
+    }
+    fn visit_assignment_pattern(&mut self, assignment: &mut Assignment) -> ControlFlow<B> {
+        walk_assignment_pattern(self, assignment)
+    }
+    fn visit_expression(&mut self, expr: &mut Expression) -> ControlFlow<B> {
+        walk_expression(self, expr)
+    }
+    fn visit_identifier(&mut self, _ident: &mut Identifier) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_literal(&mut self, literal: &mut Literal) -> ControlFlow<B> {
+        walk_literal(self, literal)
+    }
+    fn visit_this(&mut self, _this: &mut This) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_super(&mut self, _sup: &mut Super) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_array_expression(&mut self, array: &mut expression::Array) -> ControlFlow<B> {
+        walk_array_expression(self, array)
+    }
+    fn visit_spreadable(&mut self, spreadable: &mut Spreadable) -> ControlFlow<B> {
+        walk_spreadable(self, spreadable)
+    }
+    fn visit_spread_element(&mut self, spread: &mut SpreadElement) -> ControlFlow<B> {
+        walk_spread_element(self, spread)
+    }
+    fn visit_object_expression(&mut self, object: &mut expression::Object) -> ControlFlow<B> {
+        walk_object_expression(self, object)
+    }
+    fn visit_property(&mut self, property: &mut Property) -> ControlFlow<B> {
+        walk_property(self, property)
+    }
+    fn visit_unary(&mut self, unary: &mut Unary) -> ControlFlow<B> {
+        walk_unary(self, unary)
+    }
+    fn visit_update(&mut self, update: &mut Update) -> ControlFlow<B> {
+        walk_update(self, update)
+    }
+    fn visit_binary(&mut self, binary: &mut Binary) -> ControlFlow<B> {
+        walk_binary(self, binary)
+    }
+    fn visit_assignment_expression(
+        &mut self,
+        assignment: &mut expression::Assignment,
+    ) -> ControlFlow<B> {
+        walk_assignment_expression(self, assignment)
+    }
+    fn visit_pat_or_expr(&mut self, pat_or_expr: &mut PatOrExpr) -> ControlFlow<B> {
+        walk_pat_or_expr(self, pat_or_expr)
+    }
+    fn visit_logical(&mut self, logical: &mut Logical) -> ControlFlow<B> {
+        walk_logical(self, logical)
+    }
+    fn visit_member(&mut self, member: &mut Member) -> ControlFlow<B> {
+        walk_member(self, member)
+    }
+    fn visit_parent(&mut self, parent: &mut Parent) -> ControlFlow<B> {
+        walk_parent(self, parent)
+    }
+    fn visit_conditional(&mut self, conditional: &mut Conditional) -> ControlFlow<B> {
+        walk_conditional(self, conditional)
+    }
+    fn visit_call(&mut self, call: &mut Call) -> ControlFlow<B> {
+        walk_call(self, call)
+    }
+    fn visit_new(&mut self, new: &mut New) -> ControlFlow<B> {
+        walk_new(self, new)
+    }
+    fn visit_sequence(&mut self, sequence: &mut Sequence) -> ControlFlow<B> {
+        walk_sequence(self, sequence)
+    }
+    fn visit_arrow_function(&mut self, arrow: &mut ArrowFunction) -> ControlFlow<B> {
+        walk_arrow_function(self, arrow)
+    }
+    fn visit_arrow_function_body(&mut self, body: &mut ArrowFunctionBody) -> ControlFlow<B> {
+        walk_arrow_function_body(self, body)
+    }
+    fn visit_yield(&mut self, expr: &mut Yield) -> ControlFlow<B> {
+        walk_yield(self, expr)
+    }
+    fn visit_await(&mut self, expr: &mut Await) -> ControlFlow<B> {
+        walk_await(self, expr)
+    }
+    fn visit_chain(&mut self, chain: &mut Chain) -> ControlFlow<B> {
+        walk_chain(self, chain)
+    }
+    fn visit_template_literal(&mut self, template: &mut TemplateLiteral) -> ControlFlow<B> {
+        walk_template_literal(self, template)
+    }
+    fn visit_template_element(&mut self, _element: &mut TemplateElement) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_tagged_template(&mut self, tagged: &mut TaggedTemplate) -> ControlFlow<B> {
+        walk_tagged_template(self, tagged)
+    }
+    fn visit_meta_property(&mut self, meta: &mut MetaProperty) -> ControlFlow<B> {
+        walk_meta_property(self, meta)
+    }
+    fn visit_statement(&mut self, stmt: &mut Statement) -> ControlFlow<B> {
+        walk_statement(self, stmt)
+    }
+    fn visit_block(&mut self, block: &mut Block) -> ControlFlow<B> {
+        walk_block(self, block)
+    }
+    fn visit_empty(&mut self, _empty: &mut Empty) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_debugger(&mut self, _debugger: &mut Debugger) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+    fn visit_with(&mut self, with: &mut With) -> ControlFlow<B> {
+        walk_with(self, with)
+    }
+    fn visit_return(&mut self, ret: &mut Return) -> ControlFlow<B> {
+        walk_return(self, ret)
+    }
+    fn visit_labeled(&mut self, labeled: &mut Labeled) -> ControlFlow<B> {
+        walk_labeled(self, labeled)
+    }
+    fn visit_break(&mut self, brk: &mut Break) -> ControlFlow<B> {
+        walk_break(self, brk)
+    }
+    fn visit_continue(&mut self, cont: &mut Continue) -> ControlFlow<B> {
+        walk_continue(self, cont)
+    }
+    fn visit_if(&mut self, stmt: &mut If) -> ControlFlow<B> {
+        walk_if(self, stmt)
+    }
+    fn visit_switch(&mut self, switch: &mut Switch) -> ControlFlow<B> {
+        walk_switch(self, switch)
+    }
+    fn visit_switch_case(&mut self, case: &mut SwitchCase) -> ControlFlow<B> {
+        walk_switch_case(self, case)
+    }
+    fn visit_throw(&mut self, throw: &mut Throw) -> ControlFlow<B> {
+        walk_throw(self, throw)
+    }
+    fn visit_try(&mut self, stmt: &mut Try) -> ControlFlow<B> {
+        walk_try(self, stmt)
+    }
+    fn visit_catch_clause(&mut self, clause: &mut CatchClause) -> ControlFlow<B> {
+        walk_catch_clause(self, clause)
+    }
+    fn visit_while(&mut self, stmt: &mut While) -> ControlFlow<B> {
+        walk_while(self, stmt)
+    }
+    fn visit_do_while(&mut self, stmt: &mut DoWhile) -> ControlFlow<B> {
+        walk_do_while(self, stmt)
+    }
+    fn visit_for(&mut self, stmt: &mut For) -> ControlFlow<B> {
+        walk_for(self, stmt)
+    }
+    fn visit_for_init(&mut self, init: &mut ForInit) -> ControlFlow<B> {
+        walk_for_init(self, init)
+    }
+    fn visit_for_in(&mut self, stmt: &mut ForIn) -> ControlFlow<B> {
+        walk_for_in(self, stmt)
+    }
+    fn visit_for_of(&mut self, stmt: &mut ForOf) -> ControlFlow<B> {
+        walk_for_of(self, stmt)
+    }
+    fn visit_for_left(&mut self, left: &mut ForLeft) -> ControlFlow<B> {
+        walk_for_left(self, left)
+    }
+    fn visit_declaration(&mut self, decl: &mut Declaration) -> ControlFlow<B> {
+        walk_declaration(self, decl)
+    }
+    fn visit_variable_declaration(&mut self, decl: &mut VariableDeclaration) -> ControlFlow<B> {
+        walk_variable_declaration(self, decl)
+    }
+    fn visit_variable_declarator(&mut self, decl: &mut VariableDeclarator) -> ControlFlow<B> {
+        walk_variable_declarator(self, decl)
+    }
+    fn visit_module_declaration(&mut self, decl: &mut ModuleDeclaration) -> ControlFlow<B> {
+        walk_module_declaration(self, decl)
+    }
+    fn visit_import_declaration(&mut self, decl: &mut ImportDeclaration) -> ControlFlow<B> {
+        walk_import_declaration(self, decl)
+    }
+    fn visit_import_specifier(&mut self, spec: &mut ImportSpecifier) -> ControlFlow<B> {
+        walk_import_specifier(self, spec)
+    }
+    fn visit_import(&mut self, import: &mut Import) -> ControlFlow<B> {
+        walk_import(self, import)
+    }
+    fn visit_import_default(&mut self, import: &mut ImportDefault) -> ControlFlow<B> {
+        walk_import_default(self, import)
+    }
+    fn visit_import_namespace(&mut self, import: &mut ImportNamespace) -> ControlFlow<B> {
+        walk_import_namespace(self, import)
+    }
+    fn visit_export_declaration(&mut self, decl: &mut ExportDeclaration) -> ControlFlow<B> {
+        walk_export_declaration(self, decl)
+    }
+    fn visit_export_named(&mut self, export: &mut ExportNamed) -> ControlFlow<B> {
+        walk_export_named(self, export)
+    }
+    fn visit_export_specifier(&mut self, spec: &mut ExportSpecifier) -> ControlFlow<B> {
+        walk_export_specifier(self, spec)
+    }
+    fn visit_export_default(&mut self, export: &mut ExportDefault) -> ControlFlow<B> {
+        walk_export_default(self, export)
+    }
+    fn visit_export_decl(&mut self, decl: &mut ExportDecl) -> ControlFlow<B> {
+        walk_export_decl(self, decl)
+    }
+    fn visit_export_all(&mut self, export: &mut ExportAll) -> ControlFlow<B> {
+        walk_export_all(self, export)
+    }
+}
+
+pub fn walk_program<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    program: &mut Program,
+) -> ControlFlow<B> {
+    for part in &mut program.body {
+        visitor.visit_program_part(part)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_program_part<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    part: &mut ProgramPart,
+) -> ControlFlow<B> {
+    match part {
+        ProgramPart::Directive(ref mut d) => visitor.visit_directive(d),
+        ProgramPart::Statement(ref mut s) => visitor.visit_statement(s),
+        ProgramPart::ModuleDecl(ref mut m) => visitor.visit_module_declaration(m),
+    }
+}
+
+pub fn walk_directive<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    directive: &mut Directive,
+) -> ControlFlow<B> {
+    visitor.visit_literal(&mut directive.expression)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_function<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    function: &mut Function,
+) -> ControlFlow<B> {
+    if let Some(ref mut id) = function.id {
+        visitor.visit_identifier(id)?;
+    }
+    for param in &mut function.params {
+        visitor.visit_pattern(param)?;
+    }
+    visitor.visit_function_body(&mut function.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_function_body<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    body: &mut FunctionBody,
+) -> ControlFlow<B> {
+    for part in &mut body.body {
+        visitor.visit_function_body_part(part)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_function_body_part<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    part: &mut FunctionBodyPart,
+) -> ControlFlow<B> {
+    match part {
+        FunctionBodyPart::Directive(ref mut d) => visitor.visit_directive(d),
+        FunctionBodyPart::Statement(ref mut s) => visitor.visit_statement(s),
+    }
+}
+
+pub fn walk_class<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    class: &mut Class,
+) -> ControlFlow<B> {
+    if let Some(ref mut id) = class.id {
+        visitor.visit_identifier(id)?;
+    }
+    if let Some(ref mut super_class) = class.super_class {
+        visitor.visit_expression(super_class)?;
+    }
+    visitor.visit_class_body(&mut class.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_class_body<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    body: &mut ClassBody,
+) -> ControlFlow<B> {
+    for method in &mut body.body {
+        visitor.visit_method_definition(method)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_method_definition<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    method: &mut MethodDefinition,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut method.key)?;
+    visitor.visit_function(&mut method.value)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_pattern<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    pattern: &mut Pattern,
+) -> ControlFlow<B> {
+    match pattern {
+        Pattern::Ident(ref mut i) => visitor.visit_identifier(i),
+        Pattern::Object(ref mut o) => visitor.visit_object_pattern(o),
+        Pattern::Array(ref mut a) => visitor.visit_array_pattern(a),
+        Pattern::RestElement(ref mut r) => visitor.visit_rest_element(r),
+        Pattern::Assignment(ref mut a) => visitor.visit_assignment_pattern(a),
+    }
+}
+
+pub fn walk_object_pattern<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    object: &mut Object,
+) -> ControlFlow<B> {
+    for property in &mut object.properties {
+        visitor.visit_assignment_property(property)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_assignment_property<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    property: &mut AssignmentProperty,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut property.key)?;
+    visitor.visit_pattern(&mut property.value)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_array_pattern<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    array: &mut Array,
+) -> ControlFlow<B> {
+    for element in array.elements.iter_mut().flatten() {
+        visitor.visit_pattern(element)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_rest_element<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    rest: &mut RestElement,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&mut rest.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_assignment_pattern<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    assignment: &mut Assignment,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&mut assignment.left)?;
+    visitor.visit_expression(&mut assignment.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_expression<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    expr: &mut Expression,
+) -> ControlFlow<B> {
+    match expr {
+        Expression::This(ref mut inner) => visitor.visit_this(inner),
+        Expression::Array(ref mut inner) => visitor.visit_array_expression(inner),
+        Expression::Object(ref mut inner) => visitor.visit_object_expression(inner),
+        Expression::Function(ref mut inner) => visitor.visit_function(inner),
+        Expression::Unary(ref mut inner) => visitor.visit_unary(inner),
+        Expression::Update(ref mut inner) => visitor.visit_update(inner),
+        Expression::Binary(ref mut inner) => visitor.visit_binary(inner),
+        Expression::Assignment(ref mut inner) => visitor.visit_assignment_expression(inner),
+        Expression::Logical(ref mut inner) => visitor.visit_logical(inner),
+        Expression::Member(ref mut inner) => visitor.visit_member(inner),
+        Expression::Conditional(ref mut inner) => visitor.visit_conditional(inner),
+        Expression::Call(ref mut inner) => visitor.visit_call(inner),
+        Expression::New(ref mut inner) => visitor.visit_new(inner),
+        Expression::Sequence(ref mut inner) => visitor.visit_sequence(inner),
+        Expression::Spread(ref mut inner) => visitor.visit_spread_element(inner),
+        Expression::ArrowFunction(ref mut inner) => visitor.visit_arrow_function(inner),
+        Expression::Yield(ref mut inner) => visitor.visit_yield(inner),
+        Expression::Await(ref mut inner) => visitor.visit_await(inner),
+        Expression::Chain(ref mut inner) => visitor.visit_chain(inner),
+        Expression::Identifier(ref mut inner) => visitor.visit_identifier(inner),
+        Expression::Literal(ref mut inner) => visitor.visit_literal(inner),
+        Expression::TaggedTemplate(ref mut inner) => visitor.visit_tagged_template(inner),
+        Expression::Class(ref mut inner) => visitor.visit_class(inner),
+        Expression::Meta(ref mut inner) => visitor.visit_meta_property(inner),
+    }
+}
+
+pub fn walk_literal<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    literal: &mut Literal,
+) -> ControlFlow<B> {
+    if let LiteralValue::TemplateLiteral(ref mut template) = literal.value {
+        visitor.visit_template_literal(template)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_array_expression<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    array: &mut expression::Array,
+) -> ControlFlow<B> {
+    for element in array.elements.iter_mut().flatten() {
+        visitor.visit_spreadable(element)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_spreadable<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    spreadable: &mut Spreadable,
+) -> ControlFlow<B> {
+    match spreadable {
+        Spreadable::Expr(ref mut e) => visitor.visit_expression(e),
+        Spreadable::Spread(ref mut s) => visitor.visit_spread_element(s),
+    }
+}
+
+pub fn walk_spread_element<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    spread: &mut SpreadElement,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut spread.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_object_expression<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    object: &mut expression::Object,
+) -> ControlFlow<B> {
+    for property in &mut object.properties {
+        visitor.visit_property(property)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_property<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    property: &mut Property,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut property.key)?;
+    visitor.visit_expression(&mut property.value)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_unary<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    unary: &mut Unary,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut unary.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_update<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    update: &mut Update,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut update.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_binary<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    binary: &mut Binary,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut binary.left)?;
+    visitor.visit_expression(&mut binary.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_assignment_expression<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    assignment: &mut expression::Assignment,
+) -> ControlFlow<B> {
+    visitor.visit_pat_or_expr(&mut assignment.left)?;
+    visitor.visit_expression(&mut assignment.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_pat_or_expr<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    pat_or_expr: &mut PatOrExpr,
+) -> ControlFlow<B> {
+    match pat_or_expr {
+        PatOrExpr::Pat(ref mut p) => visitor.visit_pattern(p),
+        PatOrExpr::Expr(ref mut e) => visitor.visit_expression(e),
+    }
+}
+
+pub fn walk_logical<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    logical: &mut Logical,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut logical.left)?;
+    visitor.visit_expression(&mut logical.right)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_member<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    member: &mut Member,
+) -> ControlFlow<B> {
+    visitor.visit_parent(&mut member.object)?;
+    visitor.visit_expression(&mut member.property)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_parent<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    parent: &mut Parent,
+) -> ControlFlow<B> {
+    match parent {
+        Parent::Expr(ref mut e) => visitor.visit_expression(e),
+        Parent::Super(ref mut s) => visitor.visit_super(s),
+    }
+}
+
+pub fn walk_conditional<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    conditional: &mut Conditional,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut conditional.test)?;
+    visitor.visit_expression(&mut conditional.consequent)?;
+    visitor.visit_expression(&mut conditional.alternate)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_call<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, call: &mut Call) -> ControlFlow<B> {
+    visitor.visit_parent(&mut call.callee)?;
+    for arg in &mut call.arguments {
+        visitor.visit_spreadable(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_new<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, new: &mut New) -> ControlFlow<B> {
+    visitor.visit_expression(&mut new.callee)?;
+    for arg in &mut new.arguments {
+        visitor.visit_spreadable(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_sequence<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    sequence: &mut Sequence,
+) -> ControlFlow<B> {
+    for expr in &mut sequence.expressions {
+        visitor.visit_expression(expr)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_arrow_function<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    arrow: &mut ArrowFunction,
+) -> ControlFlow<B> {
+    if let Some(ref mut id) = arrow.id {
+        visitor.visit_identifier(id)?;
+    }
+    for param in &mut arrow.params {
+        visitor.visit_pattern(param)?;
+    }
+    visitor.visit_arrow_function_body(&mut arrow.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_arrow_function_body<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    body: &mut ArrowFunctionBody,
+) -> ControlFlow<B> {
+    match body {
+        ArrowFunctionBody::Expr(ref mut e) => visitor.visit_expression(e),
+        ArrowFunctionBody::Block(ref mut b) => visitor.visit_function_body(b),
+    }
+}
+
+pub fn walk_yield<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, expr: &mut Yield) -> ControlFlow<B> {
+    if let Some(ref mut arg) = expr.argument {
+        visitor.visit_expression(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_await<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, expr: &mut Await) -> ControlFlow<B> {
+    visitor.visit_expression(&mut expr.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_chain<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    chain: &mut Chain,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut chain.expression)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_template_literal<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    template: &mut TemplateLiteral,
+) -> ControlFlow<B> {
+    for quasi in &mut template.quasis {
+        visitor.visit_template_element(quasi)?;
+    }
+    for expr in &mut template.expressions {
+        visitor.visit_expression(expr)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_tagged_template<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    tagged: &mut TaggedTemplate,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut tagged.tag)?;
+    visitor.visit_template_literal(&mut tagged.quasi)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_meta_property<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    meta: &mut MetaProperty,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&mut meta.meta)?;
+    visitor.visit_identifier(&mut meta.property)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_statement<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &mut Statement,
+) -> ControlFlow<B> {
+    match stmt {
+        Statement::Expr(ref mut inner) => visitor.visit_expression(inner),
+        Statement::Block(ref mut inner) => visitor.visit_block(inner),
+        Statement::FuncBody(ref mut inner) => visitor.visit_function_body(inner),
+        Statement::Empty(ref mut inner) => visitor.visit_empty(inner),
+        Statement::Debugger(ref mut inner) => visitor.visit_debugger(inner),
+        Statement::With(ref mut inner) => visitor.visit_with(inner),
+        Statement::Return(ref mut inner) => visitor.visit_return(inner),
+        Statement::Labeled(ref mut inner) => visitor.visit_labeled(inner),
+        Statement::Break(ref mut inner) => visitor.visit_break(inner),
+        Statement::Continue(ref mut inner) => visitor.visit_continue(inner),
+        Statement::If(ref mut inner) => visitor.visit_if(inner),
+        Statement::Switch(ref mut inner) => visitor.visit_switch(inner),
+        Statement::Throw(ref mut inner) => visitor.visit_throw(inner),
+        Statement::Try(ref mut inner) => visitor.visit_try(inner),
+        Statement::While(ref mut inner) => visitor.visit_while(inner),
+        Statement::DoWhile(ref mut inner) => visitor.visit_do_while(inner),
+        Statement::For(ref mut inner) => visitor.visit_for(inner),
+        Statement::ForIn(ref mut inner) => visitor.visit_for_in(inner),
+        Statement::ForOf(ref mut inner) => visitor.visit_for_of(inner),
+        Statement::Decl(ref mut inner) => visitor.visit_declaration(inner),
+    }
+}
+
+pub fn walk_block<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    block: &mut Block,
+) -> ControlFlow<B> {
+    for stmt in &mut block.body {
+        visitor.visit_statement(stmt)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_with<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, with: &mut With) -> ControlFlow<B> {
+    visitor.visit_expression(&mut with.object)?;
+    visitor.visit_statement(&mut with.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_return<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    ret: &mut Return,
+) -> ControlFlow<B> {
+    if let Some(ref mut arg) = ret.argument {
+        visitor.visit_expression(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_labeled<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    labeled: &mut Labeled,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&mut labeled.label)?;
+    visitor.visit_statement(&mut labeled.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_break<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, brk: &mut Break) -> ControlFlow<B> {
+    if let Some(ref mut label) = brk.label {
+        visitor.visit_identifier(label)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_continue<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    cont: &mut Continue,
+) -> ControlFlow<B> {
+    if let Some(ref mut label) = cont.label {
+        visitor.visit_identifier(label)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_if<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, stmt: &mut If) -> ControlFlow<B> {
+    visitor.visit_expression(&mut stmt.test)?;
+    visitor.visit_statement(&mut stmt.consequent)?;
+    if let Some(ref mut alt) = stmt.alternate {
+        visitor.visit_statement(alt)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_switch<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    switch: &mut Switch,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut switch.discriminant)?;
+    for case in &mut switch.cases {
+        visitor.visit_switch_case(case)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_switch_case<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    case: &mut SwitchCase,
+) -> ControlFlow<B> {
+    if let Some(ref mut test) = case.test {
+        visitor.visit_expression(test)?;
+    }
+    for stmt in &mut case.consequent {
+        visitor.visit_statement(stmt)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_throw<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    throw: &mut Throw,
+) -> ControlFlow<B> {
+    visitor.visit_expression(&mut throw.argument)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_try<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, stmt: &mut Try) -> ControlFlow<B> {
+    visitor.visit_block(&mut stmt.block)?;
+    if let Some(ref mut handler) = stmt.handler {
+        visitor.visit_catch_clause(handler)?;
+    }
+    if let Some(ref mut finalizer) = stmt.finalizer {
+        visitor.visit_block(finalizer)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_catch_clause<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    clause: &mut CatchClause,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&mut clause.param)?;
+    visitor.visit_block(&mut clause.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_while<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, stmt: &mut While) -> ControlFlow<B> {
+    visitor.visit_expression(&mut stmt.test)?;
+    visitor.visit_statement(&mut stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_do_while<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &mut DoWhile,
+) -> ControlFlow<B> {
+    visitor.visit_statement(&mut stmt.body)?;
+    visitor.visit_expression(&mut stmt.test)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for<B, V: VisitMut<B> + ?Sized>(visitor: &mut V, stmt: &mut For) -> ControlFlow<B> {
+    visitor.visit_for_init(&mut stmt.init)?;
+    if let Some(ref mut test) = stmt.test {
+        visitor.visit_expression(test)?;
+    }
+    if let Some(ref mut update) = stmt.update {
+        visitor.visit_expression(update)?;
+    }
+    visitor.visit_statement(&mut stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for_init<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    init: &mut ForInit,
+) -> ControlFlow<B> {
+    match init {
+        ForInit::Var(ref mut v) => visitor.visit_variable_declaration(v),
+        ForInit::Expr(ref mut e) => visitor.visit_expression(e),
+        ForInit::Null => ControlFlow::Continue(()),
+    }
+}
+
+pub fn walk_for_in<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &mut ForIn,
+) -> ControlFlow<B> {
+    visitor.visit_for_left(&mut stmt.left)?;
+    visitor.visit_expression(&mut stmt.right)?;
+    visitor.visit_statement(&mut stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for_of<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    stmt: &mut ForOf,
+) -> ControlFlow<B> {
+    visitor.visit_for_left(&mut stmt.left)?;
+    visitor.visit_expression(&mut stmt.right)?;
+    visitor.visit_statement(&mut stmt.body)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_for_left<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    left: &mut ForLeft,
+) -> ControlFlow<B> {
+    match left {
+        ForLeft::Var(ref mut v) => visitor.visit_variable_declaration(v),
+        ForLeft::Pat(ref mut p) => visitor.visit_pattern(p),
+    }
+}
+
+pub fn walk_declaration<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    decl: &mut Declaration,
+) -> ControlFlow<B> {
+    match decl {
+        Declaration::Function(ref mut f) => visitor.visit_function(f),
+        Declaration::Variable(ref mut v) => visitor.visit_variable_declaration(v),
+        Declaration::Class(ref mut c) => visitor.visit_class(c),
+    }
+}
+
+pub fn walk_variable_declaration<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    decl: &mut VariableDeclaration,
+) -> ControlFlow<B> {
+    for declarator in &mut decl.declarations {
+        visitor.visit_variable_declarator(declarator)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_variable_declarator<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    decl: &mut VariableDeclarator,
+) -> ControlFlow<B> {
+    visitor.visit_pattern(&mut decl.id)?;
+    if let Some(ref mut init) = decl.init {
+        visitor.visit_expression(init)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_module_declaration<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    decl: &mut ModuleDeclaration,
+) -> ControlFlow<B> {
+    match decl {
+        ModuleDeclaration::Import(ref mut i) => visitor.visit_import_declaration(i),
+        ModuleDeclaration::Export(ref mut e) => visitor.visit_export_declaration(e),
+    }
+}
+
+pub fn walk_import_declaration<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    decl: &mut ImportDeclaration,
+) -> ControlFlow<B> {
+    for spec in &mut decl.specifiers {
+        visitor.visit_import_specifier(spec)?;
+    }
+    visitor.visit_literal(&mut decl.source)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_import_specifier<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    spec: &mut ImportSpecifier,
+) -> ControlFlow<B> {
+    match spec {
+        ImportSpecifier::Named(ref mut n) => visitor.visit_import(n),
+        ImportSpecifier::Default(ref mut d) => visitor.visit_import_default(d),
+        ImportSpecifier::NameSpace(ref mut n) => visitor.visit_import_namespace(n),
+    }
+}
+
+pub fn walk_import<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    import: &mut Import,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&mut import.import)?;
+    visitor.visit_identifier(&mut import.local)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_import_default<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    import: &mut ImportDefault,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&mut import.local)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_import_namespace<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    import: &mut ImportNamespace,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&mut import.local)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_declaration<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    decl: &mut ExportDeclaration,
+) -> ControlFlow<B> {
+    match decl {
+        ExportDeclaration::Named(ref mut n) => visitor.visit_export_named(n),
+        ExportDeclaration::Default(ref mut d) => visitor.visit_export_default(d),
+        ExportDeclaration::All(ref mut a) => visitor.visit_export_all(a),
+    }
+}
+
+pub fn walk_export_named<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    export: &mut ExportNamed,
+) -> ControlFlow<B> {
+    if let Some(ref mut decl) = export.declaration {
+        visitor.visit_declaration(decl)?;
+    }
+    for spec in &mut export.specifiers {
+        visitor.visit_export_specifier(spec)?;
+    }
+    if let Some(ref mut source) = export.source {
+        visitor.visit_literal(source)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_specifier<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    spec: &mut ExportSpecifier,
+) -> ControlFlow<B> {
+    visitor.visit_identifier(&mut spec.local)?;
+    visitor.visit_identifier(&mut spec.exported)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_default<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    export: &mut ExportDefault,
+) -> ControlFlow<B> {
+    visitor.visit_export_decl(&mut export.declaration)?;
+    ControlFlow::Continue(())
+}
+
+pub fn walk_export_decl<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    decl: &mut ExportDecl,
+) -> ControlFlow<B> {
+    match decl {
+        ExportDecl::Decl(ref mut d) => visitor.visit_declaration(d),
+        ExportDecl::Expr(ref mut e) => visitor.visit_expression(e),
+    }
+}
+
+pub fn walk_export_all<B, V: VisitMut<B> + ?Sized>(
+    visitor: &mut V,
+    export: &mut ExportAll,
+) -> ControlFlow<B> {
+    visitor.visit_literal(&mut export.source)?;
+    ControlFlow::Continue(())
+}
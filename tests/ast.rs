@@ -0,0 +1,125 @@
+extern crate res_tree;
+mod common;
+use common::{id, ident, loc};
+use res_tree::codegen::{print, print_node};
+use res_tree::expression::{
+    Await, Chain, Expression, Literal, LiteralValue, Member, Parent, SpreadElement, Yield,
+};
+use res_tree::module::{
+    ExportNamed, ExportSpecifier, Import, ImportDeclaration, ImportSpecifier, ModuleDeclaration,
+};
+use res_tree::node::{Node, NodeKind};
+use res_tree::node_ref::NodeRef;
+use res_tree::statement::{FunctionBody, Statement};
+use res_tree::{Function, Program, ProgramPart, SourceType};
+
+fn kind(expr: &Expression) -> NodeKind {
+    expr.kind()
+}
+
+#[test]
+fn await_yield_and_spread_are_nodes() {
+    let awaited = Expression::Await(Box::new(Await {
+        argument: ident("a", 6),
+        loc: loc(0, 7),
+    }));
+    assert_eq!(kind(&awaited), NodeKind::AwaitExpression);
+    assert_eq!(awaited.loc(), loc(0, 7));
+    assert_eq!(awaited.children().len(), 1);
+
+    let yielded = Expression::Yield(Box::new(Yield {
+        argument: Some(ident("a", 7)),
+        delegate: true,
+        loc: loc(0, 8),
+    }));
+    assert_eq!(kind(&yielded), NodeKind::YieldExpression);
+    assert_eq!(yielded.children().len(), 1);
+
+    let spread = Expression::Spread(Box::new(SpreadElement {
+        argument: ident("a", 3),
+        loc: loc(0, 4),
+    }));
+    assert_eq!(kind(&spread), NodeKind::SpreadElement);
+    assert_eq!(spread.loc(), loc(0, 4));
+}
+
+#[test]
+fn functions_know_if_they_are_async() {
+    let f = Function {
+        id: Some(id("f", 15)),
+        params: Vec::new(),
+        body: FunctionBody {
+            body: Vec::new(),
+            loc: loc(19, 21),
+        },
+        generator: false,
+        _async: true,
+        loc: loc(0, 21),
+    };
+    let program = Program {
+        source_type: SourceType::Script,
+        body: vec![ProgramPart::Statement(Statement::Expr(
+            Expression::Function(Box::new(f)),
+        ))],
+        loc: loc(0, 22),
+    };
+    assert_eq!(print(&program), "(async function f() {});\n");
+}
+
+#[test]
+fn imports_have_a_source_and_exports_a_local_name() {
+    // `import { a as b } from "m"; export { b as c };`
+    let import = ModuleDeclaration::Import(ImportDeclaration {
+        specifiers: vec![ImportSpecifier::Named(Import {
+            import: id("a", 9),
+            local: id("b", 14),
+            loc: loc(9, 15),
+        })],
+        source: Literal {
+            value: LiteralValue::String("m".to_string()),
+            loc: loc(23, 26),
+        },
+        loc: loc(0, 27),
+    });
+    let export =
+        ModuleDeclaration::Export(res_tree::module::ExportDeclaration::Named(ExportNamed {
+            declaration: None,
+            specifiers: vec![ExportSpecifier {
+                local: id("b", 37),
+                exported: id("c", 42),
+                loc: loc(37, 43),
+            }],
+            source: None,
+            loc: loc(28, 46),
+        }));
+    let program = Program {
+        source_type: SourceType::Module,
+        body: vec![
+            ProgramPart::ModuleDecl(import),
+            ProgramPart::ModuleDecl(export),
+        ],
+        loc: loc(0, 46),
+    };
+    assert_eq!(
+        print(&program),
+        "import {a as b} from \"m\";\nexport {b as c};\n"
+    );
+}
+
+#[test]
+fn optional_chains_are_wrapped() {
+    // `a?.b`
+    let chain = Expression::Chain(Box::new(Chain {
+        expression: Expression::Member(Box::new(Member {
+            object: Parent::Expr(ident("a", 0)),
+            property: ident("b", 3),
+            computed: false,
+            optional: true,
+            loc: loc(0, 4),
+        })),
+        loc: loc(0, 4),
+    }));
+    assert_eq!(kind(&chain), NodeKind::ChainExpression);
+    assert_eq!(chain.children().len(), 1);
+    assert_eq!(print_node(NodeRef::Expression(&chain)), "a?.b");
+}
@@ -0,0 +1,230 @@
+#![cfg(feature = "serde")]
+extern crate res_tree;
+#[macro_use]
+extern crate serde_json;
+use res_tree::estree::{self, babel};
+use res_tree::expression::{Expression, LiteralValue, Parent};
+use res_tree::statement::Statement;
+use res_tree::ProgramPart;
+use serde_json::Value;
+
+/// Give every node without a location an empty one at the start, as
+/// reading needs one on each node
+fn located(mut value: Value) -> Value {
+    fn visit(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                if map.contains_key("type") && !map.contains_key("loc") {
+                    map.insert("start".to_string(), json!(0));
+                    map.insert("end".to_string(), json!(0));
+                    let start = json!({ "line": 1, "column": 0 });
+                    map.insert("loc".to_string(), json!({ "start": start, "end": start }));
+                }
+                map.values_mut().for_each(visit);
+            }
+            Value::Array(items) => items.iter_mut().for_each(visit),
+            _ => (),
+        }
+    }
+    visit(&mut value);
+    value
+}
+
+fn ident(name: &str) -> Value {
+    json!({ "type": "Identifier", "name": name })
+}
+
+fn function() -> Value {
+    json!({
+        "type": "FunctionExpression",
+        "id": null,
+        "params": [],
+        "body": { "type": "BlockStatement", "body": [] },
+        "generator": false,
+        "async": false,
+    })
+}
+
+fn member(object: Value, property: &str, optional: bool) -> Value {
+    json!({
+        "type": "MemberExpression",
+        "object": object,
+        "property": ident(property),
+        "computed": false,
+        "optional": optional,
+    })
+}
+
+fn statement(expression: Value) -> Value {
+    json!({ "type": "ExpressionStatement", "expression": expression })
+}
+
+// "use strict"; ({ a: "x", m() {}, get g() {} }); class C { static s() {} } a?.b.c; /re/g;
+fn estree() -> Value {
+    json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": [
+            {
+                "type": "ExpressionStatement",
+                "expression": { "type": "Literal", "value": "use strict", "raw": "\"use strict\"" },
+                "directive": "use strict",
+            },
+            statement(json!({
+                "type": "ObjectExpression",
+                "properties": [
+                    {
+                        "type": "Property", "key": ident("a"), "kind": "init",
+                        "value": { "type": "Literal", "value": "x", "raw": "'x'" },
+                        "method": false, "shorthand": false, "computed": false,
+                    },
+                    {
+                        "type": "Property", "key": ident("m"), "kind": "init", "value": function(),
+                        "method": true, "shorthand": false, "computed": false,
+                    },
+                    {
+                        "type": "Property", "key": ident("g"), "kind": "get", "value": function(),
+                        "method": false, "shorthand": false, "computed": false,
+                    },
+                ],
+            })),
+            {
+                "type": "ClassDeclaration",
+                "id": ident("C"),
+                "superClass": null,
+                "body": {
+                    "type": "ClassBody",
+                    "body": [{
+                        "type": "MethodDefinition", "key": ident("s"), "value": function(),
+                        "kind": "method", "computed": false, "static": true,
+                    }],
+                },
+            },
+            statement(json!({
+                "type": "ChainExpression",
+                "expression": member(member(ident("a"), "b", true), "c", false),
+            })),
+            statement(json!({
+                "type": "Literal", "value": null, "raw": "/re/g",
+                "regex": { "pattern": "re", "flags": "g" },
+            })),
+        ],
+    })
+}
+
+#[test]
+fn writes_babel_shapes_and_reads_them_back() {
+    let program = estree::from_value(&located(estree())).unwrap();
+    let babel = babel::to_value(&program).unwrap();
+
+    assert_eq!(babel["directives"][0]["type"], "Directive");
+    assert_eq!(babel["directives"][0]["value"]["value"], "use strict");
+    let body = babel["body"].as_array().unwrap();
+    assert_eq!(body.len(), 4);
+
+    let properties = &body[0]["expression"]["properties"];
+    assert_eq!(properties[0]["type"], "ObjectProperty");
+    assert_eq!(properties[0]["value"]["type"], "StringLiteral");
+    assert_eq!(properties[0]["value"]["extra"]["raw"], "\"x\"");
+    assert_eq!(properties[1]["type"], "ObjectMethod");
+    assert_eq!(properties[1]["kind"], "method");
+    assert_eq!(properties[2]["kind"], "get");
+    assert!(properties[2].get("value").is_none());
+
+    let method = &body[1]["body"]["body"][0];
+    assert_eq!(method["type"], "ClassMethod");
+    assert_eq!(method["static"], true);
+    assert_eq!(method["body"]["type"], "BlockStatement");
+
+    let chain = &body[2]["expression"];
+    assert_eq!(chain["type"], "OptionalMemberExpression");
+    assert_eq!(chain["optional"], false);
+    assert_eq!(chain["object"]["type"], "OptionalMemberExpression");
+    assert_eq!(chain["object"]["optional"], true);
+
+    assert_eq!(body[3]["expression"]["type"], "RegExpLiteral");
+    assert_eq!(body[3]["expression"]["pattern"], "re");
+    assert!(body[3].get("range").is_none());
+
+    assert_eq!(babel::from_value(babel).unwrap(), program);
+}
+
+#[test]
+fn bigints_are_bigint_literals() {
+    // `1_000n;`
+    let program = estree::from_value(&located(json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": [statement(json!({
+            "type": "Literal", "value": null, "raw": "1_000n", "bigint": "1000",
+        }))],
+    })))
+    .unwrap();
+    let babel = babel::to_value(&program).unwrap();
+    let literal = &babel["body"][0]["expression"];
+    assert_eq!(literal["type"], "BigIntLiteral");
+    assert_eq!(literal["value"], "1000");
+    assert_eq!(literal["extra"]["raw"], "1_000n");
+    assert!(literal.get("bigint").is_none());
+    assert_eq!(babel::from_value(babel).unwrap(), program);
+
+    // `2n;` as Babel writes it
+    let file = json!({
+        "type": "File",
+        "program": {
+            "type": "Program",
+            "sourceType": "script",
+            "directives": [],
+            "body": [statement(json!({
+                "type": "BigIntLiteral",
+                "value": "2",
+                "extra": { "rawValue": "2", "raw": "2n" },
+            }))],
+        },
+    });
+    let program = babel::from_value(located(file)).unwrap();
+    match program.body[0] {
+        ProgramPart::Statement(Statement::Expr(Expression::Literal(ref literal))) => {
+            assert_eq!(literal.value, LiteralValue::Number("2n".to_string()))
+        }
+        ref other => panic!("expected a literal statement, found {:?}", other),
+    }
+}
+
+#[test]
+fn optional_chains_keep_their_parens() {
+    // `(a?.b).c;`
+    let file = json!({
+        "type": "File",
+        "program": {
+            "type": "Program",
+            "sourceType": "module",
+            "directives": [],
+            "body": [statement(json!({
+                "type": "MemberExpression",
+                "object": {
+                    "type": "OptionalMemberExpression",
+                    "object": ident("a"),
+                    "property": ident("b"),
+                    "computed": false,
+                    "optional": true,
+                },
+                "property": ident("c"),
+                "computed": false,
+            }))],
+        },
+    });
+    let program = babel::from_str(&located(file).to_string()).unwrap();
+    let outer = match program.body[0] {
+        ProgramPart::Statement(Statement::Expr(Expression::Member(ref outer))) => outer,
+        ref other => panic!("expected a member statement, found {:?}", other),
+    };
+    assert!(!outer.optional);
+    match outer.object {
+        Parent::Expr(Expression::Chain(ref chain)) => match chain.expression {
+            Expression::Member(ref inner) => assert!(inner.optional),
+            ref other => panic!("expected a member in the chain, found {:?}", other),
+        },
+        ref other => panic!("expected a chain, found {:?}", other),
+    }
+}
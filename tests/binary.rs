@@ -0,0 +1,137 @@
+extern crate res_tree;
+mod common;
+use common::{ident, loc, number, script};
+use res_tree::binary::{self, Error, MAX_DEPTH, VERSION};
+use res_tree::expression::{
+    Binary, BinaryOperator, Expression, Literal, LiteralValue, Unary, UnaryOperator,
+};
+use res_tree::node::SourceRegistry;
+use res_tree::statement::Statement;
+use res_tree::Program;
+use std::num::NonZeroU64;
+
+// `total + 1;\n"total" + total;`
+fn program() -> Program {
+    let line_two = |start: u64, end: u64| {
+        let mut loc = loc(start, end);
+        loc.start.line = NonZeroU64::new(2).unwrap();
+        loc.start.column = start - 11;
+        loc.end.line = loc.start.line;
+        loc.end.column = end - 11;
+        loc
+    };
+    let mut program = script(
+        vec![
+            Statement::Expr(Expression::Binary(Box::new(Binary {
+                operator: BinaryOperator::Plus,
+                left: ident("total", 0),
+                right: number("1", 8),
+                loc: loc(0, 9),
+            }))),
+            Statement::Expr(Expression::Binary(Box::new(Binary {
+                operator: BinaryOperator::Plus,
+                left: Expression::Literal(Box::new(Literal {
+                    value: LiteralValue::String("total".to_string()),
+                    loc: line_two(11, 18),
+                })),
+                right: ident("total", 21),
+                loc: line_two(11, 26),
+            }))),
+        ],
+        27,
+    );
+    program.loc.source = Some(SourceRegistry::new().add("main.js"));
+    program
+}
+
+#[test]
+fn round_trips_exactly() {
+    let program = program();
+    let bytes = binary::encode(&program);
+    assert_eq!(binary::decode(&bytes).unwrap(), program);
+}
+
+#[test]
+fn strings_are_stored_once() {
+    let bytes = binary::encode(&program());
+    let occurrences = bytes.windows(5).filter(|w| *w == b"total").count();
+    assert_eq!(occurrences, 1);
+}
+
+#[test]
+fn rejects_bad_input() {
+    let bytes = binary::encode(&program());
+    assert_eq!(binary::decode(b"{}"), Err(Error::NotBinary));
+
+    let mut newer = bytes.clone();
+    newer[4] = VERSION as u8 + 1;
+    assert_eq!(binary::decode(&newer), Err(Error::Version(VERSION + 1)));
+
+    assert_eq!(
+        binary::decode(&bytes[..bytes.len() - 1]),
+        Err(Error::UnexpectedEnd)
+    );
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(binary::decode(&trailing), Err(Error::TrailingBytes));
+}
+
+fn varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// An empty script whose location is in the file with the encoded
+/// id `file`, one more than its index
+fn empty_in_file(file: u64) -> Vec<u8> {
+    let mut bytes = b"RSTB".to_vec();
+    varint(&mut bytes, VERSION);
+    // no strings, a script with no body
+    bytes.extend_from_slice(&[0, 0, 0]);
+    varint(&mut bytes, file);
+    // line 1, column 0 for the start and end and an empty range
+    bytes.extend_from_slice(&[2, 0, 2, 0, 0, 0]);
+    bytes
+}
+
+#[test]
+fn rejects_file_ids_past_u32() {
+    let last = u64::from(u32::MAX) + 1;
+    let program = binary::decode(&empty_in_file(last)).unwrap();
+    assert_eq!(program.loc.source.unwrap().index(), u32::MAX as usize);
+    assert_eq!(
+        binary::decode(&empty_in_file(last + 1)),
+        Err(Error::InvalidLocation)
+    );
+}
+
+#[test]
+fn rejects_trees_nested_too_deeply() {
+    fn nested(depth: usize) -> Program {
+        let mut expr = ident("a", 0);
+        for _ in 0..depth {
+            expr = Expression::Unary(Box::new(Unary {
+                operator: UnaryOperator::Not,
+                prefix: true,
+                argument: expr,
+                loc: loc(0, 1),
+            }));
+        }
+        script(vec![Statement::Expr(expr)], 1)
+    }
+    // building and dropping the tree recurses too
+    let deep = ::std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(|| {
+            let shallow = nested(100);
+            assert_eq!(binary::decode(&binary::encode(&shallow)), Ok(shallow));
+            binary::decode(&binary::encode(&nested(MAX_DEPTH)))
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert_eq!(deep, Err(Error::TooDeep));
+}
@@ -0,0 +1,549 @@
+extern crate res_tree;
+mod common;
+use common::{id, ident, loc, number, script};
+use res_tree::codegen::{minify, print, print_node, print_with, Indent, PrintOptions, Quote};
+use res_tree::expression::*;
+use res_tree::node_ref::NodeRef;
+use res_tree::statement::*;
+use res_tree::{Directive, Function, Program, ProgramPart};
+
+fn binary(operator: BinaryOperator, left: Expression, right: Expression) -> Expression {
+    Expression::Binary(Box::new(Binary {
+        operator,
+        left,
+        right,
+        loc: loc(0, 0),
+    }))
+}
+
+fn member(object: Expression, name: &str) -> Expression {
+    Expression::Member(Box::new(Member {
+        object: Parent::Expr(object),
+        property: ident(name, 0),
+        computed: false,
+        optional: false,
+        loc: loc(0, 0),
+    }))
+}
+
+fn call(callee: Expression) -> Expression {
+    Expression::Call(Box::new(Call {
+        callee: Parent::Expr(callee),
+        arguments: Vec::new(),
+        optional: false,
+        loc: loc(0, 0),
+    }))
+}
+
+fn object() -> Expression {
+    Expression::Object(Object {
+        properties: vec![Property {
+            key: ident("a", 0),
+            value: number("1", 0),
+            kind: PropertyKind::Init,
+            method: false,
+            shorthand: false,
+            computed: false,
+            loc: loc(0, 0),
+        }],
+        loc: loc(0, 0),
+    })
+}
+
+fn function() -> Expression {
+    Expression::Function(Box::new(Function {
+        id: None,
+        params: Vec::new(),
+        body: FunctionBody {
+            body: Vec::new(),
+            loc: loc(0, 0),
+        },
+        generator: false,
+        _async: false,
+        loc: loc(0, 0),
+    }))
+}
+
+fn unary(operator: UnaryOperator, argument: Expression) -> Expression {
+    Expression::Unary(Box::new(Unary {
+        operator,
+        prefix: true,
+        argument,
+        loc: loc(0, 0),
+    }))
+}
+
+fn expr(expr: &Expression) -> String {
+    print_node(NodeRef::Expression(expr))
+}
+
+fn statement(expr: Expression) -> String {
+    print(&script(vec![Statement::Expr(expr)], 0))
+}
+
+#[test]
+fn parens_follow_precedence() {
+    use res_tree::expression::BinaryOperator::*;
+    let sum = binary(Plus, ident("a", 0), ident("b", 0));
+    assert_eq!(
+        expr(&binary(Times, sum.clone(), ident("c", 0))),
+        "(a + b) * c"
+    );
+    assert_eq!(
+        expr(&binary(Times, ident("c", 0), sum.clone())),
+        "c * (a + b)"
+    );
+    assert_eq!(expr(&binary(Plus, sum.clone(), ident("c", 0))), "a + b + c");
+    assert_eq!(
+        expr(&binary(Minus, ident("c", 0), sum.clone())),
+        "c - (a + b)"
+    );
+
+    let either = Expression::Logical(Box::new(Logical {
+        operator: LogicalOperator::Or,
+        left: ident("a", 0),
+        right: ident("b", 0),
+        loc: loc(0, 0),
+    }));
+    let both = Expression::Logical(Box::new(Logical {
+        operator: LogicalOperator::And,
+        left: either.clone(),
+        right: ident("c", 0),
+        loc: loc(0, 0),
+    }));
+    assert_eq!(expr(&both), "(a || b) && c");
+
+    let sequence = Expression::Sequence(Sequence {
+        expressions: vec![ident("a", 0), ident("b", 0)],
+        loc: loc(0, 0),
+    });
+    let conditional = Expression::Conditional(Box::new(Conditional {
+        test: either,
+        consequent: sequence.clone(),
+        alternate: Expression::Yield(Box::new(Yield {
+            argument: Some(ident("c", 0)),
+            delegate: false,
+            loc: loc(0, 0),
+        })),
+        loc: loc(0, 0),
+    }));
+    assert_eq!(expr(&conditional), "a || b ? (a, b) : yield c");
+
+    let arrow = Expression::ArrowFunction(Box::new(ArrowFunction {
+        id: None,
+        params: vec![res_tree::Pattern::Ident(id("x", 0))],
+        body: ArrowFunctionBody::Expr(sequence),
+        generator: false,
+        _async: false,
+        loc: loc(0, 0),
+    }));
+    assert_eq!(expr(&call(arrow.clone())), "((x) => (a, b))()");
+    assert_eq!(
+        expr(&member(binary(Plus, arrow, sum), "d")),
+        "(((x) => (a, b)) + (a + b)).d"
+    );
+}
+
+#[test]
+fn members_calls_and_new() {
+    assert_eq!(expr(&member(number("1", 0), "toFixed")), "(1).toFixed");
+    assert_eq!(expr(&member(number("1.5", 0), "toFixed")), "1.5.toFixed");
+
+    let new = |callee| {
+        Expression::New(Box::new(New {
+            callee,
+            arguments: Vec::new(),
+            loc: loc(0, 0),
+        }))
+    };
+    assert_eq!(
+        expr(&new(member(call(ident("a", 0)), "b"))),
+        "new (a().b)()"
+    );
+    assert_eq!(expr(&new(member(ident("a", 0), "b"))), "new a.b()");
+    assert_eq!(expr(&member(new(ident("a", 0)), "b")), "new a().b");
+
+    let chain = Expression::Chain(Box::new(Chain {
+        expression: Expression::Member(Box::new(Member {
+            object: Parent::Expr(ident("a", 0)),
+            property: ident("b", 0),
+            computed: false,
+            optional: true,
+            loc: loc(0, 0),
+        })),
+        loc: loc(0, 0),
+    }));
+    assert_eq!(expr(&chain), "a?.b");
+    assert_eq!(expr(&member(chain, "c")), "(a?.b).c");
+}
+
+#[test]
+fn statements_cannot_start_ambiguously() {
+    use res_tree::expression::BinaryOperator::*;
+    assert_eq!(statement(object()), "({a: 1});\n");
+    assert_eq!(statement(call(function())), "(function() {}());\n");
+    assert_eq!(
+        statement(binary(Plus, member(object(), "a"), number("1", 0))),
+        "({a: 1}.a + 1);\n"
+    );
+    let let_index = Expression::Member(Box::new(Member {
+        object: Parent::Expr(ident("let", 0)),
+        property: number("0", 0),
+        computed: true,
+        optional: false,
+        loc: loc(0, 0),
+    }));
+    assert_eq!(statement(let_index), "(let[0]);\n");
+    let string = Expression::Literal(Box::new(Literal {
+        value: LiteralValue::String("use strict".to_string()),
+        loc: loc(0, 0),
+    }));
+    assert_eq!(statement(string), "(\"use strict\");\n");
+
+    let arrow = Expression::ArrowFunction(Box::new(ArrowFunction {
+        id: None,
+        params: Vec::new(),
+        body: ArrowFunctionBody::Expr(object()),
+        generator: false,
+        _async: false,
+        loc: loc(0, 0),
+    }));
+    assert_eq!(expr(&arrow), "() => ({a: 1})");
+}
+
+#[test]
+fn tokens_do_not_merge() {
+    use res_tree::expression::UnaryOperator::*;
+    assert_eq!(expr(&unary(Minus, unary(Minus, ident("a", 0)))), "- -a");
+    let plus = binary(
+        BinaryOperator::Plus,
+        ident("a", 0),
+        unary(Plus, ident("b", 0)),
+    );
+    assert_eq!(expr(&plus), "a + +b");
+    assert_eq!(expr(&unary(TypeOf, ident("a", 0))), "typeof a");
+    assert_eq!(expr(&unary(Not, unary(Void, number("0", 0)))), "!void 0");
+}
+
+#[test]
+fn for_heads_and_dangling_else() {
+    use res_tree::expression::BinaryOperator::*;
+    let for_loop = Statement::For(Box::new(For {
+        init: ForInit::Expr(binary(
+            Plus,
+            binary(In, ident("a", 0), ident("b", 0)),
+            number("1", 0),
+        )),
+        test: None,
+        update: None,
+        body: Statement::Empty(Empty { loc: loc(0, 0) }),
+        loc: loc(0, 0),
+    }));
+    assert_eq!(print(&script(vec![for_loop], 0)), "for ((a in b) + 1;;);\n");
+
+    let inner = Statement::If(Box::new(If {
+        test: ident("b", 0),
+        consequent: Statement::Expr(call(ident("f", 0))),
+        alternate: None,
+        loc: loc(0, 0),
+    }));
+    let outer = Statement::If(Box::new(If {
+        test: ident("a", 0),
+        consequent: inner,
+        alternate: Some(Statement::Expr(call(ident("g", 0)))),
+        loc: loc(0, 0),
+    }));
+    assert_eq!(
+        print(&script(vec![outer], 0)),
+        "if (a) {\n  if (b) f();\n} else g();\n"
+    );
+}
+
+fn using(kind: VariableKind, init: Option<Expression>) -> VariableDeclaration {
+    VariableDeclaration {
+        declarations: vec![VariableDeclarator {
+            id: res_tree::Pattern::Ident(id("x", 0)),
+            init,
+            loc: loc(0, 0),
+        }],
+        kind,
+        loc: loc(0, 0),
+    }
+}
+
+#[test]
+fn using_in_loop_heads() {
+    let empty = || Statement::Empty(Empty { loc: loc(0, 0) });
+    let for_loop = Statement::For(Box::new(For {
+        init: ForInit::Var(using(VariableKind::Using, Some(ident("a", 0)))),
+        test: None,
+        update: None,
+        body: empty(),
+        loc: loc(0, 0),
+    }));
+    let for_in = Statement::ForIn(Box::new(ForIn {
+        left: ForLeft::Var(using(VariableKind::Using, None)),
+        right: ident("a", 0),
+        body: empty(),
+        loc: loc(0, 0),
+    }));
+    let for_of = Statement::ForOf(Box::new(ForOf {
+        left: ForLeft::Var(using(VariableKind::AwaitUsing, None)),
+        right: ident("a", 0),
+        body: empty(),
+        _await: true,
+        loc: loc(0, 0),
+    }));
+    assert_eq!(
+        print(&script(vec![for_loop, for_in, for_of], 0)),
+        "for (using x = a;;);
+for (using x in a);
+for await (await using x of a);
+"
+    );
+}
+
+fn string(value: &str) -> Expression {
+    Expression::Literal(Box::new(Literal {
+        value: LiteralValue::String(value.to_string()),
+        loc: loc(0, 0),
+    }))
+}
+
+#[test]
+fn minified() {
+    let arguments = [
+        "1000",
+        "0.50",
+        "0x10",
+        "1_000_000",
+        "123456789012",
+        "1.5e-7",
+        "10n",
+    ]
+    .iter()
+    .map(|n| number(n, 0))
+    .chain(vec![string("it's"), string("say \"hi\"")])
+    .map(Spreadable::Expr)
+    .collect();
+    let first = Statement::Expr(Expression::Call(Box::new(Call {
+        callee: Parent::Expr(ident("f", 0)),
+        arguments,
+        optional: false,
+        loc: loc(0, 0),
+    })));
+    let branch = Statement::If(Box::new(If {
+        test: ident("a", 0),
+        consequent: Statement::Block(Block {
+            body: vec![first, Statement::Expr(call(ident("g", 0)))],
+            loc: loc(0, 0),
+        }),
+        alternate: Some(Statement::Empty(Empty { loc: loc(0, 0) })),
+        loc: loc(0, 0),
+    }));
+    let for_loop = Statement::For(Box::new(For {
+        init: ForInit::Null,
+        test: None,
+        update: None,
+        body: Statement::Block(Block {
+            body: Vec::new(),
+            loc: loc(0, 0),
+        }),
+        loc: loc(0, 0),
+    }));
+    let regex = Expression::Literal(Box::new(Literal {
+        value: LiteralValue::RegExp(RegExpLiteral {
+            regex: RegEx {
+                pattern: "re".to_string(),
+                flags: String::new(),
+                loc: loc(0, 0),
+            },
+            loc: loc(0, 0),
+        }),
+        loc: loc(0, 0),
+    }));
+    let instance = binary(BinaryOperator::InstanceOf, regex, ident("y", 0));
+    let program = script(
+        vec![
+            branch,
+            for_loop,
+            Statement::Expr(instance),
+            Statement::Expr(member(number("1000", 0), "toFixed")),
+        ],
+        0,
+    );
+    assert_eq!(
+        minify(&program),
+        "if(a){f(1e3,.5,16,1e6,123456789012,15e-8,10n,\"it's\",'say \"hi\"');g()}else;\
+         for(;;){}/re/ instanceof y;1e3.toFixed"
+    );
+}
+
+fn array(elements: Vec<Expression>) -> Expression {
+    Expression::Array(Array {
+        elements: elements
+            .into_iter()
+            .map(|e| Some(Spreadable::Expr(e)))
+            .collect(),
+        loc: loc(0, 0),
+    })
+}
+
+fn call_with(callee: &str, arguments: Vec<Expression>) -> Expression {
+    Expression::Call(Box::new(Call {
+        callee: Parent::Expr(ident(callee, 0)),
+        arguments: arguments.into_iter().map(Spreadable::Expr).collect(),
+        optional: false,
+        loc: loc(0, 0),
+    }))
+}
+
+#[test]
+fn style_options() {
+    let assign = Expression::Assignment(Box::new(Assignment {
+        operator: AssignmentOperator::Assign,
+        left: PatOrExpr::Pat(res_tree::Pattern::Ident(id("a", 0))),
+        right: string("x"),
+        loc: loc(0, 0),
+    }));
+    let each = Expression::Call(Box::new(Call {
+        callee: Parent::Expr(member(
+            array(vec![number("1", 0), number("2", 0)]),
+            "forEach",
+        )),
+        arguments: vec![Spreadable::Expr(ident("g", 0))],
+        optional: false,
+        loc: loc(0, 0),
+    }));
+    let branch = Statement::If(Box::new(If {
+        test: ident("a", 0),
+        consequent: Statement::Block(Block {
+            body: vec![Statement::Expr(assign), Statement::Expr(each)],
+            loc: loc(0, 0),
+        }),
+        alternate: None,
+        loc: loc(0, 0),
+    }));
+    let options = PrintOptions {
+        indent: Indent::Tabs,
+        quote: Quote::Single,
+        semicolons: false,
+        ..PrintOptions::default()
+    };
+    assert_eq!(
+        print_with(&script(vec![branch], 0), options),
+        "if (a) {\n\ta = 'x'\n\t;[1, 2].forEach(g)\n}\n"
+    );
+}
+
+#[test]
+fn long_lists_break() {
+    let inner = array(vec![number("1", 0), number("2", 0)]);
+    let long = call_with(
+        "call",
+        vec![
+            ident("alpha", 0),
+            ident("beta", 0),
+            ident("gamma", 0),
+            inner.clone(),
+        ],
+    );
+    let short = call_with("f", vec![inner]);
+    let program = script(vec![Statement::Expr(long), Statement::Expr(short)], 0);
+    let options = PrintOptions {
+        indent: Indent::Spaces(4),
+        trailing_commas: true,
+        print_width: 24,
+        ..PrintOptions::default()
+    };
+    assert_eq!(
+        print_with(&program, options),
+        "call(\n    alpha,\n    beta,\n    gamma,\n    [1, 2],\n);\nf([1, 2]);\n"
+    );
+    assert_eq!(
+        print(&program),
+        "call(alpha, beta, gamma, [1, 2]);\nf([1, 2]);\n"
+    );
+}
+
+#[test]
+fn text_after_a_list_counts_toward_its_line() {
+    use res_tree::expression::BinaryOperator::*;
+    // `call(alpha, beta)` fits, but not with the rest of its line
+    let sum = binary(
+        Plus,
+        binary(
+            Plus,
+            call_with("call", vec![ident("alpha", 0), ident("beta", 0)]),
+            ident("gamma", 0),
+        ),
+        ident("delta", 0),
+    );
+    let options = PrintOptions {
+        indent: Indent::Spaces(4),
+        print_width: 24,
+        ..PrintOptions::default()
+    };
+    assert_eq!(
+        print_with(&script(vec![Statement::Expr(sum)], 0), options),
+        "call(\n    alpha,\n    beta\n) + gamma + delta;\n"
+    );
+}
+
+#[test]
+fn semicolons_stay_on_their_statement() {
+    let branch = Statement::If(Box::new(If {
+        test: ident("a", 0),
+        consequent: Statement::Expr(ident("b", 0)),
+        alternate: Some(Statement::Expr(ident("c", 0))),
+        loc: loc(0, 0),
+    }));
+    let repeat = Statement::DoWhile(Box::new(DoWhile {
+        body: Statement::Expr(ident("x", 0)),
+        test: ident("a", 0),
+        loc: loc(0, 0),
+    }));
+    let options = PrintOptions {
+        semicolons: false,
+        ..PrintOptions::default()
+    };
+    assert_eq!(
+        print_with(&script(vec![branch, repeat], 0), options),
+        "if (a) b; else c\ndo x; while (a)\n"
+    );
+}
+
+fn directive(raw: &str) -> ProgramPart {
+    ProgramPart::Directive(Directive {
+        expression: Literal {
+            value: LiteralValue::String(raw.to_string()),
+            loc: loc(0, 0),
+        },
+        directive: raw.to_string(),
+        loc: loc(0, 0),
+    })
+}
+
+#[test]
+fn directives_keep_their_raw_text() {
+    let program = Program {
+        body: vec![
+            directive(r#"say \"hi\""#),
+            directive("it's"),
+            directive(r"a\'b"),
+        ],
+        ..script(Vec::new(), 0)
+    };
+    assert_eq!(
+        print(&program),
+        "\"say \\\"hi\\\"\";\n\"it's\";\n\"a\\'b\";\n"
+    );
+    let single = PrintOptions {
+        quote: Quote::Single,
+        ..PrintOptions::default()
+    };
+    assert_eq!(
+        print_with(&program, single),
+        "'say \\\"hi\\\"';\n\"it's\";\n'a\\'b';\n"
+    );
+}
@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+use res_tree::expression::{Binary, BinaryOperator, Expression, Identifier, Literal, LiteralValue};
+use res_tree::node::{Position, Range, SourceLocation};
+use res_tree::statement::Statement;
+use res_tree::{Program, ProgramPart, SourceType};
+use std::num::NonZeroU64;
+
+pub fn pos(column: u64) -> Position {
+    Position {
+        line: NonZeroU64::new(1).unwrap(),
+        column,
+    }
+}
+
+pub fn loc(start: u64, end: u64) -> SourceLocation {
+    SourceLocation {
+        source: None,
+        start: pos(start),
+        end: pos(end),
+        range: Range::new(start as usize, end as usize),
+    }
+}
+
+pub fn id(name: &str, start: u64) -> Identifier {
+    Identifier {
+        name: name.to_string(),
+        loc: loc(start, start + name.len() as u64),
+    }
+}
+
+pub fn ident(name: &str, start: u64) -> Expression {
+    Expression::Identifier(Box::new(id(name, start)))
+}
+
+pub fn number(value: &str, start: u64) -> Expression {
+    Expression::Literal(Box::new(Literal {
+        value: LiteralValue::Number(value.to_string()),
+        loc: loc(start, start + value.len() as u64),
+    }))
+}
+
+pub fn script(body: Vec<Statement>, end: u64) -> Program {
+    Program {
+        source_type: SourceType::Script,
+        body: body.into_iter().map(ProgramPart::Statement).collect(),
+        loc: loc(0, end),
+    }
+}
+/// `a + bc;`
+pub fn sum() -> Program {
+    script(
+        vec![Statement::Expr(Expression::Binary(Box::new(Binary {
+            operator: BinaryOperator::Plus,
+            left: ident("a", 0),
+            right: ident("bc", 4),
+            loc: loc(0, 6),
+        })))],
+        7,
+    )
+}
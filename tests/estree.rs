@@ -0,0 +1,350 @@
+#![cfg(feature = "serde")]
+extern crate res_tree;
+#[macro_use]
+extern crate serde_json;
+mod common;
+use common::{ident, loc, number, script};
+use res_tree::estree::{self, ErrorKind};
+use res_tree::expression::{Binary, BinaryOperator, Expression, Literal, LiteralValue, Yield};
+use res_tree::node::{Node, SourceRegistry};
+use res_tree::statement::Statement;
+use std::io::{self, Write};
+
+fn loc_json(start: u64, end: u64) -> serde_json::Value {
+    json!({
+        "start": { "line": 1, "column": start },
+        "end": { "line": 1, "column": end },
+    })
+}
+
+#[test]
+fn program_shape() {
+    // `a + 1;`
+    let program = script(
+        vec![Statement::Expr(Expression::Binary(Box::new(Binary {
+            operator: BinaryOperator::Plus,
+            left: ident("a", 0),
+            right: number("1", 4),
+            loc: loc(0, 5),
+        })))],
+        6,
+    );
+    let expected = json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": [{
+            "type": "ExpressionStatement",
+            "expression": {
+                "type": "BinaryExpression",
+                "operator": "+",
+                "left": {
+                    "type": "Identifier",
+                    "name": "a",
+                    "loc": loc_json(0, 1),
+                    "range": [0, 1],
+                },
+                "right": {
+                    "type": "Literal",
+                    "value": 1,
+                    "raw": "1",
+                    "loc": loc_json(4, 5),
+                    "range": [4, 5],
+                },
+                "loc": loc_json(0, 5),
+                "range": [0, 5],
+            },
+            "loc": loc_json(0, 5),
+            "range": [0, 5],
+        }],
+        "loc": loc_json(0, 6),
+        "range": [0, 6],
+    });
+    assert_eq!(serde_json::to_value(&program).unwrap(), expected);
+}
+
+#[test]
+fn literals_and_yield() {
+    let string = Literal {
+        value: LiteralValue::String("say \"hi\"".to_string()),
+        loc: loc(0, 10),
+    };
+    let json = serde_json::to_value(&string).unwrap();
+    assert_eq!(json["value"], "say \"hi\"");
+    assert_eq!(json["raw"], "\"say \\\"hi\\\"\"");
+
+    let hex = serde_json::to_value(number("0xff", 0)).unwrap();
+    assert_eq!(hex["value"], 255);
+    assert_eq!(hex["raw"], "0xff");
+    let float = serde_json::to_value(number("1.5e3", 0)).unwrap();
+    assert_eq!(float["value"], 1500);
+    let half = serde_json::to_value(number(".5", 0)).unwrap();
+    assert_eq!(half["value"], 0.5);
+    let octal = serde_json::to_value(number("010", 0)).unwrap();
+    assert_eq!(octal["value"], 8);
+    let decimal = serde_json::to_value(number("09", 0)).unwrap();
+    assert_eq!(decimal["value"], 9);
+    let big = serde_json::to_value(number("1_000n", 0)).unwrap();
+    assert_eq!(big["value"], serde_json::Value::Null);
+    assert_eq!(big["bigint"], "1000");
+    assert_eq!(big["raw"], "1_000n");
+
+    let delegate = Expression::Yield(Box::new(Yield {
+        argument: Some(ident("g", 7)),
+        delegate: true,
+        loc: loc(0, 8),
+    }));
+    let json = serde_json::to_value(&delegate).unwrap();
+    assert_eq!(json["type"], "YieldExpression");
+    assert_eq!(json["delegate"], true);
+    assert_eq!(json["argument"]["name"], "g");
+}
+
+#[test]
+fn sources_give_raw_text_names_and_utf16_columns() {
+    let mut registry = SourceRegistry::new();
+    let file = registry.add_with_text("a.js", r"'\u{1F600}😀';".to_string());
+    let mut string = Literal {
+        value: LiteralValue::String("😀😀".to_string()),
+        loc: loc(0, 15),
+    };
+    string.loc.source = Some(file);
+    let json = serde_json::to_value(estree::with_sources(&string, &registry)).unwrap();
+    assert_eq!(json["raw"], "'\\u{1F600}😀'");
+    assert_eq!(json["loc"]["source"], "a.js");
+    assert_eq!(json["loc"]["end"]["column"], 13);
+    assert_eq!(json["range"], json!([0, 15]));
+
+    // without the registry the raw text is made up from the value
+    let json = serde_json::to_value(&string).unwrap();
+    assert_eq!(json["raw"], "\"😀😀\"");
+    assert_eq!(json["loc"]["source"], serde_json::Value::Null);
+    assert_eq!(json["loc"]["end"]["column"], 15);
+}
+
+#[test]
+fn reads_back_what_it_writes() {
+    // `a + 0xff;`
+    let program = script(
+        vec![Statement::Expr(Expression::Binary(Box::new(Binary {
+            operator: BinaryOperator::Plus,
+            left: ident("a", 0),
+            right: number("0xff", 4),
+            loc: loc(0, 8),
+        })))],
+        9,
+    );
+    let json = serde_json::to_value(&program).unwrap();
+    assert_eq!(estree::from_value(&json).unwrap(), program);
+    let text = serde_json::to_string(&program).unwrap();
+    assert_eq!(estree::from_str(&text).unwrap(), program);
+}
+
+#[test]
+fn reads_back_literals_without_a_json_value() {
+    // `1_000n; 1e400; null;`
+    let program = script(
+        vec![
+            Statement::Expr(number("1_000n", 0)),
+            Statement::Expr(number("1e400", 8)),
+            Statement::Expr(Expression::Literal(Box::new(Literal {
+                value: LiteralValue::Null,
+                loc: loc(15, 19),
+            }))),
+        ],
+        20,
+    );
+    let text = serde_json::to_string(&program).unwrap();
+    assert_eq!(estree::from_str(&text).unwrap(), program);
+
+    // a BigInt from a tool that doesn't keep the raw text
+    let bigint = json!({
+        "type": "Literal",
+        "value": null,
+        "bigint": "1000",
+        "loc": loc_json(0, 5),
+        "range": [0, 5],
+    });
+    let json = json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": [{
+            "type": "ExpressionStatement",
+            "expression": bigint,
+            "loc": loc_json(0, 5),
+            "range": [0, 5],
+        }],
+        "loc": loc_json(0, 5),
+        "range": [0, 5],
+    });
+    let program = estree::from_value(&json).unwrap();
+    assert_eq!(
+        program.body,
+        script(vec![Statement::Expr(number("1000n", 0))], 0).body
+    );
+}
+
+#[test]
+fn reads_acorn_offsets() {
+    let json = json!({
+        "type": "Program",
+        "start": 0,
+        "end": 2,
+        "sourceType": "module",
+        "body": [{
+            "type": "ExpressionStatement",
+            "start": 0,
+            "end": 2,
+            "expression": { "type": "Identifier", "start": 0, "end": 1, "name": "a" },
+        }],
+    });
+    // without the text there's no way to tell the lines and columns
+    let err = estree::from_value(&json).unwrap_err();
+    assert_eq!(err.path(), "$.body[0].expression");
+    match err.kind() {
+        ErrorKind::MissingField("loc") => (),
+        other => panic!("unexpected error {:?}", other),
+    }
+
+    let mut registry = SourceRegistry::new();
+    let file = registry.add_with_text("a.js", "a;".to_string());
+    let program = estree::from_value_with_source(&json, &registry, file).unwrap();
+    assert_eq!(program.loc.range.end, 2);
+    match program.body[0] {
+        res_tree::ProgramPart::Statement(Statement::Expr(Expression::Identifier(ref id))) => {
+            assert_eq!(id.name, "a");
+            assert_eq!(id.loc.range.end, 1);
+            assert_eq!(id.loc.end.column, 1);
+            assert_eq!(id.loc.source, Some(file));
+        }
+        ref other => panic!("expected an identifier statement, found {:?}", other),
+    }
+}
+
+#[test]
+fn reads_utf16_columns_with_the_source_text() {
+    // `"😀";b`, where acorn puts `b` at column 5 and offset 7
+    let text = "\"😀\";b";
+    let columns = |start: u64, end: u64| {
+        json!({
+            "start": { "line": 1, "column": start },
+            "end": { "line": 1, "column": end },
+        })
+    };
+    let json = json!({
+        "type": "Program",
+        "sourceType": "script",
+        "loc": columns(0, 6),
+        "range": [0, 8],
+        "body": [{
+            "type": "ExpressionStatement",
+            "loc": columns(5, 6),
+            "range": [7, 8],
+            "expression": { "type": "Identifier", "loc": columns(5, 6), "range": [7, 8], "name": "b" },
+        }],
+    });
+    let mut registry = SourceRegistry::new();
+    let file = registry.add_with_text("a.js", text.to_string());
+    let program = estree::from_value_with_source(&json, &registry, file).unwrap();
+    assert_eq!(program.loc.end.column, 8);
+    match program.body[0] {
+        res_tree::ProgramPart::Statement(ref stmt) => assert_eq!(stmt.loc().start.column, 7),
+        ref other => panic!("expected a statement, found {:?}", other),
+    }
+
+    // a column inside of the emoji's surrogate pair is an error
+    let mut split = json.clone();
+    split["body"][0]["expression"]["loc"] = columns(2, 6);
+    let err = estree::from_value_with_source(&split, &registry, file).unwrap_err();
+    assert_eq!(err.path(), "$.body[0].expression.loc.start");
+}
+
+#[test]
+fn errors_point_at_the_json_path() {
+    let unknown = json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": [{
+            "type": "ExpressionStatement",
+            "expression": {
+                "type": "BinaryExpression",
+                "operator": "+",
+                "left": { "type": "JSXElement" },
+                "right": { "type": "Identifier", "name": "b" },
+            },
+        }],
+    });
+    let err = estree::from_value(&unknown).unwrap_err();
+    assert_eq!(err.path(), "$.body[0].expression.left");
+    match *err.kind() {
+        ErrorKind::UnknownType { ref found, .. } => assert_eq!(found, "JSXElement"),
+        ref other => panic!("expected an unknown type, found {:?}", other),
+    }
+    assert_eq!(
+        err.to_string(),
+        "unknown node type `JSXElement`, expected an expression at $.body[0].expression.left"
+    );
+
+    let missing = json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": [{ "type": "ThrowStatement" }],
+    });
+    let err = estree::from_value(&missing).unwrap_err();
+    assert_eq!(err.path(), "$.body[0]");
+    assert_eq!(err.to_string(), "missing field `argument` at $.body[0]");
+
+    assert!(estree::from_str("{").is_err());
+}
+
+/// Records the size of the largest single write
+#[derive(Default)]
+struct Chunks {
+    total: usize,
+    largest: usize,
+}
+
+impl Write for Chunks {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.total += buf.len();
+        self.largest = self.largest.max(buf.len());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Full;
+
+impl Write for Full {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::other("disk full"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writer_streams_small_pieces() {
+    let statements = (0..5_000)
+        .map(|idx| Statement::Expr(number("1", idx * 3)))
+        .collect();
+    let program = script(statements, 15_000);
+    let mut chunks = Chunks::default();
+    estree::to_writer(&mut chunks, &program).unwrap();
+    assert_eq!(chunks.total, serde_json::to_vec(&program).unwrap().len());
+    assert!(chunks.largest < 64, "largest write {}", chunks.largest);
+
+    let mut pretty = Vec::new();
+    estree::to_writer_pretty(&mut pretty, &program).unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&pretty).unwrap(),
+        serde_json::to_value(&program).unwrap()
+    );
+
+    let err = estree::to_writer(Full, &program).unwrap_err();
+    assert_eq!(err.to_string(), "disk full");
+}
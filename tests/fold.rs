@@ -0,0 +1,189 @@
+extern crate res_tree;
+mod common;
+use common::{id, ident, loc, number, script};
+use res_tree::expression::{ArrowFunction, ArrowFunctionBody, Expression, Identifier};
+use res_tree::fold::{self, Fold, TryFold};
+use res_tree::node::{Node, NodeKind};
+use res_tree::statement::{
+    Debugger, Declaration, FunctionBody, FunctionBodyPart, Return, Statement, VariableDeclaration,
+    VariableDeclarator, VariableKind,
+};
+use res_tree::{Function, Pattern, ProgramPart};
+use std::ops::ControlFlow;
+
+struct ArrowToFunction;
+
+impl Fold for ArrowToFunction {
+    fn fold_expression(&mut self, expr: Expression) -> Expression {
+        match fold::walk_expression(self, expr) {
+            Expression::ArrowFunction(arrow) => {
+                let arrow = *arrow;
+                let body = match arrow.body {
+                    ArrowFunctionBody::Block(body) => body,
+                    ArrowFunctionBody::Expr(expr) => FunctionBody {
+                        loc: expr.loc(),
+                        body: vec![FunctionBodyPart::Statement(Statement::Return(Return {
+                            loc: expr.loc(),
+                            argument: Some(expr),
+                        }))],
+                    },
+                };
+                Expression::Function(Box::new(Function {
+                    id: arrow.id,
+                    params: arrow.params,
+                    body,
+                    generator: arrow.generator,
+                    _async: arrow._async,
+                    loc: arrow.loc,
+                }))
+            }
+            other => other,
+        }
+    }
+}
+/// Drops `debugger` statements and splits multi-declarator
+/// `let` declarations into one declaration per declarator
+struct Normalize;
+
+impl Fold for Normalize {
+    fn fold_statement_many(&mut self, stmt: Statement) -> Vec<Statement> {
+        match fold::walk_statement(self, stmt) {
+            Statement::Debugger(_) => Vec::new(),
+            Statement::Decl(Declaration::Variable(decl)) => decl
+                .declarations
+                .into_iter()
+                .map(|d| {
+                    Statement::Decl(Declaration::Variable(VariableDeclaration {
+                        loc: d.loc,
+                        declarations: vec![d],
+                        kind: VariableKind::Let,
+                    }))
+                })
+                .collect(),
+            other => vec![other],
+        }
+    }
+}
+
+fn declarator(name: &str, start: u64) -> VariableDeclarator {
+    VariableDeclarator {
+        id: Pattern::Ident(id(name, start)),
+        init: Some(number("1", start + 4)),
+        loc: loc(start, start + 5),
+    }
+}
+
+#[test]
+fn arrow_becomes_function() {
+    // `(x => x);`
+    let program = script(
+        vec![Statement::Expr(Expression::ArrowFunction(Box::new(
+            ArrowFunction {
+                id: None,
+                params: vec![Pattern::Ident(id("x", 1))],
+                generator: false,
+                _async: false,
+                body: ArrowFunctionBody::Expr(ident("x", 6)),
+                loc: loc(1, 7),
+            },
+        )))],
+        9,
+    );
+    let original = program.clone();
+    let program = ArrowToFunction.fold_program(program);
+    assert_ne!(program, original);
+    assert_eq!(ArrowToFunction.fold_program(original.clone()), program);
+    match program.body[0] {
+        ProgramPart::Statement(Statement::Expr(ref e)) => match e.kind() {
+            NodeKind::FunctionExpression => (),
+            other => panic!("expected a function expression, found {:?}", other),
+        },
+        _ => panic!("expected an expression statement"),
+    }
+}
+
+#[test]
+fn statements_fold_to_zero_or_many() {
+    // `debugger; let a = 1, b = 1;`
+    let program = script(
+        vec![
+            Statement::Debugger(Debugger { loc: loc(0, 9) }),
+            Statement::Decl(Declaration::Variable(VariableDeclaration {
+                declarations: vec![declarator("a", 14), declarator("b", 21)],
+                kind: VariableKind::Let,
+                loc: loc(10, 27),
+            })),
+        ],
+        27,
+    );
+    let program = Normalize.fold_program(program);
+    assert_eq!(program.body.len(), 2);
+    for part in &program.body {
+        match part {
+            ProgramPart::Statement(Statement::Decl(Declaration::Variable(ref v))) => {
+                assert_eq!(v.declarations.len(), 1)
+            }
+            _ => panic!("expected a variable declaration"),
+        }
+    }
+}
+
+/// Prefixes identifiers with `_` until it reaches `eval`, then
+/// stops with the number it had renamed
+struct RenameUntilEval {
+    renamed: usize,
+}
+
+impl TryFold<usize> for RenameUntilEval {
+    fn try_fold_identifier(&mut self, ident: Identifier) -> ControlFlow<usize, Identifier> {
+        if ident.name == "eval" {
+            return ControlFlow::Break(self.renamed);
+        }
+        self.renamed += 1;
+        ControlFlow::Continue(Identifier {
+            name: format!("_{}", ident.name),
+            ..ident
+        })
+    }
+}
+
+#[test]
+fn try_fold_stops_early() {
+    // `a; eval; b;`
+    let program = script(
+        vec![
+            Statement::Expr(ident("a", 0)),
+            Statement::Expr(ident("eval", 3)),
+            Statement::Expr(ident("b", 9)),
+        ],
+        11,
+    );
+    let mut folder = RenameUntilEval { renamed: 0 };
+    assert_eq!(folder.try_fold_program(program), ControlFlow::Break(1));
+    assert_eq!(folder.renamed, 1);
+
+    // `a; b;`
+    let program = script(
+        vec![
+            Statement::Expr(ident("a", 0)),
+            Statement::Expr(ident("b", 3)),
+        ],
+        5,
+    );
+    let mut folder = RenameUntilEval { renamed: 0 };
+    let folded = match folder.try_fold_program(program.clone()) {
+        ControlFlow::Continue(folded) => folded,
+        ControlFlow::Break(_) => panic!("expected the fold to finish"),
+    };
+    assert_eq!(folded.body.len(), 2);
+    match folded.body[1] {
+        ProgramPart::Statement(Statement::Expr(Expression::Identifier(ref i))) => {
+            assert_eq!(i.name, "_b")
+        }
+        _ => panic!("expected an identifier statement"),
+    }
+    assert_eq!(
+        ArrowToFunction.try_fold_program(program.clone()),
+        ControlFlow::Continue(program)
+    );
+}
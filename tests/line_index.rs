@@ -0,0 +1,77 @@
+extern crate res_tree;
+use res_tree::line_index::LineIndex;
+use res_tree::node::{Position, Range};
+use std::num::NonZeroU64;
+
+fn pos(line: u64, column: u64) -> Position {
+    Position {
+        line: NonZeroU64::new(line).unwrap(),
+        column,
+    }
+}
+
+#[test]
+fn line_terminators() {
+    let text = "a\nb\r\nc\rd\u{2028}e\u{2029}f";
+    let index = LineIndex::new(text);
+    assert_eq!(index.line_count(), 6);
+    for (line, ch) in ["a", "b", "c", "d", "e", "f"].iter().enumerate() {
+        let offset = text.find(ch).unwrap();
+        let p = index.position(offset).unwrap();
+        assert_eq!((p.line.get(), p.column), (line as u64 + 1, 0));
+        assert_eq!(index.offset(p), Some(offset));
+    }
+    assert!(index.position(text.len() + 1).is_none());
+    assert_eq!(
+        index
+            .line_range(NonZeroU64::new(2).unwrap())
+            .unwrap()
+            .slice(text),
+        "b\r\n"
+    );
+}
+
+#[test]
+fn utf16_columns() {
+    let text = "x\nlet é = '😀'; y";
+    let index = LineIndex::new(text);
+    let y = index.position(text.find('y').unwrap()).unwrap();
+    assert_eq!(y.column, 17);
+    assert_eq!(index.utf16_column(y), Some(14));
+    let back = index.position_from_utf16(y.line, 14).unwrap();
+    assert_eq!(back.column, y.column);
+    let e = index.position_from_utf16(y.line, 4).unwrap();
+    assert_eq!(index.offset(e), text.find('é'));
+    let loc = index.location(Range::new(2, 5), None).unwrap();
+    assert_eq!(loc.slice(text), "let");
+    assert_eq!(loc.start.line, pos(2, 0).line);
+}
+
+#[test]
+fn utf16_columns_inside_surrogate_pairs() {
+    let text = "😀x";
+    let index = LineIndex::new(text);
+    let line = NonZeroU64::new(1).unwrap();
+    assert_eq!(index.position_from_utf16(line, 0), Some(pos(1, 0)));
+    assert_eq!(index.position_from_utf16(line, 1), None);
+    let x = index.position_from_utf16(line, 2).unwrap();
+    assert_eq!(x, pos(1, 4));
+    assert_eq!(index.utf16_column(x), Some(2));
+    assert_eq!(index.position_from_utf16(line, 3), Some(pos(1, 5)));
+    assert_eq!(index.position_from_utf16(line, 4), None);
+}
+
+#[test]
+fn columns_out_of_range_or_inside_a_char() {
+    let index = LineIndex::new("€a");
+    assert_eq!(index.utf16_column(pos(1, 1)), None);
+    assert_eq!(index.utf16_column(pos(1, 2)), None);
+    assert_eq!(index.utf16_column(pos(1, 3)), Some(1));
+    assert_eq!(index.utf16_column(pos(1, 4)), Some(2));
+
+    let index = LineIndex::new("a\n€");
+    assert_eq!(index.offset(pos(2, u64::MAX)), None);
+    assert_eq!(index.utf16_column(pos(2, u64::MAX)), None);
+    let line = NonZeroU64::new(2).unwrap();
+    assert_eq!(index.position_from_utf16(line, u64::MAX), None);
+}
@@ -0,0 +1,102 @@
+extern crate res_tree;
+mod common;
+use common::{ident, loc, sum};
+use res_tree::expression::{Binary, Expression, Identifier};
+use res_tree::node::NodeKind;
+use res_tree::node_id::{NodeId, NodeMap, NodeTable};
+use res_tree::visit::Visit;
+use std::ops::ControlFlow;
+
+#[test]
+fn ids_parents_and_lookup() {
+    let program = sum();
+    let table = NodeTable::new(&program);
+    let kinds: Vec<NodeKind> = table.iter().map(|(_, node)| node.kind()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            NodeKind::Program,
+            NodeKind::ExpressionStatement,
+            NodeKind::BinaryExpression,
+            NodeKind::Identifier,
+            NodeKind::Identifier,
+        ]
+    );
+    assert_eq!(table.id_of(&program), Some(table.root()));
+
+    let stmt = table.children(table.root())[0];
+    let binary = table.children(stmt)[0];
+    let right = table.children(binary)[1];
+    assert_eq!(table.get(right).unwrap().loc(), loc(4, 6));
+    assert_eq!(table.parent(right), Some(binary));
+    assert_eq!(table.ancestors(right), vec![binary, stmt, table.root()]);
+    assert_eq!(table.parent(table.root()), None);
+
+    let right_node = table.get(right).unwrap();
+    assert_eq!(table.id_of(right_node), Some(right));
+    let other = ident("bc", 4);
+    assert_eq!(table.id_of(&other), None);
+}
+
+#[test]
+fn side_tables_survive_a_rebuild() {
+    let program = sum();
+    let mut widths = NodeMap::new();
+    {
+        let table = NodeTable::new(&program);
+        for (id, node) in table.iter() {
+            if node.kind() == NodeKind::Identifier {
+                widths.insert(id, node.loc().range.len());
+            }
+        }
+    }
+    let table = NodeTable::new(&program);
+    let found: Vec<(NodeKind, usize)> = widths
+        .iter()
+        .map(|(id, width)| (table.get(id).unwrap().kind(), *width))
+        .collect();
+    assert_eq!(
+        found,
+        vec![(NodeKind::Identifier, 1), (NodeKind::Identifier, 2)]
+    );
+    assert!(!widths.contains(table.root()));
+}
+
+/// Looks up the ID of every node a visitor is handed
+struct Lookup<'a, 'ast: 'a> {
+    table: &'a NodeTable<'ast>,
+    found: Vec<Option<NodeId>>,
+}
+
+impl<'a, 'ast> Visit<'ast> for Lookup<'a, 'ast> {
+    fn visit_expression(&mut self, expr: &'ast Expression) -> ControlFlow<()> {
+        self.found.push(self.table.id_of(expr));
+        res_tree::visit::walk_expression(self, expr)
+    }
+
+    fn visit_binary(&mut self, binary: &'ast Binary) -> ControlFlow<()> {
+        self.found.push(self.table.id_of(binary));
+        res_tree::visit::walk_binary(self, binary)
+    }
+
+    fn visit_identifier(&mut self, ident: &'ast Identifier) -> ControlFlow<()> {
+        self.found.push(self.table.id_of(ident));
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn visitors_find_ids_of_the_nodes_they_are_handed() {
+    let program = sum();
+    let table = NodeTable::new(&program);
+    let mut lookup = Lookup {
+        table: &table,
+        found: Vec::new(),
+    };
+    let _ = lookup.visit_program(&program);
+    let stmt = table.children(table.root())[0];
+    let binary = table.children(stmt)[0];
+    let (a, bc) = (table.children(binary)[0], table.children(binary)[1]);
+    let (binary, a, bc) = (Some(binary), Some(a), Some(bc));
+    assert_eq!(lookup.found, vec![binary, binary, a, a, bc, bc]);
+}
@@ -0,0 +1,70 @@
+extern crate res_tree;
+mod common;
+use common::{ident, loc, number, script};
+use res_tree::expression::{Binary, BinaryOperator, Expression, Identifier, Literal};
+use res_tree::node::{Node, NodeKind};
+use res_tree::node_ref::{NodeMut, NodeRef};
+use res_tree::statement::Statement;
+use res_tree::visit::{self, Visit};
+use std::ops::ControlFlow;
+
+#[derive(Default)]
+struct Leaves<'ast>(Vec<NodeRef<'ast>>);
+
+impl<'ast> Visit<'ast> for Leaves<'ast> {
+    fn visit_identifier(&mut self, ident: &'ast Identifier) -> ControlFlow<()> {
+        self.0.push(ident.into());
+        ControlFlow::Continue(())
+    }
+    fn visit_literal(&mut self, literal: &'ast Literal) -> ControlFlow<()> {
+        self.0.push(literal.into());
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn mixed_nodes_in_one_collection() {
+    // `a + 1;`
+    let program = script(
+        vec![Statement::Expr(Expression::Binary(Box::new(Binary {
+            operator: BinaryOperator::Plus,
+            left: ident("a", 0),
+            right: number("1", 4),
+            loc: loc(0, 5),
+        })))],
+        6,
+    );
+    let mut leaves = Leaves::default();
+    let _ = visit::walk_program(&mut leaves, &program);
+    let kinds: Vec<String> = leaves.0.iter().map(|n| format!("{:?}", n.kind())).collect();
+    assert_eq!(kinds, vec!["Identifier", "Literal"]);
+    assert_eq!(leaves.0[1].loc(), loc(4, 5));
+    match leaves.0[0] {
+        NodeRef::Identifier(ident) => assert_eq!(ident.name, "a"),
+        other => panic!("expected identifier, found {:?}", other),
+    }
+
+    let root = NodeRef::from(&program);
+    assert!(root.ptr_eq(&NodeRef::Program(&program)));
+    assert!(!root.ptr_eq(&leaves.0[0]));
+    assert_eq!(root.children().len(), 1);
+}
+
+#[test]
+fn mutate_through_node_mut() {
+    let mut expr = ident("a", 0);
+    {
+        let node = NodeMut::from(&mut expr);
+        match node.as_ref().kind() {
+            NodeKind::Identifier => (),
+            other => panic!("unexpected {:?}", other),
+        }
+        if let NodeMut::Expression(Expression::Identifier(ident)) = node {
+            ident.name = "b".to_string();
+        }
+    }
+    match expr {
+        Expression::Identifier(ref ident) => assert_eq!(ident.name, "b"),
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,209 @@
+extern crate res_tree;
+mod common;
+use common::{ident, loc, script};
+use res_tree::expression::Expression;
+use res_tree::node::{Node, NodeKind};
+use res_tree::node_ref::NodeRef;
+use res_tree::path::{Edits, Error, Paths};
+use res_tree::statement::{Block, If, Statement};
+use res_tree::{Program, ProgramPart};
+
+fn expr(name: &str, start: u64) -> Statement {
+    Statement::Expr(ident(name, start))
+}
+
+// `a; if (b) c; { d; }`
+fn program() -> Program {
+    script(
+        vec![
+            expr("a", 0),
+            Statement::If(Box::new(If {
+                test: ident("b", 7),
+                consequent: expr("c", 10),
+                alternate: None,
+                loc: loc(3, 12),
+            })),
+            Statement::Block(Block {
+                body: vec![expr("d", 15)],
+                loc: loc(13, 19),
+            }),
+        ],
+        19,
+    )
+}
+
+fn name(stmt: &Statement) -> String {
+    match stmt {
+        Statement::Expr(Expression::Identifier(ref id)) => id.name.clone(),
+        Statement::Empty(_) => ";".to_string(),
+        other => format!("{:?}", other.kind()),
+    }
+}
+
+fn top_level(program: &Program) -> Vec<&Statement> {
+    program
+        .body
+        .iter()
+        .map(|part| match part {
+            ProgramPart::Statement(ref stmt) => stmt,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[test]
+fn parents_and_siblings() {
+    let program = program();
+    let paths = Paths::new(&program);
+    let kinds: Vec<String> = paths
+        .iter()
+        .map(|path| format!("{:?}", path.node().kind()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "ExpressionStatement",
+            "IfStatement",
+            "ExpressionStatement",
+            "BlockStatement",
+            "ExpressionStatement"
+        ]
+    );
+
+    let c = paths.get(2).unwrap();
+    assert!(c.is_slot());
+    match c.parent().kind() {
+        NodeKind::IfStatement => (),
+        other => panic!("unexpected parent {:?}", other),
+    }
+    let if_path = paths.parent_path(c).unwrap();
+    assert!(if_path.node().ptr_eq(&paths.get(1).unwrap().node()));
+    assert_eq!(if_path.siblings().len(), 3);
+    assert_eq!(if_path.index(), 1);
+    assert!(paths.parent_path(if_path).is_none());
+
+    let d = paths.get(4).unwrap();
+    assert!(!d.is_slot());
+    match d.parent() {
+        NodeRef::Block(block) => assert_eq!(block.loc, loc(13, 19)),
+        other => panic!("unexpected parent {:?}", other),
+    }
+    assert_eq!(paths.ancestors(d).len(), 1);
+    assert!(paths.find(d.node()).unwrap().node().ptr_eq(&d.node()));
+}
+
+#[test]
+fn edits_keep_the_tree_valid() {
+    let mut program = program();
+    let mut edits = Edits::new();
+    {
+        let paths = Paths::new(&program);
+        edits.insert_before(paths.get(0).unwrap(), expr("x", 0));
+        edits.remove(paths.get(2).unwrap());
+        edits.replace(paths.get(4).unwrap(), expr("e", 15));
+        edits.insert_after(paths.get(4).unwrap(), expr("f", 15));
+    }
+    edits.apply(&mut program).unwrap();
+
+    let top = top_level(&program);
+    let names: Vec<String> = top.iter().map(|stmt| name(stmt)).collect();
+    assert_eq!(names, vec!["x", "a", "IfStatement", "BlockStatement"]);
+    match top[2] {
+        Statement::If(ref stmt) => {
+            assert_eq!(name(&stmt.consequent), ";");
+            assert_eq!(stmt.consequent.loc(), loc(10, 11));
+        }
+        _ => unreachable!(),
+    }
+    match top[3] {
+        Statement::Block(ref block) => {
+            let names: Vec<String> = block.body.iter().map(name).collect();
+            assert_eq!(names, vec!["e", "f"]);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn inserting_into_a_slot_wraps_it_in_a_block() {
+    let mut program = program();
+    let mut edits = Edits::new();
+    {
+        let paths = Paths::new(&program);
+        edits.insert_after(paths.get(2).unwrap(), expr("z", 10));
+    }
+    edits.apply(&mut program).unwrap();
+    match top_level(&program)[1] {
+        Statement::If(ref stmt) => match stmt.consequent {
+            Statement::Block(ref block) => {
+                let names: Vec<String> = block.body.iter().map(name).collect();
+                assert_eq!(names, vec!["c", "z"]);
+                assert_eq!(block.loc, loc(10, 11));
+            }
+            ref other => panic!("expected a block, found {:?}", other),
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn expressions_have_parents_and_can_be_replaced() {
+    let mut program = program();
+    let mut edits = Edits::new();
+    {
+        let paths = Paths::new(&program);
+        let names: Vec<String> = paths
+            .expressions()
+            .iter()
+            .map(|path| name(&Statement::Expr(path.node().clone())))
+            .collect();
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+        let b = paths.expressions()[1];
+        match b.parent() {
+            NodeRef::Statement(Statement::If(_)) => (),
+            other => panic!("unexpected parent {:?}", other),
+        }
+        assert!(paths
+            .statement_of(&b)
+            .unwrap()
+            .node()
+            .ptr_eq(&paths.get(1).unwrap().node()));
+        edits.replace_expression(&b, ident("x", 7));
+    }
+    edits.apply(&mut program).unwrap();
+    match top_level(&program)[1] {
+        Statement::If(ref stmt) => assert_eq!(name(&Statement::Expr(stmt.test.clone())), "x"),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn stale_edits_are_rejected() {
+    let mut program = program();
+    let mut edits = Edits::new();
+    {
+        let paths = Paths::new(&program);
+        edits.remove(paths.get(1).unwrap());
+        edits.replace_expression(&paths.expressions()[3], ident("x", 15));
+    }
+    // drop `a;`, so the `if` is no longer the second statement
+    program.body.remove(0);
+    let before = program.clone();
+    match edits.apply(&mut program) {
+        Err(Error::Mismatch {
+            expected: NodeKind::IfStatement,
+            found: NodeKind::BlockStatement,
+            ..
+        }) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(program, before);
+
+    let mut edits = Edits::new();
+    {
+        let paths = Paths::new(&before);
+        edits.insert_after(paths.get(3).unwrap(), expr("x", 15));
+    }
+    let mut shorter = script(vec![expr("a", 0)], 2);
+    assert_eq!(edits.apply(&mut shorter), Err(Error::Missing));
+}
@@ -0,0 +1,310 @@
+extern crate res_tree;
+mod common;
+use common::{id, loc, script};
+use res_tree::codegen::{print_with_map, PrintOptions};
+use res_tree::expression::{
+    Binary, BinaryOperator, Expression, Literal, LiteralValue, Member, Parent,
+};
+use res_tree::node::{FileId, Position, SourceLocation, SourceRegistry};
+use res_tree::source_map::{decode_mappings, Error, Mapping, Original, SourceMap};
+use res_tree::statement::Statement;
+use std::num::NonZeroU64;
+
+fn at(file: FileId, line: u64, column: u64, start: u64, end: u64) -> SourceLocation {
+    let mut loc = loc(start, end);
+    loc.source = Some(file);
+    loc.start.line = NonZeroU64::new(line).unwrap();
+    loc.start.column = column;
+    loc.end.line = loc.start.line;
+    loc.end.column = column + end - start;
+    loc
+}
+
+fn position(line: u64, column: u64) -> Position {
+    Position {
+        line: NonZeroU64::new(line).unwrap(),
+        column,
+    }
+}
+
+fn renamed(name: &str, loc: SourceLocation) -> Expression {
+    let mut ident = id(name, 0);
+    ident.loc = loc;
+    Expression::Identifier(Box::new(ident))
+}
+
+#[test]
+fn maps_nodes_and_original_names() {
+    let mut registry = SourceRegistry::new();
+    let file = registry.add_with_text("input.js", "value + 1;\nwidth;".to_string());
+    let program = script(
+        vec![
+            Statement::Expr(Expression::Binary(Box::new(Binary {
+                operator: BinaryOperator::Plus,
+                left: renamed("v", at(file, 1, 0, 0, 5)),
+                right: Expression::Literal(Box::new(Literal {
+                    value: LiteralValue::Number("1".to_string()),
+                    loc: at(file, 1, 8, 8, 9),
+                })),
+                loc: at(file, 1, 0, 0, 9),
+            }))),
+            Statement::Expr(renamed("w", at(file, 2, 0, 11, 16))),
+        ],
+        17,
+    );
+    let (code, map) = print_with_map(&program, PrintOptions::default(), &registry);
+    assert_eq!(code, "v + 1;\nw;\n");
+    assert_eq!(map.names, vec!["value", "width"]);
+    assert_eq!(map.encode_mappings(), "AAAAA,IAAQ;AACRC");
+    assert_eq!(
+        map.to_json(),
+        r#"{"version":3,"sources":["input.js"],"sourcesContent":["value + 1;\nwidth;"],"names":["value","width"],"mappings":"AAAAA,IAAQ;AACRC"}"#
+    );
+}
+
+#[test]
+fn columns_count_utf16() {
+    let mut registry = SourceRegistry::new();
+    let file = registry.add_with_text("input.js", "'é' + value".to_string());
+    let program = script(
+        vec![Statement::Expr(Expression::Binary(Box::new(Binary {
+            operator: BinaryOperator::Plus,
+            left: Expression::Literal(Box::new(Literal {
+                value: LiteralValue::String("é".to_string()),
+                loc: at(file, 1, 0, 0, 4),
+            })),
+            right: renamed("v", at(file, 1, 7, 7, 12)),
+            loc: at(file, 1, 0, 0, 12),
+        })))],
+        12,
+    );
+    let (code, map) = print_with_map(&program, PrintOptions::default(), &registry);
+    assert_eq!(code, "\"é\" + v;\n");
+    assert_eq!(
+        map.mappings.last(),
+        Some(&Mapping {
+            generated: position(1, 6),
+            original: Some(Original {
+                source: 0,
+                position: position(1, 6),
+                name: Some(0),
+            }),
+        })
+    );
+}
+
+#[test]
+fn files_without_text_are_not_mapped() {
+    // without its text, the byte column of `b` can't be turned into
+    // the UTF-16 column a source map needs
+    let mut registry = SourceRegistry::new();
+    let known = registry.add_with_text("known.js", "value".to_string());
+    let unknown = registry.add("unknown.js");
+    let program = script(
+        vec![
+            Statement::Expr(renamed("a", at(known, 1, 0, 0, 5))),
+            Statement::Expr(renamed("b", at(unknown, 1, 7, 7, 12))),
+        ],
+        12,
+    );
+    let (code, map) = print_with_map(&program, PrintOptions::default(), &registry);
+    assert_eq!(code, "a;\nb;\n");
+    assert_eq!(map.sources, vec!["known.js", "unknown.js"]);
+    assert_eq!(
+        map.mappings,
+        vec![Mapping {
+            generated: position(1, 0),
+            original: Some(Original {
+                source: 0,
+                position: position(1, 0),
+                name: Some(0),
+            }),
+        }]
+    );
+}
+
+#[test]
+fn maps_literal_objects() {
+    let mut registry = SourceRegistry::new();
+    let file = registry.add_with_text("input.js", "x + (1).toFixed".to_string());
+    let member = Expression::Member(Box::new(Member {
+        object: Parent::Expr(Expression::Literal(Box::new(Literal {
+            value: LiteralValue::Number("1".to_string()),
+            loc: at(file, 1, 5, 5, 6),
+        }))),
+        property: renamed("toFixed", at(file, 1, 8, 8, 15)),
+        computed: false,
+        optional: false,
+        loc: at(file, 1, 4, 4, 15),
+    }));
+    let program = script(
+        vec![Statement::Expr(Expression::Binary(Box::new(Binary {
+            operator: BinaryOperator::Plus,
+            left: renamed("x", at(file, 1, 0, 0, 1)),
+            right: member,
+            loc: at(file, 1, 0, 0, 15),
+        })))],
+        15,
+    );
+    let (code, map) = print_with_map(&program, PrintOptions::default(), &registry);
+    assert_eq!(code, "x + (1).toFixed;\n");
+    let literal = map
+        .mappings
+        .iter()
+        .find(|m| m.generated == position(1, 5))
+        .and_then(|m| m.original.as_ref())
+        .map(|o| o.position);
+    assert_eq!(literal, Some(position(1, 5)));
+}
+
+fn mapping(line: u64, column: u64, original: Option<(u64, u64, Option<u32>)>) -> Mapping {
+    Mapping {
+        generated: position(line, column),
+        original: original.map(|(line, column, name)| Original {
+            source: 0,
+            position: position(line, column),
+            name,
+        }),
+    }
+}
+
+#[test]
+fn decodes_what_it_encodes() {
+    let map = SourceMap {
+        sources: vec!["a.js".to_string()],
+        names: vec!["a".to_string(), "b".to_string()],
+        mappings: vec![
+            mapping(1, 0, Some((3, 100, Some(1)))),
+            mapping(1, 5, None),
+            mapping(1, 70000, Some((1, 0, Some(0)))),
+            mapping(4, 2, Some((2, 7, None))),
+        ],
+        ..SourceMap::default()
+    };
+    let encoded = map.encode_mappings();
+    assert_eq!(decode_mappings(&encoded).unwrap(), map.mappings);
+
+    assert!(matches!(decode_mappings("AA"), Err(Error::Segment)));
+    assert!(matches!(decode_mappings("AAAg"), Err(Error::Segment)));
+    assert!(matches!(decode_mappings("A!"), Err(Error::Base64('!'))));
+    assert!(matches!(decode_mappings("D"), Err(Error::Index)));
+}
+
+#[test]
+fn rejects_hostile_mappings() {
+    // twelve full digits and a last one with more than the 4 bits left
+    assert!(matches!(
+        decode_mappings("////////////Q"),
+        Err(Error::Segment)
+    ));
+    assert!(matches!(
+        decode_mappings("////////////P"),
+        Err(Error::Index)
+    ));
+    // a delta of i64::MAX on top of source 1
+    let max = "+///////////P";
+    assert!(matches!(
+        decode_mappings(&format!("ACAA,A{}AA", max)),
+        Err(Error::Index)
+    ));
+    // a long run of digits can't shift past 64 bits
+    assert!(matches!(
+        decode_mappings(&"/".repeat(100)),
+        Err(Error::Segment)
+    ));
+}
+
+#[test]
+fn looks_up_and_composes() {
+    let first = SourceMap {
+        sources: vec!["a.js".to_string()],
+        names: vec!["value".to_string()],
+        mappings: vec![
+            mapping(1, 0, Some((1, 4, Some(0)))),
+            mapping(1, 6, Some((2, 0, None))),
+        ],
+        ..SourceMap::default()
+    };
+    assert_eq!(
+        first.lookup(position(1, 3)).unwrap().position,
+        position(1, 4)
+    );
+    assert_eq!(
+        first.lookup(position(1, 9)).unwrap().position,
+        position(2, 0)
+    );
+    assert_eq!(first.lookup(position(2, 0)), None);
+
+    let second = SourceMap {
+        file: Some("c.js".to_string()),
+        sources: vec!["b.js".to_string()],
+        names: vec!["x".to_string()],
+        mappings: vec![
+            mapping(1, 2, Some((1, 0, None))),
+            mapping(1, 3, Some((1, 7, Some(0)))),
+            mapping(1, 5, Some((3, 0, None))),
+        ],
+        ..SourceMap::default()
+    };
+    let composed = first.compose(&second);
+    assert_eq!(composed.file.as_deref(), Some("c.js"));
+    assert_eq!(composed.sources, first.sources);
+    assert_eq!(composed.names, vec!["value", "x"]);
+    assert_eq!(
+        composed.mappings,
+        vec![
+            mapping(1, 2, Some((1, 4, Some(0)))),
+            mapping(1, 3, Some((2, 0, Some(1)))),
+            mapping(1, 5, None),
+        ]
+    );
+}
+
+#[test]
+fn remaps_locations_into_the_original() {
+    let mut registry = SourceRegistry::new();
+    let file = registry.add_with_text("input.js", "value + 1;\nwidth;".to_string());
+    let program = script(
+        vec![Statement::Expr(Expression::Binary(Box::new(Binary {
+            operator: BinaryOperator::Plus,
+            left: renamed("v", at(file, 1, 0, 0, 5)),
+            right: Expression::Literal(Box::new(Literal {
+                value: LiteralValue::Number("1".to_string()),
+                loc: at(file, 1, 8, 8, 9),
+            })),
+            loc: at(file, 1, 0, 0, 9),
+        })))],
+        17,
+    );
+    let (code, map) = print_with_map(&program, PrintOptions::default(), &registry);
+    let output = registry.add_with_text("output.js", code);
+    assert_eq!(
+        at(output, 1, 4, 4, 5).remap(&map, &registry),
+        Some(at(file, 1, 8, 8, 9))
+    );
+    assert_eq!(at(output, 3, 0, 10, 10).remap(&map, &registry), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn reads_json() {
+    let map = SourceMap {
+        file: Some("out.js".to_string()),
+        sources: vec!["a.js".to_string()],
+        sources_content: vec![Some("a\n\"b\"".to_string())],
+        names: vec!["a".to_string()],
+        mappings: vec![mapping(1, 0, Some((1, 0, Some(0))))],
+    };
+    assert_eq!(SourceMap::from_json(&map.to_json()).unwrap(), map);
+
+    let rooted =
+        r#"{"version":3,"sourceRoot":"src","sources":["a.js"],"names":[],"mappings":";AAAA"}"#;
+    let rooted = SourceMap::from_json(rooted).unwrap();
+    assert_eq!(rooted.sources, vec!["src/a.js"]);
+    assert_eq!(rooted.mappings, vec![mapping(2, 0, Some((1, 0, None)))]);
+
+    let old = r#"{"version":2,"sources":[],"names":[],"mappings":""}"#;
+    assert!(matches!(SourceMap::from_json(old), Err(Error::Version(2))));
+    let dangling = r#"{"version":3,"sources":[],"names":[],"mappings":"AAAA"}"#;
+    assert!(matches!(SourceMap::from_json(dangling), Err(Error::Index)));
+}
@@ -0,0 +1,36 @@
+extern crate res_tree;
+mod common;
+use common::loc;
+use res_tree::node::SourceRegistry;
+
+#[test]
+fn file_ids_round_trip() {
+    let mut registry = SourceRegistry::new();
+    let a = registry.add("a.js");
+    let b = registry.add_with_text("b.js", "b;".to_string());
+    assert_ne!(a, b);
+    assert_eq!(registry.add("a.js"), a);
+    assert_eq!(registry.add_with_text("a.js", "a;".to_string()), a);
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.lookup("b.js"), Some(b));
+    assert_eq!(registry.lookup("c.js"), None);
+    assert_eq!(registry.name(a), Some("a.js"));
+    assert_eq!(registry.text(a), Some("a;"));
+    for (id, file) in registry.iter() {
+        assert_eq!(registry.lookup(&file.name), Some(id));
+    }
+}
+
+#[test]
+fn locations_are_copied() {
+    let mut registry = SourceRegistry::new();
+    let mut first = loc(0, 2);
+    first.source = Some(registry.add_with_text("a.js", "a;".to_string()));
+    let second = first;
+    first.range.end = 1;
+    assert_eq!(second.range.end, 2);
+    assert_eq!(second.source, first.source);
+    let file = second.source.unwrap();
+    assert_eq!(registry.name(file), Some("a.js"));
+    assert_eq!(second.get(registry.text(file).unwrap()), Some("a;"));
+}
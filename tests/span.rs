@@ -0,0 +1,96 @@
+extern crate res_tree;
+mod common;
+use common::{loc, pos, sum};
+use res_tree::node::{Node, NodeKind, Range, SourceLocation};
+
+#[test]
+fn span_algebra() {
+    assert!(pos(1) < pos(2));
+    let outer = loc(0, 6);
+    let inner = loc(4, 6);
+    assert!(outer.contains(pos(6)));
+    assert!(!inner.contains(pos(3)));
+    assert!(outer.contains_loc(&inner));
+    assert!(!inner.contains_loc(&outer));
+    assert_eq!(loc(0, 1).merge(&inner), outer);
+    assert!(loc(0, 2).overlaps(&loc(1, 3)));
+    assert!(!loc(0, 1).overlaps(&loc(1, 3)));
+}
+
+#[test]
+fn ranges_on_user_text() {
+    let text = "let é = 1;";
+    assert_eq!(Range::new(4, 6).len(), 2);
+    assert_eq!(Range::new(6, 4).len(), 0);
+    assert_eq!(Range::new(4, 6).get(text), Some("é"));
+    assert_eq!(loc(0, 3).get(text), Some("let"));
+    assert_eq!(Range::new(4, 5).get(text), None);
+    assert_eq!(Range::new(6, 4).get(text), None);
+    assert_eq!(Range::new(8, 20).get(text), None);
+}
+
+#[test]
+fn innermost_node() {
+    let program = sum();
+    let kinds: Vec<_> = program
+        .nodes_at(pos(5))
+        .iter()
+        .map(|n| format!("{:?}", n.kind()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "Program",
+            "ExpressionStatement",
+            "BinaryExpression",
+            "Identifier"
+        ]
+    );
+    let found = program.node_at(pos(2)).unwrap();
+    assert_eq!(found.loc(), loc(0, 6));
+    assert!(program.node_at(pos(9)).is_none());
+    match program.node_at(pos(0)).unwrap().kind() {
+        NodeKind::Identifier => (),
+        other => panic!("expected identifier, found {:?}", other),
+    }
+}
+
+fn count(node: &dyn Node) -> usize {
+    1 + node.children().into_iter().map(count).sum::<usize>()
+}
+
+#[test]
+fn children_in_source_order() {
+    let program = sum();
+    assert_eq!(count(&program), 5);
+    let stmt = program.children()[0];
+    let binary = stmt.children()[0];
+    let columns: Vec<_> = binary
+        .children()
+        .iter()
+        .map(|c| c.loc().start.column)
+        .collect();
+    assert_eq!(columns, vec![0, 4]);
+}
+
+struct Leaf;
+
+impl Node for Leaf {
+    fn loc(&self) -> SourceLocation {
+        loc(0, 1)
+    }
+
+    fn kind(&self) -> NodeKind {
+        NodeKind::Identifier
+    }
+}
+
+#[test]
+fn nodes_default_to_no_children() {
+    assert!(Leaf.children().is_empty());
+    let program = sum();
+    let mut columns = Vec::new();
+    program.children()[0].children()[0]
+        .for_each_child(&mut |child| columns.push(child.loc().start.column));
+    assert_eq!(columns, vec![0, 4]);
+}
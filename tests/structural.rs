@@ -0,0 +1,79 @@
+extern crate res_tree;
+mod common;
+use common::{ident, loc, number};
+use res_tree::expression::{Binary, BinaryOperator, Expression, Member, Parent};
+use res_tree::structural::{structural_eq, structural_hash, Structural, StructuralOptions};
+
+fn binary(operator: BinaryOperator, left: &str, right: &str, start: u64) -> Expression {
+    Expression::Binary(Box::new(Binary {
+        operator,
+        left: ident(left, start),
+        right: ident(right, start + 4),
+        loc: loc(start, start + 5),
+    }))
+}
+
+#[test]
+fn locations_are_ignored() {
+    let a = binary(BinaryOperator::Plus, "a", "b", 0);
+    let b = binary(BinaryOperator::Plus, "a", "b", 10);
+    assert_ne!(a, b);
+    assert!(structural_eq(&a, &b));
+    let options = StructuralOptions::default();
+    assert_eq!(structural_hash(&a, options), structural_hash(&b, options));
+
+    let minus = binary(BinaryOperator::Minus, "a", "b", 0);
+    assert!(!structural_eq(&a, &minus));
+    assert_ne!(
+        structural_hash(&a, options),
+        structural_hash(&minus, options)
+    );
+    assert!(!structural_eq(&ident("a", 0), &number("1", 0)));
+}
+
+#[test]
+fn names_can_be_ignored() {
+    let a = binary(BinaryOperator::Plus, "a", "b", 0);
+    let renamed = binary(BinaryOperator::Plus, "x", "y", 3);
+    assert!(!structural_eq(&a, &renamed));
+    let options = StructuralOptions { ignore_names: true };
+    assert!(a.structural_eq(&renamed, options));
+    assert_eq!(
+        structural_hash(&a, options),
+        structural_hash(&renamed, options)
+    );
+    assert_ne!(
+        structural_hash(&a, options),
+        structural_hash(&number("1", 0), options)
+    );
+}
+
+fn member(object: &str, property: &str, computed: bool) -> Expression {
+    Expression::Member(Box::new(Member {
+        object: Parent::Expr(ident(object, 0)),
+        property: ident(property, 2),
+        computed,
+        optional: false,
+        loc: loc(0, 5),
+    }))
+}
+
+#[test]
+fn property_names_are_kept() {
+    let options = StructuralOptions { ignore_names: true };
+    assert!(!member("a", "foo", false).structural_eq(&member("a", "bar", false), options));
+    assert_ne!(
+        structural_hash(&member("a", "foo", false), options),
+        structural_hash(&member("a", "bar", false), options)
+    );
+    assert!(member("a", "foo", false).structural_eq(&member("b", "foo", false), options));
+    assert!(member("a", "foo", true).structural_eq(&member("a", "bar", true), options));
+}
+
+// changing this value breaks caches keyed on the hash
+#[test]
+fn hash_is_stable() {
+    let a = binary(BinaryOperator::Plus, "a", "b", 0);
+    let hash = structural_hash(&a, StructuralOptions::default());
+    assert_eq!(format!("{:016x}", hash), "40d1fa9dcf13d134");
+}
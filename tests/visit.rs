@@ -0,0 +1,211 @@
+extern crate res_tree;
+mod common;
+use common::{id, ident, loc, number, script};
+use res_tree::expression::{
+    Call, Expression, Identifier, Literal, LiteralValue, Parent, SpreadElement, Spreadable, Yield,
+};
+use res_tree::statement::{
+    Declaration, FunctionBody, FunctionBodyPart, Statement, VariableDeclaration,
+    VariableDeclarator, VariableKind,
+};
+use res_tree::visit::{self, Visit};
+use res_tree::visit_mut::VisitMut;
+use res_tree::{Function, Pattern, Program};
+use std::ops::ControlFlow;
+
+// `let x = f(y, ...z, 1);`
+fn program() -> Program {
+    script(
+        vec![Statement::Decl(Declaration::Variable(
+            VariableDeclaration {
+                kind: VariableKind::Let,
+                declarations: vec![VariableDeclarator {
+                    id: Pattern::Ident(id("x", 4)),
+                    init: Some(Expression::Call(Box::new(Call {
+                        callee: Parent::Expr(ident("f", 8)),
+                        arguments: vec![
+                            Spreadable::Expr(ident("y", 10)),
+                            Spreadable::Spread(SpreadElement {
+                                argument: ident("z", 16),
+                                loc: loc(13, 17),
+                            }),
+                            Spreadable::Expr(number("1", 19)),
+                        ],
+                        optional: false,
+                        loc: loc(8, 21),
+                    }))),
+                    loc: loc(4, 21),
+                }],
+                loc: loc(0, 22),
+            },
+        ))],
+        22,
+    )
+}
+
+#[derive(Default)]
+struct Names(Vec<String>);
+
+impl<'ast> Visit<'ast> for Names {
+    fn visit_identifier(&mut self, ident: &'ast Identifier) -> ControlFlow<()> {
+        self.0.push(ident.name.clone());
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Default)]
+struct Numbers(Vec<String>);
+
+impl<'ast> Visit<'ast> for Numbers {
+    fn visit_literal(&mut self, literal: &'ast Literal) -> ControlFlow<()> {
+        if let LiteralValue::Number(ref n) = literal.value {
+            self.0.push(n.clone());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Default)]
+struct Callees<'ast>(Vec<&'ast Parent>, usize);
+
+impl<'ast> Visit<'ast> for Callees<'ast> {
+    fn visit_call(&mut self, call: &'ast Call) -> ControlFlow<()> {
+        self.0.push(&call.callee);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expression(&mut self, expr: &'ast Expression) -> ControlFlow<()> {
+        self.1 += 1;
+        visit::walk_expression(self, expr)
+    }
+}
+
+#[test]
+fn visits_every_identifier_in_order() {
+    let mut names = Names::default();
+    let _ = names.visit_program(&program());
+    assert_eq!(names.0, vec!["x", "f", "y", "z"]);
+}
+
+#[test]
+fn overriding_without_walking_stops_descent() {
+    let program = program();
+    let mut callees = Callees::default();
+    let _ = callees.visit_program(&program);
+    assert_eq!(callees.0.len(), 1);
+    assert_eq!(callees.1, 1);
+}
+
+struct Rewrite<'a>(&'a str, &'a str);
+
+impl<'a> VisitMut for Rewrite<'a> {
+    fn visit_identifier(&mut self, ident: &mut Identifier) -> ControlFlow<()> {
+        if ident.name == self.0 {
+            ident.name = self.1.to_string();
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_literal(&mut self, literal: &mut Literal) -> ControlFlow<()> {
+        if let LiteralValue::Number(ref mut n) = literal.value {
+            n.push('0');
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn visit_mut_rewrites_in_place() {
+    let mut program = program();
+    let _ = Rewrite("y", "why").visit_program(&mut program);
+    let mut names = Names::default();
+    let _ = names.visit_program(&program);
+    assert_eq!(names.0, vec!["x", "f", "why", "z"]);
+    let mut numbers = Numbers::default();
+    let _ = numbers.visit_program(&program);
+    assert_eq!(numbers.0, vec!["10"]);
+}
+
+fn call(callee: &str, start: u64, arg: Expression) -> Expression {
+    Expression::Call(Box::new(Call {
+        callee: Parent::Expr(ident(callee, start)),
+        arguments: vec![Spreadable::Expr(arg)],
+        optional: false,
+        loc: loc(start, start + callee.len() as u64 + 3),
+    }))
+}
+
+#[derive(Default)]
+struct FirstEval(usize);
+
+impl<'ast> Visit<'ast, &'ast Call> for FirstEval {
+    fn visit_call(&mut self, call: &'ast Call) -> ControlFlow<&'ast Call> {
+        self.0 += 1;
+        if let Parent::Expr(Expression::Identifier(ref callee)) = call.callee {
+            if callee.name == "eval" {
+                return ControlFlow::Break(call);
+            }
+        }
+        visit::walk_call(self, call)
+    }
+}
+
+struct ContainsYield;
+
+impl<'ast> Visit<'ast> for ContainsYield {
+    fn visit_expression(&mut self, expr: &'ast Expression) -> ControlFlow<()> {
+        match expr {
+            Expression::Yield(_) => ControlFlow::Break(()),
+            Expression::Function(_) | Expression::ArrowFunction(_) => ControlFlow::Continue(()),
+            _ => visit::walk_expression(self, expr),
+        }
+    }
+}
+
+#[test]
+fn break_stops_the_traversal_with_a_value() {
+    // `f(eval(a)); eval(b);`
+    let program = script(
+        vec![
+            Statement::Expr(call("f", 0, call("eval", 2, ident("a", 7)))),
+            Statement::Expr(call("eval", 12, ident("b", 17))),
+        ],
+        20,
+    );
+    let mut finder = FirstEval::default();
+    let found = finder.visit_program(&program).break_value().unwrap();
+    match found.arguments[0] {
+        Spreadable::Expr(Expression::Identifier(ref arg)) => assert_eq!(arg.name, "a"),
+        _ => panic!("expected an identifier argument"),
+    }
+    assert_eq!(finder.0, 2);
+}
+
+#[test]
+fn continue_without_walking_skips_children() {
+    let yield_one = || {
+        Expression::Yield(Box::new(Yield {
+            argument: Some(number("1", 6)),
+            delegate: false,
+            loc: loc(0, 7),
+        }))
+    };
+    // `(function() { yield 1 });`
+    let nested = script(
+        vec![Statement::Expr(Expression::Function(Box::new(Function {
+            id: None,
+            params: Vec::new(),
+            body: FunctionBody {
+                body: vec![FunctionBodyPart::Statement(Statement::Expr(yield_one()))],
+                loc: loc(0, 1),
+            },
+            generator: true,
+            _async: false,
+            loc: loc(0, 1),
+        })))],
+        1,
+    );
+    assert!(ContainsYield.visit_program(&nested).is_continue());
+    let direct = script(vec![Statement::Expr(yield_one())], 1);
+    assert!(ContainsYield.visit_program(&direct).is_break());
+}